@@ -0,0 +1,437 @@
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use std::env;
+
+use crate::{
+    ChainContributionState, ChainDevState, ChainOrgMembership, ChainOrgState, ChainRepoState,
+    ChainScoringTier,
+};
+
+// Program the badges are read from, overridable for local validators with a fresh deploy
+fn program_id() -> anyhow::Result<[u8; 32]> {
+    let program_id = env::var("GhostCheck_Program_Id")
+        .unwrap_or_else(|_| "GQsPhnZApw9MY7khsbRLtL5mAGpmMn8wp8CFNDPTxGQr".to_string());
+
+    crate::decode_wallet(&program_id)
+}
+
+fn rpc_url() -> String {
+    env::var("GhostCheck_Rpc_Url").unwrap_or_else(|_| "http://127.0.0.1:8899".to_string())
+}
+
+// Anchor account discriminator, first 8 bytes of sha256("account:<Name>")
+fn account_discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("account:{name}"));
+    hash[..8].try_into().unwrap()
+}
+
+async fn rpc_call(
+    client: &Client,
+    method: &str,
+    params: serde_json::Value,
+) -> anyhow::Result<serde_json::Value> {
+    let response: serde_json::Value = client
+        .post(rpc_url())
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(error) = response.get("error") {
+        anyhow::bail!("RPC {method} failed: {error}");
+    }
+    Ok(response["result"].clone())
+}
+
+fn decode_account_data(account: &serde_json::Value) -> anyhow::Result<Vec<u8>> {
+    let data = account["data"][0]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Account data is not base64 encoded"))?;
+
+    Ok(BASE64.decode(data)?)
+}
+
+async fn get_account_data(client: &Client, address: &[u8; 32]) -> anyhow::Result<Vec<u8>> {
+    let result = rpc_call(
+        client,
+        "getAccountInfo",
+        serde_json::json!([bs58::encode(address).into_string(), {"encoding": "base64"}]),
+    )
+    .await?;
+    if result["value"].is_null() {
+        anyhow::bail!("Account not found");
+    }
+
+    decode_account_data(&result["value"])
+}
+
+// Data of every ghost_check `account_name` account matching each (offset, bytes) filter
+async fn get_program_accounts(
+    client: &Client,
+    account_name: &str,
+    memcmp: &[(usize, &[u8])],
+) -> anyhow::Result<Vec<Vec<u8>>> {
+    let mut filters = vec![
+        serde_json::json!({"memcmp": {"offset": 0, "bytes": bs58::encode(account_discriminator(account_name)).into_string()}}),
+    ];
+    filters.extend(memcmp.iter().map(|(offset, bytes)| {
+        serde_json::json!({"memcmp": {"offset": offset, "bytes": bs58::encode(bytes).into_string()}})
+    }));
+
+    let result = rpc_call(
+        client,
+        "getProgramAccounts",
+        serde_json::json!([
+            bs58::encode(program_id()?).into_string(),
+            {
+                "encoding": "base64",
+                "filters": filters,
+            },
+        ]),
+    )
+    .await?;
+
+    result
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Unexpected getProgramAccounts response"))?
+        .iter()
+        .map(|keyed| decode_account_data(&keyed["account"]))
+        .collect()
+}
+
+// Borsh reader over an account's data, only what the ghost_check states and Core assets need
+struct AccountReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> AccountReader<'a> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.data.len() < len {
+            anyhow::bail!("Account data too short");
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn i64(&mut self) -> anyhow::Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn pubkey(&mut self) -> anyhow::Result<[u8; 32]> {
+        Ok(self.take(32)?.try_into()?)
+    }
+
+    fn bytes(&mut self) -> anyhow::Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+}
+
+// Reputation levels above 1 in ScoringParams, SCORING_TIERS in the program
+const SCORING_TIERS: usize = 4;
+
+// Offset of DevState.asset_address: discriminator + dev_addr
+const DEV_STATE_ASSET_OFFSET: usize = 8 + 32;
+// Offset of RepoState.dev_badge: discriminator + owner
+const REPO_STATE_DEV_BADGE_OFFSET: usize = 8 + 32;
+// Offset of ContributionState.dev_badge: discriminator + contributor
+const CONTRIBUTION_STATE_DEV_BADGE_OFFSET: usize = 8 + 32;
+// Offset of OrgState.org_badge: discriminator + authority
+const ORG_STATE_BADGE_OFFSET: usize = 8 + 32;
+// Offset of OrgMembership.member_badge: discriminator + org_badge + dev + dev_badge
+const ORG_MEMBERSHIP_BADGE_OFFSET: usize = 8 + 32 * 3;
+
+fn parse_dev_state(data: &[u8]) -> anyhow::Result<ChainDevState> {
+    let mut reader = AccountReader { data };
+    reader.take(8)?; // discriminator
+
+    Ok(ChainDevState {
+        dev_addr: reader.pubkey()?,
+        asset_address: reader.pubkey()?,
+        hashed_username: reader.pubkey()?,
+        repo_count: reader.u32()?,
+        owned_repo_count: reader.u32()?,
+        total_stars: reader.u32()?,
+        total_commits: reader.u32()?,
+        prs_merged: reader.u32()?,
+        issues_closed: reader.u32()?,
+        followers: reader.u32()?,
+        account_age_days: reader.u32()?,
+        reputation_level: reader.u8()?,
+        verified_repos: reader.u64()?,
+        vouch_count: reader.u64()?,
+        vouch_score: reader.u64()?,
+        vouches_given: reader.u32()?,
+        last_updated: reader.i64()?,
+    })
+}
+
+fn parse_repo_state(data: &[u8]) -> anyhow::Result<ChainRepoState> {
+    let mut reader = AccountReader { data };
+    reader.take(8)?; // discriminator
+
+    let owner = reader.pubkey()?;
+    let dev_badge = reader.pubkey()?;
+    reader.pubkey()?; // hashed_username
+    let repo_name = reader.bytes()?;
+    let stars = reader.u32()?;
+    let commits = reader.u32()?;
+    let forks = reader.u32()?;
+    let open_issues = reader.u32()?;
+    let is_fork = reader.u8()? == 1;
+    let lang1 = reader.bytes()?;
+    let lang2 = reader.bytes()?;
+    let last_updated = reader.i64()?;
+    reader.u64()?; // nonce
+    reader.u8()?; // bump
+    let badge_bump = reader.u8()?;
+
+    Ok(ChainRepoState {
+        owner,
+        dev_badge,
+        repo_name,
+        stars,
+        commits,
+        forks,
+        open_issues,
+        is_fork,
+        lang1,
+        lang2,
+        last_updated,
+        badge_bump,
+    })
+}
+
+fn parse_contribution_state(data: &[u8]) -> anyhow::Result<ChainContributionState> {
+    let mut reader = AccountReader { data };
+    reader.take(8)?; // discriminator
+
+    let contributor = reader.pubkey()?;
+    let dev_badge = reader.pubkey()?;
+    reader.pubkey()?; // hashed_username
+    let hashed_repo = reader.pubkey()?;
+    let repo_full_name = reader.bytes()?;
+    let prs_merged = reader.u32()?;
+    let commits = reader.u32()?;
+    let last_updated = reader.i64()?;
+    reader.u64()?; // nonce
+    reader.u8()?; // bump
+    let badge_bump = reader.u8()?;
+
+    Ok(ChainContributionState {
+        contributor,
+        dev_badge,
+        hashed_repo,
+        repo_full_name,
+        prs_merged,
+        commits,
+        last_updated,
+        badge_bump,
+    })
+}
+
+fn parse_org_state(data: &[u8]) -> anyhow::Result<ChainOrgState> {
+    let mut reader = AccountReader { data };
+    reader.take(8)?; // discriminator
+
+    let authority = reader.pubkey()?;
+    reader.pubkey()?; // org_badge
+    reader.pubkey()?; // hashed_org
+
+    Ok(ChainOrgState {
+        authority,
+        public_repos: reader.u32()?,
+        total_stars: reader.u32()?,
+        member_count: reader.u32()?,
+        linked_members: reader.u32()?,
+        last_updated: reader.i64()?,
+    })
+}
+
+fn parse_org_membership(data: &[u8]) -> anyhow::Result<ChainOrgMembership> {
+    let mut reader = AccountReader { data };
+    reader.take(8)?; // discriminator
+
+    let org_badge = reader.pubkey()?;
+    let dev = reader.pubkey()?;
+    let dev_badge = reader.pubkey()?;
+    reader.pubkey()?; // member_badge
+
+    Ok(ChainOrgMembership {
+        org_badge,
+        dev,
+        dev_badge,
+        linked_at: reader.i64()?,
+    })
+}
+
+// DevState of the dev whose Dev_Badge collection is `asset`
+pub async fn fetch_dev_state(client: &Client, asset: &[u8; 32]) -> anyhow::Result<ChainDevState> {
+    let accounts =
+        get_program_accounts(client, "DevState", &[(DEV_STATE_ASSET_OFFSET, asset)]).await?;
+    let data = accounts
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("No dev badge at this address"))?;
+
+    parse_dev_state(data)
+}
+
+// Collection of a Core asset, read from BaseAssetV1: key, owner, update_authority
+async fn fetch_asset_collection(client: &Client, asset: &[u8; 32]) -> anyhow::Result<[u8; 32]> {
+    let data = get_account_data(client, asset).await?;
+    let mut reader = AccountReader { data: &data };
+
+    reader.u8()?; // key
+    reader.pubkey()?; // owner
+    // UpdateAuthority::Collection
+    if reader.u8()? != 2 {
+        anyhow::bail!("Asset is not part of a collection");
+    }
+    reader.pubkey()
+}
+
+// Address of the `[b"repo_badge", dev_badge, repo_name_padded, badge_bump]` PDA
+fn repo_badge_address(repo_state: &ChainRepoState, program_id: &[u8; 32]) -> [u8; 32] {
+    let mut repo_name_padded = [0u8; 32];
+    let len = repo_state.repo_name.len().min(32);
+    repo_name_padded[..len].copy_from_slice(&repo_state.repo_name[..len]);
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"repo_badge");
+    hasher.update(repo_state.dev_badge);
+    hasher.update(repo_name_padded);
+    hasher.update([repo_state.badge_bump]);
+    hasher.update(program_id);
+    hasher.update(b"ProgramDerivedAddress");
+    hasher.finalize().into()
+}
+
+// RepoState behind the repo badge `asset`, found among the repo states of its dev collection
+pub async fn fetch_repo_state(client: &Client, asset: &[u8; 32]) -> anyhow::Result<ChainRepoState> {
+    let dev_badge = fetch_asset_collection(client, asset).await?;
+    let program_id = program_id()?;
+
+    for data in get_program_accounts(
+        client,
+        "RepoState",
+        &[(REPO_STATE_DEV_BADGE_OFFSET, &dev_badge)],
+    )
+    .await?
+    {
+        let repo_state = parse_repo_state(&data)?;
+        if &repo_badge_address(&repo_state, &program_id) == asset {
+            return Ok(repo_state);
+        }
+    }
+
+    anyhow::bail!("No repo badge at this address")
+}
+
+// Address of the `[b"contribution_badge", dev_badge, hashed_repo, badge_bump]` PDA
+fn contribution_badge_address(
+    contribution_state: &ChainContributionState,
+    program_id: &[u8; 32],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"contribution_badge");
+    hasher.update(contribution_state.dev_badge);
+    hasher.update(contribution_state.hashed_repo);
+    hasher.update([contribution_state.badge_bump]);
+    hasher.update(program_id);
+    hasher.update(b"ProgramDerivedAddress");
+    hasher.finalize().into()
+}
+
+// ContributionState behind the contribution badge `asset`, found the same way as repo badges
+pub async fn fetch_contribution_state(
+    client: &Client,
+    asset: &[u8; 32],
+) -> anyhow::Result<ChainContributionState> {
+    let dev_badge = fetch_asset_collection(client, asset).await?;
+    let program_id = program_id()?;
+
+    for data in get_program_accounts(
+        client,
+        "ContributionState",
+        &[(CONTRIBUTION_STATE_DEV_BADGE_OFFSET, &dev_badge)],
+    )
+    .await?
+    {
+        let contribution_state = parse_contribution_state(&data)?;
+        if &contribution_badge_address(&contribution_state, &program_id) == asset {
+            return Ok(contribution_state);
+        }
+    }
+
+    anyhow::bail!("No contribution badge at this address")
+}
+
+// OrgState of the org whose Org_Badge collection is `asset`
+pub async fn fetch_org_state(client: &Client, asset: &[u8; 32]) -> anyhow::Result<ChainOrgState> {
+    let accounts =
+        get_program_accounts(client, "OrgState", &[(ORG_STATE_BADGE_OFFSET, asset)]).await?;
+    let data = accounts
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("No org badge at this address"))?;
+
+    parse_org_state(data)
+}
+
+// OrgMembership of the member badge `asset`
+pub async fn fetch_org_membership(
+    client: &Client,
+    asset: &[u8; 32],
+) -> anyhow::Result<ChainOrgMembership> {
+    let accounts = get_program_accounts(
+        client,
+        "OrgMembership",
+        &[(ORG_MEMBERSHIP_BADGE_OFFSET, asset)],
+    )
+    .await?;
+    let data = accounts
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("No org member badge at this address"))?;
+
+    parse_org_membership(data)
+}
+
+// Tiers of the program's ScoringParams, the only account of its kind, levels 2 to 5 in order
+pub async fn fetch_scoring_tiers(client: &Client) -> anyhow::Result<Vec<ChainScoringTier>> {
+    let accounts = get_program_accounts(client, "ScoringParams", &[]).await?;
+    let data = accounts
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Scoring params are not initialized"))?;
+
+    let mut reader = AccountReader { data };
+    reader.take(8)?; // discriminator
+
+    (0..SCORING_TIERS)
+        .map(|_| {
+            Ok(ChainScoringTier {
+                min_repos: reader.u32()?,
+                min_commits: reader.u32()?,
+                min_account_age_days: reader.u32()?,
+            })
+        })
+        .collect()
+}
@@ -3,6 +3,9 @@ use std::env;
 
 pub use sessions::*;
 
+pub mod nonces;
+pub use nonces::*;
+
 use sqlx::{PgPool, postgres::PgPoolOptions};
 
 pub async fn init_db() -> PgPool {
@@ -0,0 +1,34 @@
+use sqlx::PgPool;
+
+pub async fn create_nonces_table(pool: &PgPool) -> anyhow::Result<()> {
+    // Last attestation nonce handed out per wallet, the program rejects anything not above it
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS attestation_nonces (
+            wallet TEXT PRIMARY KEY,
+            nonce BIGINT NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Atomically bumps and returns the nonce for the wallet, starting at 1
+pub async fn next_nonce(pool: &PgPool, wallet: &str) -> anyhow::Result<u64> {
+    let (nonce,): (i64,) = sqlx::query_as(
+        r#"
+        INSERT INTO attestation_nonces (wallet, nonce)
+        VALUES ($1, 1)
+        ON CONFLICT (wallet) DO UPDATE SET nonce = attestation_nonces.nonce + 1
+        RETURNING nonce
+        "#,
+    )
+    .bind(wallet)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(nonce as u64)
+}
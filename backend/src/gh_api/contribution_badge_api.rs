@@ -0,0 +1,157 @@
+use crate::{
+    decode_wallet, get_session, github_get, models::api_models::*, next_nonce,
+    sign_contribution_metrics, signer_key_id, signer_public_key,
+};
+use anyhow;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+
+pub async fn fetch_contribution_metrics(
+    client: &Client,
+    token_access: &str,
+    username: &str,
+    repo: &str,
+) -> anyhow::Result<(String, u32, u32)> {
+    // returning (full_name, prs_merged, commits)
+
+    let Some((owner, repo_name)) = repo.split_once('/') else {
+        anyhow::bail!("Repo must be given as owner/repo");
+    };
+
+    let repo_info: RepoInfo = github_get(
+        client,
+        token_access,
+        &format!("https://api.github.com/repos/{}/{}", owner, repo_name),
+    )
+    .await
+    .map_err(|_| {
+        anyhow::anyhow!("RepoStats fetch failed : Did you enter the repo name correctly ?")
+    })?;
+
+    // Own repos get a repo badge instead
+    if repo_info.owner.login.eq_ignore_ascii_case(username) {
+        anyhow::bail!("User is owner of repo, mint a repo badge instead!");
+    }
+
+    let prs: serde_json::Value = github_get(
+        client,
+        token_access,
+        &format!(
+            "https://api.github.com/search/issues?q=repo:{}+author:{}+type:pr+is:merged",
+            repo_info.full_name, username
+        ),
+    )
+    .await?;
+    let prs_merged = prs["total_count"].as_u64().unwrap_or(0) as u32;
+
+    // Commits on the default branch, squash merged PRs count under the author too
+    let commits: serde_json::Value = github_get(
+        client,
+        token_access,
+        &format!(
+            "https://api.github.com/search/commits?q=repo:{}+author:{}",
+            repo_info.full_name, username
+        ),
+    )
+    .await?;
+    let commits = commits["total_count"].as_u64().unwrap_or(0) as u32;
+
+    if prs_merged == 0 && commits == 0 {
+        anyhow::bail!("No merged PRs or commits by user in this repo!");
+    }
+
+    Ok((repo_info.full_name, prs_merged, commits))
+}
+
+// /api/metrics/contribution?repo=owner/repo&wallet=...
+pub async fn contribution_metrics(
+    State(state): State<AppState>,
+    Query(params): Query<ContributionQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let session_id = headers
+        .get("cookie")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .split(';')
+        .find_map(|s| s.trim().strip_prefix("session_id="))
+        .unwrap_or("");
+
+    if session_id.is_empty() {
+        return Json(serde_json::json!({
+            "error": "Not authorized"
+        }))
+        .into_response();
+    }
+
+    let Ok(wallet) = decode_wallet(&params.wallet) else {
+        return Json(serde_json::json!({
+            "error": "Invalid wallet address"
+        }))
+        .into_response();
+    };
+
+    let Ok(session) = get_session(&state.db, session_id).await else {
+        return Json(serde_json::json!({
+            "error": "Session not found"
+        }))
+        .into_response();
+    };
+
+    let (repo_full_name, prs_merged, commits) = match fetch_contribution_metrics(
+        &state.client,
+        &session.access_token,
+        &session.username,
+        &params.repo,
+    )
+    .await
+    {
+        Ok(metrics) => metrics,
+        Err(e) => return Json(serde_json::json!({"error": e.to_string()})).into_response(),
+    };
+
+    // Freshness and replay protection checked by the program
+    let issued_at = chrono::Utc::now().timestamp();
+    let Ok(nonce) = next_nonce(&state.db, &params.wallet).await else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Error generating attestation nonce"})),
+        )
+            .into_response();
+    };
+
+    let (signature, hashed_username, hashed_message) = sign_contribution_metrics(
+        &wallet,
+        &session.username,
+        &repo_full_name,
+        prs_merged,
+        commits,
+        issued_at,
+        nonce,
+    );
+
+    // Seed of the contribution PDAs, the program lowercases the same way
+    let hashed_repo: [u8; 32] = Sha256::digest(repo_full_name.to_ascii_lowercase()).into();
+
+    Json(serde_json::json!({
+        "wallet": params.wallet,
+        "hashed_username": hashed_username,
+        "repo_full_name_bytes": repo_full_name.as_bytes(),
+        "hashed_repo": hashed_repo,
+        "prs_merged": prs_merged,
+        "commits": commits,
+        "issued_at": issued_at,
+        "nonce": nonce,
+        "signature": signature,
+        "public_key_bytes": signer_public_key(),
+        "key_id": signer_key_id(),
+        "signed_message": hashed_message,
+    }))
+    .into_response()
+}
@@ -1,11 +1,17 @@
-use axum::{Json, extract::State, http::HeaderMap};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
 use chrono::{DateTime, Utc};
 use futures::future::join_all;
 use reqwest::Client;
 
 // Use Models
 use crate::{
-    GithubUser, api_models::*, get_session, signer::sign_dev_badge_metrics, signer_public_key,
+    ChainScoringTier, GithubUser, api_models::*, decode_wallet, fetch_scoring_tiers, get_session,
+    next_nonce, signer::sign_dev_badge_metrics, signer_key_id, signer_public_key,
 };
 
 pub async fn fetch_github_user(access_token: &str) -> GithubUser {
@@ -115,7 +121,7 @@ pub async fn compute_dev_metrics(
     access_token: &str,
     username: &str,
 ) -> (u32, u32, u32, u32) {
-    let repos = fetch_user_repos(&access_token).await;
+    let repos = fetch_user_repos(access_token).await;
     let repo_count = repos.len() as u32;
 
     let owned_repo_count = repos.iter().filter(|repo| !repo.fork).count() as u32;
@@ -151,32 +157,83 @@ pub async fn compute_dev_metrics(
     (repo_count, owned_repo_count, total_commits, stars_count)
 }
 
-// Check the reputation_level of the user
-pub async fn get_reputation_level(
+// Check the reputation_level of the user against the on-chain scoring table,
+// the program derives the same level and rejects an attestation that disagrees
+pub fn get_reputation_level(
+    tiers: &[ChainScoringTier],
     repo_count: u32,
     total_commits: u32,
     account_age_days: u32,
 ) -> u8 {
-    let user_level: u8 = if repo_count >= 100 && total_commits >= 1500 && account_age_days >= 730 {
-        5 // Legend
-    } else if repo_count >= 50 && total_commits >= 500 && account_age_days >= 365 {
-        4 // Architect
-    } else if repo_count >= 20 && total_commits >= 200 && account_age_days >= 120 {
-        3 // Builder
-    } else if repo_count >= 5 && total_commits >= 30 {
-        2 // Coder
-    } else {
-        1 // Ghost
-    };
+    tiers
+        .iter()
+        .rposition(|tier| {
+            repo_count >= tier.min_repos
+                && total_commits >= tier.min_commits
+                && account_age_days >= tier.min_account_age_days
+        })
+        .map_or(1, |i| i as u8 + 2)
+}
+
+// Metrics behind a dev attestation, derived the same way by the primary and co-signing oracles
+pub struct DevMetrics {
+    pub repo_count: u32,
+    pub owned_repo_count: u32,
+    pub total_commits: u32,
+    pub total_stars: u32,
+    pub prs_merged: u32,
+    pub issues_closed: u32,
+    pub followers: u32,
+    pub account_age_days: u32,
+    pub reputation_level: u8,
+}
+
+pub async fn derive_dev_metrics(
+    client: &Client,
+    access_token: &str,
+    username: &str,
+) -> anyhow::Result<DevMetrics> {
+    // fetch user metrics
+    let (repo_count, owned_repo_count, total_commits, total_stars) =
+        compute_dev_metrics(client, access_token, username).await;
+
+    println!(
+        "Dev Metrics\nUsername: {}\nRepos: {}\nTotal Commits: {}",
+        username, repo_count, total_commits
+    );
+
+    // Fetch user oss stats
+    let (prs_merged, issues_closed) = fetch_oss_stats(client, username, access_token).await;
+
+    // fetch user stats
+    let gh_user = fetch_github_user(access_token).await;
+    let created: DateTime<Utc> = gh_user.created_at.parse()?;
+    let account_age_days = (Utc::now() - created).num_days() as u32;
+
+    // Get dev's reputation level
+    let tiers = fetch_scoring_tiers(client).await?;
+    let reputation_level =
+        get_reputation_level(&tiers, repo_count, total_commits, account_age_days);
 
-    user_level
+    Ok(DevMetrics {
+        repo_count,
+        owned_repo_count,
+        total_commits,
+        total_stars,
+        prs_merged,
+        issues_closed,
+        followers: gh_user.followers,
+        account_age_days,
+        reputation_level,
+    })
 }
 
-// /api/metrics/dev/
+// /api/metrics/dev?wallet=...
 pub async fn dev_metrics(
     State(state): State<AppState>,
+    Query(params): Query<WalletQuery>,
     headers: HeaderMap,
-) -> Json<serde_json::Value> {
+) -> Response {
     let session_id = headers
         .get("cookie")
         .and_then(|v| v.to_str().ok())
@@ -186,9 +243,13 @@ pub async fn dev_metrics(
         .unwrap_or("");
 
     if session_id.is_empty() {
-        return Json(serde_json::json!({"error": "Not authorized"}));
+        return Json(serde_json::json!({"error": "Not authorized"})).into_response();
     }
 
+    let Ok(wallet) = decode_wallet(&params.wallet) else {
+        return Json(serde_json::json!({"error": "Invalid wallet address"})).into_response();
+    };
+
     let fetched_session = get_session(&state.db, session_id)
         .await
         .expect("Invalid Session id, failed to fetch from db");
@@ -198,55 +259,58 @@ pub async fn dev_metrics(
 
     println!("Username Received {}", username);
 
-    // fetch user metrics
-    let (repo_count, owned_repo_count, total_commits, stars) =
-        compute_dev_metrics(&state.client, &token_access, &username).await;
-
-    println!(
-        "Dev Metrics\nUsername: {}\nRepos: {}\nTotal Commits: {}",
-        username, repo_count, total_commits
-    );
-
-    // Fetch user oss stats
-    let (pr_merged, issues_closed) = fetch_oss_stats(&state.client, &username, &token_access).await;
-
-    // fetch user stats
-    let gh_user = fetch_github_user(&token_access).await;
-    let created: DateTime<Utc> = gh_user.created_at.parse().unwrap();
-    let account_age_days = (Utc::now() - created).num_days() as u32;
+    let metrics = match derive_dev_metrics(&state.client, &token_access, &username).await {
+        Ok(metrics) => metrics,
+        Err(e) => return Json(serde_json::json!({"error": e.to_string()})).into_response(),
+    };
 
-    // Get dev's reputation level
-    let user_level = get_reputation_level(repo_count, total_commits, account_age_days).await;
+    // Freshness and replay protection checked by the program
+    let issued_at = Utc::now().timestamp();
+    let Ok(nonce) = next_nonce(&state.db, &params.wallet).await else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Error generating attestation nonce"})),
+        )
+            .into_response();
+    };
 
     // Sign and parse to json
     let (signature_bytes, hashed_username, hashed_message) = sign_dev_badge_metrics(
+        &wallet,
         &username,
-        repo_count,
-        total_commits,
-        owned_repo_count,
-        stars,
-        pr_merged,
-        issues_closed,
-        gh_user.followers,
-        account_age_days,
-        user_level,
+        metrics.repo_count,
+        metrics.total_commits,
+        metrics.owned_repo_count,
+        metrics.total_stars,
+        metrics.prs_merged,
+        metrics.issues_closed,
+        metrics.followers,
+        metrics.account_age_days,
+        metrics.reputation_level,
+        issued_at,
+        nonce,
     );
 
     let public_key_bytes = signer_public_key();
 
     Json(serde_json::json!({
+        "wallet": params.wallet,
         "hashed_username": hashed_username,
-        "repo_count": repo_count,
-        "owned_repo_count": owned_repo_count,
-        "total_stars": stars,
-        "total_commit": total_commits,
-        "prs_merged": pr_merged,
-        "issues_closed": issues_closed,
-        "followers": gh_user.followers,
-        "account_age_days": account_age_days,
-        "reputation_level": user_level,
+        "repo_count": metrics.repo_count,
+        "owned_repo_count": metrics.owned_repo_count,
+        "total_stars": metrics.total_stars,
+        "total_commit": metrics.total_commits,
+        "prs_merged": metrics.prs_merged,
+        "issues_closed": metrics.issues_closed,
+        "followers": metrics.followers,
+        "account_age_days": metrics.account_age_days,
+        "reputation_level": metrics.reputation_level,
+        "issued_at": issued_at,
+        "nonce": nonce,
         "signature": signature_bytes,
         "public_key_bytes": public_key_bytes,
+        "key_id": signer_key_id(),
         "signed_message": hashed_message,
     }))
+    .into_response()
 }
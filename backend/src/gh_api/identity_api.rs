@@ -0,0 +1,82 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::HeaderMap,
+};
+use chrono::Utc;
+
+use crate::{
+    api_models::*, decode_wallet, get_session, sign_identity_migration, sign_identity_release,
+    signer_key_id, signer_public_key,
+};
+
+// /api/identity/release?old_wallet=...&new_wallet=...
+// Lets the logged in GitHub owner free their identity from a wallet they are leaving
+pub async fn identity_release(
+    State(state): State<AppState>,
+    Query(params): Query<ReleaseQuery>,
+    headers: HeaderMap,
+) -> Json<serde_json::Value> {
+    sign_identity_move(state, params, headers, sign_identity_release).await
+}
+
+// /api/identity/migrate?old_wallet=...&new_wallet=...
+// Same check, the attestation starts a migration that keeps badges and vouches
+pub async fn identity_migrate(
+    State(state): State<AppState>,
+    Query(params): Query<ReleaseQuery>,
+    headers: HeaderMap,
+) -> Json<serde_json::Value> {
+    sign_identity_move(state, params, headers, sign_identity_migration).await
+}
+
+// sign_identity_release or sign_identity_migration
+type IdentitySigner = fn(&str, &[u8; 32], &[u8; 32], i64) -> (Vec<u8>, [u8; 32], Vec<u8>);
+
+async fn sign_identity_move(
+    state: AppState,
+    params: ReleaseQuery,
+    headers: HeaderMap,
+    sign: IdentitySigner,
+) -> Json<serde_json::Value> {
+    let session_id = headers
+        .get("cookie")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .split(';')
+        .find_map(|c| c.trim().strip_prefix("session_id="))
+        .unwrap_or("");
+
+    if session_id.is_empty() {
+        return Json(serde_json::json!({"error": "Not authorized"}));
+    }
+
+    let (Ok(old_wallet), Ok(new_wallet)) = (
+        decode_wallet(&params.old_wallet),
+        decode_wallet(&params.new_wallet),
+    ) else {
+        return Json(serde_json::json!({"error": "Invalid wallet address"}));
+    };
+    if old_wallet == new_wallet {
+        return Json(serde_json::json!({"error": "Identity is already on this wallet"}));
+    }
+
+    let session = get_session(&state.db, session_id)
+        .await
+        .expect("Error Fetching session from db");
+
+    let issued_at = Utc::now().timestamp();
+    let (signature, hashed_username, hashed_message) =
+        sign(&session.username, &old_wallet, &new_wallet, issued_at);
+
+    Json(serde_json::json!({
+        "old_wallet": params.old_wallet,
+        "new_wallet": params.new_wallet,
+        "hashed_username": hashed_username,
+        "issued_at": issued_at,
+        "signature": signature,
+        "public_key_bytes": signer_public_key(),
+        "key_id": signer_key_id(),
+        "signed_message": hashed_message,
+    }))
+}
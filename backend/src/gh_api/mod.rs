@@ -3,3 +3,12 @@ pub use dev_badge_api::*;
 
 pub mod repo_badge_api;
 pub use repo_badge_api::*;
+
+pub mod identity_api;
+pub use identity_api::*;
+
+pub mod org_badge_api;
+pub use org_badge_api::*;
+
+pub mod contribution_badge_api;
+pub use contribution_badge_api::*;
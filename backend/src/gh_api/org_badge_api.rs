@@ -0,0 +1,185 @@
+use crate::{
+    decode_wallet, get_session, models::api_models::*, next_nonce, sign_org_badge_metrics,
+    signer_key_id, signer_public_key,
+};
+use anyhow;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+
+// GET on the GitHub API with the session token, non-2xx answers become errors
+pub async fn github_get<T: DeserializeOwned>(
+    client: &Client,
+    token_access: &str,
+    url: &str,
+) -> anyhow::Result<T> {
+    let response = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", token_access))
+        .header("User-Agent", "GhostCheck")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Github request failed with status {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+// Every page of a GitHub list endpoint, `url` must not carry a page parameter yet
+async fn github_get_all<T: DeserializeOwned>(
+    client: &Client,
+    token_access: &str,
+    url: &str,
+) -> anyhow::Result<Vec<T>> {
+    let mut items = Vec::new();
+
+    for page in 1.. {
+        let batch: Vec<T> =
+            github_get(client, token_access, &format!("{}&page={}", url, page)).await?;
+        let last_page = batch.len() < 100;
+        items.extend(batch);
+        if last_page {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+pub async fn fetch_org_metrics(
+    client: &Client,
+    token_access: &str,
+    org: &str,
+) -> anyhow::Result<(String, u32, u32, u32)> {
+    // returning (org login, public_repos, total_stars, member_count)
+
+    // Only an active admin of the org may mint its badge
+    let membership: GithubOrgMembership = github_get(
+        client,
+        token_access,
+        &format!("https://api.github.com/user/memberships/orgs/{}", org),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("User is not a member of this org!"))?;
+
+    if membership.role != "admin" || membership.state != "active" {
+        anyhow::bail!("User is not an admin of this org!");
+    }
+
+    let org_info: OrgInfo = github_get(
+        client,
+        token_access,
+        &format!("https://api.github.com/orgs/{}", org),
+    )
+    .await?;
+
+    let repos: Vec<OrgRepo> = github_get_all(
+        client,
+        token_access,
+        &format!(
+            "https://api.github.com/orgs/{}/repos?type=public&per_page=100",
+            org
+        ),
+    )
+    .await?;
+    let total_stars = repos.iter().map(|r| r.stargazers_count).sum();
+
+    // Admins see concealed members too, so this is the full member count
+    let members: Vec<serde_json::Value> = github_get_all(
+        client,
+        token_access,
+        &format!("https://api.github.com/orgs/{}/members?per_page=100", org),
+    )
+    .await?;
+
+    Ok((
+        org_info.login,
+        org_info.public_repos,
+        total_stars,
+        members.len() as u32,
+    ))
+}
+
+// /api/metrics/org?org=...&wallet=...
+pub async fn org_metrics(
+    State(state): State<AppState>,
+    Query(params): Query<OrgQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let session_id = headers
+        .get("cookie")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .split(';')
+        .find_map(|s| s.trim().strip_prefix("session_id="))
+        .unwrap_or("");
+
+    if session_id.is_empty() {
+        return Json(serde_json::json!({
+            "error": "Not authorized"
+        }))
+        .into_response();
+    }
+
+    let Ok(wallet) = decode_wallet(&params.wallet) else {
+        return Json(serde_json::json!({
+            "error": "Invalid wallet address"
+        }))
+        .into_response();
+    };
+
+    let Ok(session) = get_session(&state.db, session_id).await else {
+        return Json(serde_json::json!({
+            "error": "Session not found"
+        }))
+        .into_response();
+    };
+
+    let (org, public_repos, total_stars, member_count) =
+        match fetch_org_metrics(&state.client, &session.access_token, &params.org).await {
+            Ok(metrics) => metrics,
+            Err(e) => return Json(serde_json::json!({"error": e.to_string()})).into_response(),
+        };
+
+    // Freshness and replay protection checked by the program
+    let issued_at = chrono::Utc::now().timestamp();
+    let Ok(nonce) = next_nonce(&state.db, &params.wallet).await else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Error generating attestation nonce"})),
+        )
+            .into_response();
+    };
+
+    let (signature, hashed_org, hashed_message) = sign_org_badge_metrics(
+        &wallet,
+        &org,
+        public_repos,
+        total_stars,
+        member_count,
+        issued_at,
+        nonce,
+    );
+
+    Json(serde_json::json!({
+        "wallet": params.wallet,
+        "org": org,
+        "hashed_org": hashed_org,
+        "public_repos": public_repos,
+        "total_stars": total_stars,
+        "member_count": member_count,
+        "issued_at": issued_at,
+        "nonce": nonce,
+        "signature": signature,
+        "public_key_bytes": signer_public_key(),
+        "key_id": signer_key_id(),
+        "signed_message": hashed_message,
+    }))
+    .into_response()
+}
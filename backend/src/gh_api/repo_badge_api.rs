@@ -1,9 +1,13 @@
-use crate::{get_session, models::api_models::*, sign_repo_badge_metrics, signer_public_key};
+use crate::{
+    decode_wallet, get_session, models::api_models::*, next_nonce, sign_repo_badge_metrics,
+    signer_key_id, signer_public_key,
+};
 use anyhow;
 use axum::{
     Json,
     extract::{Query, State},
-    http::HeaderMap,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
 };
 use reqwest::Client;
 use std::collections::HashMap;
@@ -53,10 +57,10 @@ pub async fn fetch_repo_metrics(
 
     // Sort the language by bytes
     let mut langs: Vec<_> = languages.into_iter().collect();
-    langs.sort_by(|a, b| b.1.cmp(&a.1));
+    langs.sort_by_key(|l| std::cmp::Reverse(l.1));
 
     let lang1 = langs
-        .get(0)
+        .first()
         .map(|l| l.0.as_bytes().to_vec())
         .unwrap_or_default();
     let lang2 = langs
@@ -100,7 +104,7 @@ pub async fn repo_metrics(
     State(state): State<AppState>,
     Query(params): Query<RepoQuery>,
     headers: HeaderMap,
-) -> Json<serde_json::Value> {
+) -> Response {
     let session_id = headers
         .get("cookie")
         .and_then(|v| v.to_str().ok())
@@ -112,9 +116,17 @@ pub async fn repo_metrics(
     if session_id.is_empty() {
         return Json(serde_json::json!({
             "error": "Not authorized"
-        }));
+        }))
+        .into_response();
     }
 
+    let Ok(wallet) = decode_wallet(&params.wallet) else {
+        return Json(serde_json::json!({
+            "error": "Invalid wallet address"
+        }))
+        .into_response();
+    };
+
     let session = get_session(&state.db, session_id)
         .await
         .expect("Error Fetching session from db");
@@ -127,8 +139,19 @@ pub async fn repo_metrics(
             .await
             .expect("Error fetching repo stats");
 
+    // Freshness and replay protection checked by the program
+    let issued_at = chrono::Utc::now().timestamp();
+    let Ok(nonce) = next_nonce(&state.db, &params.wallet).await else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "Error generating attestation nonce"})),
+        )
+            .into_response();
+    };
+
     //Sign the metrics
     let (signature, hashed_username, hashed_message) = sign_repo_badge_metrics(
+        &wallet,
         &username,
         &params.repo,
         &lang1,
@@ -138,11 +161,14 @@ pub async fn repo_metrics(
         fork_count,
         issues_open_count,
         is_forked,
+        issued_at,
+        nonce,
     );
 
     let public_key = signer_public_key();
 
     Json(serde_json::json!({
+        "wallet": params.wallet,
         "hashed_username": hashed_username,
         "repo_name_bytes": params.repo.as_bytes(),
         "lang1_bytes": lang1,
@@ -152,8 +178,12 @@ pub async fn repo_metrics(
         "fork_count": fork_count,
         "issues_open_count": issues_open_count ,
         "is_fork": is_forked,
+        "issued_at": issued_at,
+        "nonce": nonce,
         "signature": signature,
         "public_key_bytes": public_key,
+        "key_id": signer_key_id(),
         "signed_message": hashed_message,
     }))
+    .into_response()
 }
@@ -1,4 +1,4 @@
-use crate::{AppState, fetch_github_user, get_session, insert_session};
+use crate::{AppState, fetch_github_user, get_session, insert_session, public_url};
 use axum::{
     Json,
     extract::{Query, State},
@@ -21,9 +21,11 @@ pub async fn github_login() -> Redirect {
 
     let client_id = env::var("GITHUB_CLIENT_ID").unwrap();
 
-    let redirect_uri = urlencoding::encode("http://localhost:3000/api/auth/github/callback");
+    // Every oracle instance, co-signers included, runs its own login
+    let redirect_uri = format!("{}/api/auth/github/callback", public_url());
+    let redirect_uri = urlencoding::encode(&redirect_uri);
     let github_url = format!(
-        "https://github.com/login/oauth/authorize?client_id={}&redirect_uri={}&scope=read:user%20repo",
+        "https://github.com/login/oauth/authorize?client_id={}&redirect_uri={}&scope=read:user%20repo%20read:org",
         client_id, redirect_uri
     );
 
@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 use tokio::net::TcpListener;
 
 pub mod gh_auth;
@@ -15,6 +17,15 @@ pub use models::*;
 pub mod signer;
 pub use signer::*;
 
+pub mod oracle;
+pub use oracle::*;
+
+pub mod chain;
+pub use chain::*;
+
+pub mod metadata;
+pub use metadata::*;
+
 pub mod db;
 pub use db::*;
 
@@ -26,6 +37,9 @@ async fn main() {
     create_sessions_table(&pool)
         .await
         .expect("Error creating sessions table");
+    create_nonces_table(&pool)
+        .await
+        .expect("Error creating attestation nonces table");
 
     // Makes an instance of the AppState to pass to axum
     let state = AppState {
@@ -34,9 +48,12 @@ async fn main() {
     };
 
     let app = routes::create_router(state);
-    let listener = TcpListener::bind("127.0.0.1:3000").await.unwrap();
+    // Co-signing oracles run as more instances, each on its own address
+    let bind_addr =
+        std::env::var("GhostCheck_Bind_Addr").unwrap_or_else(|_| "127.0.0.1:3000".to_string());
+    let listener = TcpListener::bind(&bind_addr).await.unwrap();
 
-    println!("Server running on http://localhost:3000");
+    println!("Server running on http://{}", bind_addr);
 
     axum::serve(listener, app).await.unwrap();
 }
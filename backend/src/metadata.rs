@@ -0,0 +1,335 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+};
+use std::env;
+
+use crate::{
+    AppState, ChainContributionState, ChainDevState, ChainOrgMembership, ChainOrgState,
+    ChainRepoState, decode_wallet, fetch_contribution_state, fetch_dev_state, fetch_org_membership,
+    fetch_org_state, fetch_repo_state,
+};
+
+// Public address of this backend, the program's metadata_base_uri points here too
+pub fn public_url() -> String {
+    env::var("GhostCheck_Public_Url").unwrap_or_else(|_| "http://localhost:3000".to_string())
+}
+
+// Same tiers as the frontend profile page
+fn level_name(level: u8) -> &'static str {
+    match level {
+        1 => "Ghost",
+        2 => "Coder",
+        3 => "Builder",
+        4 => "Architect",
+        5 => "Legend",
+        _ => "Unknown",
+    }
+}
+
+fn level_color(level: u8) -> &'static str {
+    match level {
+        1 => "#8b949e",
+        2 => "#3fb950",
+        3 => "#58a6ff",
+        4 => "#bc8cff",
+        5 => "#f0b429",
+        _ => "#30363d",
+    }
+}
+
+fn attribute(trait_type: &str, value: impl ToString) -> serde_json::Value {
+    serde_json::json!({"trait_type": trait_type, "value": value.to_string()})
+}
+
+// Dev states upgraded from the baseline hold u32::MAX until the admin counts their vouches,
+// real counts stay within the u16 vouch budget
+fn vouches_given(vouches_given: u32) -> String {
+    if vouches_given > u16::MAX as u32 {
+        String::from("unknown")
+    } else {
+        vouches_given.to_string()
+    }
+}
+
+// Repo names are zero padded on badges minted before they were trimmed
+fn display_bytes(bytes: &[u8]) -> String {
+    let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+fn dev_metadata(dev_state: &ChainDevState) -> serde_json::Value {
+    let level = dev_state.reputation_level;
+    let image = format!("{}/metadata/image/dev/{}", public_url(), level);
+
+    serde_json::json!({
+        "name": format!("GhostCheck Dev Badge — {}", level_name(level)),
+        "symbol": "GHOST",
+        "description": "Soulbound GhostCheck developer reputation, GitHub stats attested on-chain without revealing the account.",
+        "image": image,
+        "attributes": [
+            attribute("dev", bs58::encode(dev_state.dev_addr).into_string()),
+            attribute("reputation_level", level),
+            attribute("tier", level_name(level)),
+            attribute("repos", dev_state.repo_count),
+            attribute("owned_repos", dev_state.owned_repo_count),
+            attribute("commits", dev_state.total_commits),
+            attribute("stars", dev_state.total_stars),
+            attribute("prs_merged", dev_state.prs_merged),
+            attribute("issues_closed", dev_state.issues_closed),
+            attribute("followers", dev_state.followers),
+            attribute("account_age_days", dev_state.account_age_days),
+            attribute("verified_repos", dev_state.verified_repos),
+            attribute("vouch_count", dev_state.vouch_count),
+            attribute("vouch_score", dev_state.vouch_score),
+            attribute("vouches_given", vouches_given(dev_state.vouches_given)),
+            attribute("last_updated", dev_state.last_updated),
+        ],
+        "properties": {
+            "category": "image",
+            "files": [{"uri": image, "type": "image/svg+xml"}],
+        },
+    })
+}
+
+fn repo_metadata(repo_state: &ChainRepoState) -> serde_json::Value {
+    let repo_name = display_bytes(&repo_state.repo_name);
+    let image = format!("{}/metadata/image/repo", public_url());
+
+    serde_json::json!({
+        "name": repo_name,
+        "symbol": "GHOST",
+        "description": "GhostCheck repository badge, repo ownership and stats attested on-chain.",
+        "image": image,
+        "attributes": [
+            attribute("repo_name", &repo_name),
+            attribute("owner", bs58::encode(repo_state.owner).into_string()),
+            attribute("dev_badge", bs58::encode(repo_state.dev_badge).into_string()),
+            attribute("stars", repo_state.stars),
+            attribute("commits", repo_state.commits),
+            attribute("forks", repo_state.forks),
+            attribute("open_issues", repo_state.open_issues),
+            attribute("is_fork", repo_state.is_fork),
+            attribute("lang1", display_bytes(&repo_state.lang1)),
+            attribute("lang2", display_bytes(&repo_state.lang2)),
+            attribute("last_updated", repo_state.last_updated),
+        ],
+        "properties": {
+            "category": "image",
+            "files": [{"uri": image, "type": "image/svg+xml"}],
+        },
+    })
+}
+
+fn contribution_metadata(contribution_state: &ChainContributionState) -> serde_json::Value {
+    let repo = display_bytes(&contribution_state.repo_full_name);
+    let image = format!("{}/metadata/image/contribution", public_url());
+
+    serde_json::json!({
+        "name": repo,
+        "symbol": "GHOST",
+        "description": "GhostCheck contribution badge, merged work in someone else's repository attested on-chain.",
+        "image": image,
+        "attributes": [
+            attribute("repo", &repo),
+            attribute("contributor", bs58::encode(contribution_state.contributor).into_string()),
+            attribute("dev_badge", bs58::encode(contribution_state.dev_badge).into_string()),
+            attribute("prs_merged", contribution_state.prs_merged),
+            attribute("commits", contribution_state.commits),
+            attribute("last_updated", contribution_state.last_updated),
+        ],
+        "properties": {
+            "category": "image",
+            "files": [{"uri": image, "type": "image/svg+xml"}],
+        },
+    })
+}
+
+fn org_metadata(org_state: &ChainOrgState) -> serde_json::Value {
+    let image = format!("{}/metadata/image/org", public_url());
+
+    serde_json::json!({
+        "name": "GhostCheck Org Badge",
+        "symbol": "GHOST",
+        "description": "GhostCheck organization badge, GitHub org stats attested on-chain and linked to its members' dev badges.",
+        "image": image,
+        "attributes": [
+            attribute("authority", bs58::encode(org_state.authority).into_string()),
+            attribute("public_repos", org_state.public_repos),
+            attribute("total_stars", org_state.total_stars),
+            attribute("member_count", org_state.member_count),
+            attribute("linked_members", org_state.linked_members),
+            attribute("last_updated", org_state.last_updated),
+        ],
+        "properties": {
+            "category": "image",
+            "files": [{"uri": image, "type": "image/svg+xml"}],
+        },
+    })
+}
+
+fn org_member_metadata(membership: &ChainOrgMembership) -> serde_json::Value {
+    let image = format!("{}/metadata/image/org", public_url());
+
+    serde_json::json!({
+        "name": "GhostCheck Org Member",
+        "symbol": "GHOST",
+        "description": "GhostCheck org member badge, a dev badge linked to the organization it was minted in.",
+        "image": image,
+        "attributes": [
+            attribute("org_badge", bs58::encode(membership.org_badge).into_string()),
+            attribute("dev", bs58::encode(membership.dev).into_string()),
+            attribute("dev_badge", bs58::encode(membership.dev_badge).into_string()),
+            attribute("linked_at", membership.linked_at),
+        ],
+        "properties": {
+            "category": "image",
+            "files": [{"uri": image, "type": "image/svg+xml"}],
+        },
+    })
+}
+
+// /metadata/dev/{asset}
+// Metaplex JSON of a Dev_Badge collection, built from the DevState holding that asset
+pub async fn dev_badge_metadata(
+    State(state): State<AppState>,
+    Path(asset): Path<String>,
+) -> Json<serde_json::Value> {
+    let Ok(asset) = decode_wallet(&asset) else {
+        return Json(serde_json::json!({"error": "Invalid asset address"}));
+    };
+
+    match fetch_dev_state(&state.client, &asset).await {
+        Ok(dev_state) => Json(dev_metadata(&dev_state)),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+// /metadata/repo/{asset}
+// Metaplex JSON of a repo badge, built from the RepoState the asset was minted for
+pub async fn repo_badge_metadata(
+    State(state): State<AppState>,
+    Path(asset): Path<String>,
+) -> Json<serde_json::Value> {
+    let Ok(asset) = decode_wallet(&asset) else {
+        return Json(serde_json::json!({"error": "Invalid asset address"}));
+    };
+
+    match fetch_repo_state(&state.client, &asset).await {
+        Ok(repo_state) => Json(repo_metadata(&repo_state)),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+// /metadata/contribution/{asset}
+// Metaplex JSON of a contribution badge, built from its ContributionState
+pub async fn contribution_badge_metadata(
+    State(state): State<AppState>,
+    Path(asset): Path<String>,
+) -> Json<serde_json::Value> {
+    let Ok(asset) = decode_wallet(&asset) else {
+        return Json(serde_json::json!({"error": "Invalid asset address"}));
+    };
+
+    match fetch_contribution_state(&state.client, &asset).await {
+        Ok(contribution_state) => Json(contribution_metadata(&contribution_state)),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+// /metadata/org/{asset}
+// Metaplex JSON of an Org_Badge collection
+pub async fn org_badge_metadata(
+    State(state): State<AppState>,
+    Path(asset): Path<String>,
+) -> Json<serde_json::Value> {
+    let Ok(asset) = decode_wallet(&asset) else {
+        return Json(serde_json::json!({"error": "Invalid asset address"}));
+    };
+
+    match fetch_org_state(&state.client, &asset).await {
+        Ok(org_state) => Json(org_metadata(&org_state)),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+// /metadata/org_member/{asset}
+// Metaplex JSON of a member badge in an Org_Badge collection, built from its OrgMembership
+pub async fn org_member_badge_metadata(
+    State(state): State<AppState>,
+    Path(asset): Path<String>,
+) -> Json<serde_json::Value> {
+    let Ok(asset) = decode_wallet(&asset) else {
+        return Json(serde_json::json!({"error": "Invalid asset address"}));
+    };
+
+    match fetch_org_membership(&state.client, &asset).await {
+        Ok(membership) => Json(org_member_metadata(&membership)),
+        Err(e) => Json(serde_json::json!({"error": e.to_string()})),
+    }
+}
+
+fn svg_response(svg: String) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "image/svg+xml")], svg)
+}
+
+// /metadata/image/dev/{level}
+pub async fn dev_badge_image(Path(level): Path<u8>) -> impl IntoResponse {
+    let color = level_color(level);
+    let stars = "★".repeat(level.min(5) as usize);
+
+    svg_response(format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="512" height="512" viewBox="0 0 512 512">
+<rect width="512" height="512" rx="48" fill="#0d1117"/>
+<circle cx="256" cy="210" r="120" fill="none" stroke="{color}" stroke-width="16"/>
+<text x="256" y="238" font-family="monospace" font-size="96" fill="{color}" text-anchor="middle">{level}</text>
+<text x="256" y="400" font-family="monospace" font-size="48" fill="#e6edf3" text-anchor="middle">{name}</text>
+<text x="256" y="460" font-family="monospace" font-size="36" fill="{color}" text-anchor="middle">{stars}</text>
+</svg>"##,
+        name = level_name(level),
+    ))
+}
+
+// /metadata/image/repo
+pub async fn repo_badge_image() -> impl IntoResponse {
+    svg_response(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="512" height="512" viewBox="0 0 512 512">
+<rect width="512" height="512" rx="48" fill="#0d1117"/>
+<rect x="136" y="136" width="240" height="200" rx="16" fill="none" stroke="#58a6ff" stroke-width="16"/>
+<text x="256" y="260" font-family="monospace" font-size="72" fill="#58a6ff" text-anchor="middle">&lt;/&gt;</text>
+<text x="256" y="430" font-family="monospace" font-size="48" fill="#e6edf3" text-anchor="middle">Verified Repo</text>
+</svg>"##
+            .to_string(),
+    )
+}
+
+// /metadata/image/contribution
+pub async fn contribution_badge_image() -> impl IntoResponse {
+    svg_response(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="512" height="512" viewBox="0 0 512 512">
+<rect width="512" height="512" rx="48" fill="#0d1117"/>
+<circle cx="176" cy="150" r="40" fill="none" stroke="#bc8cff" stroke-width="16"/>
+<circle cx="176" cy="330" r="40" fill="none" stroke="#bc8cff" stroke-width="16"/>
+<circle cx="336" cy="330" r="40" fill="none" stroke="#bc8cff" stroke-width="16"/>
+<path d="M176 190 V290 M336 290 V230 Q336 190 296 190 H240" fill="none" stroke="#bc8cff" stroke-width="16"/>
+<text x="256" y="450" font-family="monospace" font-size="48" fill="#e6edf3" text-anchor="middle">Contributor</text>
+</svg>"##
+            .to_string(),
+    )
+}
+
+// /metadata/image/org
+pub async fn org_badge_image() -> impl IntoResponse {
+    svg_response(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="512" height="512" viewBox="0 0 512 512">
+<rect width="512" height="512" rx="48" fill="#0d1117"/>
+<circle cx="196" cy="200" r="64" fill="none" stroke="#3fb950" stroke-width="16"/>
+<circle cx="316" cy="200" r="64" fill="none" stroke="#3fb950" stroke-width="16"/>
+<circle cx="256" cy="290" r="64" fill="none" stroke="#3fb950" stroke-width="16"/>
+<text x="256" y="440" font-family="monospace" font-size="48" fill="#e6edf3" text-anchor="middle">Verified Org</text>
+</svg>"##
+            .to_string(),
+    )
+}
@@ -23,6 +23,7 @@ pub struct Contributor {
 // Used for repo_badge , for fetching single repo
 #[derive(Deserialize)]
 pub struct RepoInfo {
+    pub full_name: String,
     pub stargazers_count: u32,
     pub owner: Owner,
     pub forks_count: u32,
@@ -30,10 +31,70 @@ pub struct RepoInfo {
     pub open_issues_count: u32,
 }
 
-// To receive payload from GET /metrics/repo?session_id=...&repo=...
+// To receive payload from GET /metrics/repo?repo=...&wallet=...
 #[derive(Deserialize)]
 pub struct RepoQuery {
     pub repo: String,
+    pub wallet: String,
+}
+
+// To receive payload from GET /identity/release?old_wallet=...&new_wallet=...
+#[derive(Deserialize)]
+pub struct ReleaseQuery {
+    pub old_wallet: String,
+    pub new_wallet: String,
+}
+
+// To receive payload from GET /metrics/dev?wallet=... , the wallet the attestation is bound to
+#[derive(Deserialize)]
+pub struct WalletQuery {
+    pub wallet: String,
+}
+
+// Body of POST /oracle/cosign/*: the query the primary oracle answered, its freshness fields
+// and the message it signed. The co-signer derives the attested values again itself
+#[derive(Deserialize)]
+pub struct CosignRequest<Q> {
+    #[serde(flatten)]
+    pub query: Q,
+    pub issued_at: i64,
+    #[serde(default)] // identity attestations carry no nonce
+    pub nonce: u64,
+    pub signed_message: Vec<u8>,
+}
+
+// To receive payload from GET /metrics/contribution?repo=owner/repo&wallet=...
+#[derive(Deserialize)]
+pub struct ContributionQuery {
+    pub repo: String,
+    pub wallet: String,
+}
+
+// To receive payload from GET /metrics/org?org=...&wallet=...
+#[derive(Deserialize)]
+pub struct OrgQuery {
+    pub org: String,
+    pub wallet: String,
+}
+
+// Used for org_badge, membership of the session user in the org
+#[derive(Deserialize)]
+pub struct GithubOrgMembership {
+    pub role: String,
+    pub state: String,
+}
+
+// Used for org_badge, for fetching a single org
+#[derive(Deserialize)]
+pub struct OrgInfo {
+    pub login: String,
+    pub public_repos: u32,
+}
+
+// Used for org_badge, entries of /orgs/{org}/repos
+#[derive(Deserialize)]
+pub struct OrgRepo {
+    pub stargazers_count: u32,
 }
 
 // used for Axum state for sharing database and github client
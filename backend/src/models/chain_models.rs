@@ -0,0 +1,73 @@
+// On-chain DevState as read by the metadata routes
+pub struct ChainDevState {
+    pub dev_addr: [u8; 32],
+    pub asset_address: [u8; 32],
+    pub hashed_username: [u8; 32],
+    pub repo_count: u32,
+    pub owned_repo_count: u32,
+    pub total_stars: u32,
+    pub total_commits: u32,
+    pub prs_merged: u32,
+    pub issues_closed: u32,
+    pub followers: u32,
+    pub account_age_days: u32,
+    pub reputation_level: u8,
+    pub verified_repos: u64,
+    pub vouch_count: u64,
+    pub vouch_score: u64,
+    pub vouches_given: u32,
+    pub last_updated: i64,
+}
+
+// On-chain RepoState as read by the metadata routes
+pub struct ChainRepoState {
+    pub owner: [u8; 32],
+    pub dev_badge: [u8; 32],
+    pub repo_name: Vec<u8>, // Zero padded on badges minted before names were trimmed
+    pub stars: u32,
+    pub commits: u32,
+    pub forks: u32,
+    pub open_issues: u32,
+    pub is_fork: bool,
+    pub lang1: Vec<u8>,
+    pub lang2: Vec<u8>,
+    pub last_updated: i64,
+    pub badge_bump: u8,
+}
+
+// On-chain ContributionState as read by the metadata routes
+pub struct ChainContributionState {
+    pub contributor: [u8; 32],
+    pub dev_badge: [u8; 32],
+    pub hashed_repo: [u8; 32],
+    pub repo_full_name: Vec<u8>,
+    pub prs_merged: u32,
+    pub commits: u32,
+    pub last_updated: i64,
+    pub badge_bump: u8,
+}
+
+// On-chain OrgState as read by the metadata routes
+pub struct ChainOrgState {
+    pub authority: [u8; 32],
+    pub public_repos: u32,
+    pub total_stars: u32,
+    pub member_count: u32,
+    pub linked_members: u32,
+    pub last_updated: i64,
+}
+
+// On-chain OrgMembership as read by the metadata routes
+pub struct ChainOrgMembership {
+    pub org_badge: [u8; 32],
+    pub dev: [u8; 32],
+    pub dev_badge: [u8; 32],
+    pub linked_at: i64,
+}
+
+// One level of the on-chain ScoringParams table
+pub struct ChainScoringTier {
+    pub min_repos: u32,
+    pub min_commits: u32,
+    pub min_account_age_days: u32,
+}
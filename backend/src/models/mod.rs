@@ -3,3 +3,6 @@ pub use auth_models::*;
 
 pub mod api_models;
 pub use api_models::*;
+
+pub mod chain_models;
+pub use chain_models::*;
@@ -0,0 +1,250 @@
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+
+use crate::{
+    IDENTITY_MIGRATION, IDENTITY_RELEASE, Session, api_models::*, contribution_message,
+    cosign_message, decode_wallet, derive_dev_metrics, dev_badge_message,
+    fetch_contribution_metrics, fetch_org_metrics, fetch_repo_metrics, get_session, hash_org,
+    hash_username, identity_move_message, org_badge_message, repo_badge_message, signer_key_id,
+    signer_public_key,
+};
+
+// Attestations older than this are not countersigned, the program enforces its own max age
+const MAX_COSIGN_AGE_SECS: i64 = 300;
+
+// A co-signing instance is a full oracle: the user logs in to it with GitHub too, and it
+// derives every attested value again from its own session. It never signs the primary's
+// fields, only its own result, which matches the primary's message when both saw the same data
+
+// Session of the user on this instance, from the session_id cookie
+async fn cosigner_session(state: &AppState, headers: &HeaderMap) -> anyhow::Result<Session> {
+    let session_id = headers
+        .get("cookie")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .split(';')
+        .find_map(|c| c.trim().strip_prefix("session_id="))
+        .unwrap_or("");
+
+    if session_id.is_empty() {
+        anyhow::bail!("Not authorized");
+    }
+
+    get_session(&state.db, session_id).await
+}
+
+fn check_cosign_age(issued_at: i64) -> anyhow::Result<()> {
+    let age = Utc::now().timestamp() - issued_at;
+    if !(0..=MAX_COSIGN_AGE_SECS).contains(&age) {
+        anyhow::bail!("Attestation is too old to countersign");
+    }
+
+    Ok(())
+}
+
+// Signs `message`, derived by this instance, if it's the message the primary signed
+fn countersign(message: anyhow::Result<[u8; 32]>, signed_message: &[u8]) -> Response {
+    let message = match message {
+        Ok(message) => message,
+        Err(e) => return Json(serde_json::json!({"error": e.to_string()})).into_response(),
+    };
+    if message.as_slice() != signed_message {
+        return (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "Metrics derived by this oracle differ from the attestation"
+            })),
+        )
+            .into_response();
+    }
+
+    Json(serde_json::json!({
+        "signature": cosign_message(&message),
+        "public_key_bytes": signer_public_key(),
+        "key_id": signer_key_id(),
+        "signed_message": message,
+    }))
+    .into_response()
+}
+
+// POST /api/oracle/cosign/dev
+pub async fn cosign_dev_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CosignRequest<WalletQuery>>,
+) -> Response {
+    let message = async {
+        check_cosign_age(request.issued_at)?;
+        let wallet = decode_wallet(&request.query.wallet)?;
+        let session = cosigner_session(&state, &headers).await?;
+        let metrics =
+            derive_dev_metrics(&state.client, &session.access_token, &session.username).await?;
+
+        Ok(dev_badge_message(
+            &wallet,
+            &hash_username(&session.username),
+            metrics.repo_count,
+            metrics.total_commits,
+            metrics.owned_repo_count,
+            metrics.total_stars,
+            metrics.prs_merged,
+            metrics.issues_closed,
+            metrics.followers,
+            metrics.account_age_days,
+            metrics.reputation_level,
+            request.issued_at,
+            request.nonce,
+        ))
+    }
+    .await;
+
+    countersign(message, &request.signed_message)
+}
+
+// POST /api/oracle/cosign/repo
+pub async fn cosign_repo_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CosignRequest<RepoQuery>>,
+) -> Response {
+    let message = async {
+        check_cosign_age(request.issued_at)?;
+        let wallet = decode_wallet(&request.query.wallet)?;
+        let session = cosigner_session(&state, &headers).await?;
+        let (stars, lang1, lang2, commits, fork_count, issues_open_count, is_forked) =
+            fetch_repo_metrics(
+                &state.client,
+                &session.access_token,
+                &session.username,
+                &request.query.repo,
+            )
+            .await?;
+
+        Ok(repo_badge_message(
+            &wallet,
+            &hash_username(&session.username),
+            &request.query.repo,
+            &lang1,
+            &lang2,
+            stars,
+            commits,
+            fork_count,
+            issues_open_count,
+            is_forked,
+            request.issued_at,
+            request.nonce,
+        ))
+    }
+    .await;
+
+    countersign(message, &request.signed_message)
+}
+
+// POST /api/oracle/cosign/contribution
+pub async fn cosign_contribution_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CosignRequest<ContributionQuery>>,
+) -> Response {
+    let message = async {
+        check_cosign_age(request.issued_at)?;
+        let wallet = decode_wallet(&request.query.wallet)?;
+        let session = cosigner_session(&state, &headers).await?;
+        let (repo_full_name, prs_merged, commits) = fetch_contribution_metrics(
+            &state.client,
+            &session.access_token,
+            &session.username,
+            &request.query.repo,
+        )
+        .await?;
+
+        Ok(contribution_message(
+            &wallet,
+            &hash_username(&session.username),
+            &repo_full_name,
+            prs_merged,
+            commits,
+            request.issued_at,
+            request.nonce,
+        ))
+    }
+    .await;
+
+    countersign(message, &request.signed_message)
+}
+
+// POST /api/oracle/cosign/org
+pub async fn cosign_org_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CosignRequest<OrgQuery>>,
+) -> Response {
+    let message = async {
+        check_cosign_age(request.issued_at)?;
+        let wallet = decode_wallet(&request.query.wallet)?;
+        let session = cosigner_session(&state, &headers).await?;
+        let (org, public_repos, total_stars, member_count) =
+            fetch_org_metrics(&state.client, &session.access_token, &request.query.org).await?;
+
+        Ok(org_badge_message(
+            &wallet,
+            &hash_org(&org),
+            public_repos,
+            total_stars,
+            member_count,
+            request.issued_at,
+            request.nonce,
+        ))
+    }
+    .await;
+
+    countersign(message, &request.signed_message)
+}
+
+// POST /api/oracle/cosign/identity/release
+pub async fn cosign_identity_release(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CosignRequest<ReleaseQuery>>,
+) -> Response {
+    let message = identity_move(&state, &headers, &request, IDENTITY_RELEASE).await;
+
+    countersign(message, &request.signed_message)
+}
+
+// POST /api/oracle/cosign/identity/migrate
+pub async fn cosign_identity_migration(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CosignRequest<ReleaseQuery>>,
+) -> Response {
+    let message = identity_move(&state, &headers, &request, IDENTITY_MIGRATION).await;
+
+    countersign(message, &request.signed_message)
+}
+
+// The GitHub login of this instance's session is what gets attested
+async fn identity_move(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &CosignRequest<ReleaseQuery>,
+    domain: &[u8],
+) -> anyhow::Result<[u8; 32]> {
+    check_cosign_age(request.issued_at)?;
+    let old_wallet = decode_wallet(&request.query.old_wallet)?;
+    let new_wallet = decode_wallet(&request.query.new_wallet)?;
+    let session = cosigner_session(state, headers).await?;
+
+    Ok(identity_move_message(
+        domain,
+        &hash_username(&session.username),
+        &old_wallet,
+        &new_wallet,
+        request.issued_at,
+    ))
+}
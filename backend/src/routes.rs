@@ -1,8 +1,23 @@
 use crate::AppState;
-use crate::gh_api::{dev_metrics, repo_metrics};
+use crate::gh_api::{
+    contribution_metrics, dev_metrics, identity_migrate, identity_release, org_metrics,
+    repo_metrics,
+};
 use crate::gh_auth::{check_auth, github_callback, github_login, root};
+use crate::metadata::{
+    contribution_badge_image, contribution_badge_metadata, dev_badge_image, dev_badge_metadata,
+    org_badge_image, org_badge_metadata, org_member_badge_metadata, repo_badge_image,
+    repo_badge_metadata,
+};
+use crate::oracle::{
+    cosign_contribution_metrics, cosign_dev_metrics, cosign_identity_migration,
+    cosign_identity_release, cosign_org_metrics, cosign_repo_metrics,
+};
 use axum::http;
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 use reqwest::{Method, header};
 use tower_http::cors::CorsLayer;
 
@@ -13,7 +28,7 @@ pub fn create_router(state: AppState) -> Router {
                 .parse::<http::HeaderValue>()
                 .unwrap(),
         )
-        .allow_methods(Method::GET)
+        .allow_methods([Method::GET, Method::POST])
         .allow_headers([header::CONTENT_TYPE, header::COOKIE])
         .allow_credentials(true);
 
@@ -24,6 +39,43 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/auth/check", get(check_auth))
         .route("/api/metrics/dev", get(dev_metrics))
         .route("/api/metrics/repo", get(repo_metrics))
+        .route("/api/metrics/contribution", get(contribution_metrics))
+        .route("/api/metrics/org", get(org_metrics))
+        .route("/api/oracle/cosign/dev", post(cosign_dev_metrics))
+        .route("/api/oracle/cosign/repo", post(cosign_repo_metrics))
+        .route(
+            "/api/oracle/cosign/contribution",
+            post(cosign_contribution_metrics),
+        )
+        .route("/api/oracle/cosign/org", post(cosign_org_metrics))
+        .route(
+            "/api/oracle/cosign/identity/release",
+            post(cosign_identity_release),
+        )
+        .route(
+            "/api/oracle/cosign/identity/migrate",
+            post(cosign_identity_migration),
+        )
+        .route("/api/identity/release", get(identity_release))
+        .route("/api/identity/migrate", get(identity_migrate))
+        .route("/metadata/dev/{asset}", get(dev_badge_metadata))
+        .route("/metadata/repo/{asset}", get(repo_badge_metadata))
+        .route("/metadata/image/dev/{level}", get(dev_badge_image))
+        .route("/metadata/org/{asset}", get(org_badge_metadata))
+        .route(
+            "/metadata/org_member/{asset}",
+            get(org_member_badge_metadata),
+        )
+        .route(
+            "/metadata/contribution/{asset}",
+            get(contribution_badge_metadata),
+        )
+        .route("/metadata/image/repo", get(repo_badge_image))
+        .route("/metadata/image/org", get(org_badge_image))
+        .route(
+            "/metadata/image/contribution",
+            get(contribution_badge_image),
+        )
         .layer(cors)
         .with_state(state)
 }
@@ -1,9 +1,48 @@
 use ed25519_dalek::{Signer, SigningKey};
 use sha2::{Digest, Sha256};
-use std::{env, io::Read};
+use std::env;
 
-pub fn sign_dev_badge_metrics(
-    username: &str,
+// Id of the active signing key, must match `backend_key_id` in the on-chain GhostConfig.
+// Defaults to 0, the id init_config assigns to the first backend key
+pub fn signer_key_id() -> u32 {
+    env::var("GhostCheck_Signer_Key_Id")
+        .ok()
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(0)
+}
+
+// Loads the secret of the active key: GhostCheck_Signer_Secret_<key_id> once a key id is set,
+// so a rotated key can be staged next to the old one, otherwise GhostCheck_Signer_Secret.
+// A missing secret fails instead of signing with another key
+fn load_signing_key() -> SigningKey {
+    let secret_var = match env::var("GhostCheck_Signer_Key_Id") {
+        Ok(_) => format!("GhostCheck_Signer_Secret_{}", signer_key_id()),
+        Err(_) => String::from("GhostCheck_Signer_Secret"),
+    };
+    let secret_hex =
+        env::var(&secret_var).unwrap_or_else(|_| panic!("Missing signer secret {}", secret_var));
+    let secret_bytes = hex::decode(secret_hex).unwrap();
+
+    SigningKey::from_bytes(
+        &secret_bytes
+            .try_into()
+            .expect("signer key : Must be 32 bytes"),
+    )
+}
+
+// Decodes the base58 wallet address sent by the frontend into the raw 32 byte pubkey
+pub fn decode_wallet(wallet: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = bs58::decode(wallet).into_vec()?;
+
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Wallet address must decode to 32 bytes"))
+}
+
+// Canonical dev badge message, rebuilt the same way by the program and by co-signing oracles
+pub fn dev_badge_message(
+    wallet: &[u8; 32],
+    hashed_username: &[u8; 32],
     repo_count: u32,
     total_commits: u32,
     original_repos: u32,
@@ -13,19 +52,14 @@ pub fn sign_dev_badge_metrics(
     followers: u32,
     account_age_days: u32,
     reputation_level: u8,
-) -> (Vec<u8>, [u8; 32], Vec<u8>) {
-    let secret_hex = env::var("GhostCheck_Signer_Secret").unwrap();
-    let secret_bytes = hex::decode(secret_hex).unwrap();
-
-    let signing_key = SigningKey::from_bytes(&secret_bytes.try_into().expect("Must be 32 bytes"));
-
-    // hash username to 32 bytes to match Solana program
-    let mut hasher = Sha256::new();
-    hasher.update(username);
-    let hashed_username: [u8; 32] = hasher.finalize().into();
-
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
+    // Bind the attestation to the wallet so nobody else can submit it
     let mut hash = Sha256::new();
-    hash.update(&hashed_username);
+    hash.update(b"dev_badge");
+    hash.update(hashed_username);
+    hash.update(wallet);
     hash.update(repo_count.to_be_bytes());
     hash.update(total_commits.to_be_bytes());
     hash.update(original_repos.to_be_bytes());
@@ -34,8 +68,48 @@ pub fn sign_dev_badge_metrics(
     hash.update(issues_closed.to_be_bytes());
     hash.update(followers.to_be_bytes());
     hash.update(account_age_days.to_be_bytes());
-    hash.update(&[reputation_level]);
-    let hashed_message = hash.finalize();
+    hash.update([reputation_level]);
+    hash.update(issued_at.to_be_bytes());
+    hash.update(nonce.to_be_bytes());
+
+    hash.finalize().into()
+}
+
+pub fn sign_dev_badge_metrics(
+    wallet: &[u8; 32],
+    username: &str,
+    repo_count: u32,
+    total_commits: u32,
+    original_repos: u32,
+    total_stars: u32,
+    prs_merged: u32,
+    issues_closed: u32,
+    followers: u32,
+    account_age_days: u32,
+    reputation_level: u8,
+    issued_at: i64,
+    nonce: u64,
+) -> (Vec<u8>, [u8; 32], Vec<u8>) {
+    let signing_key = load_signing_key();
+
+    // hash username to 32 bytes to match Solana program
+    let hashed_username = hash_username(username);
+
+    let hashed_message = dev_badge_message(
+        wallet,
+        &hashed_username,
+        repo_count,
+        total_commits,
+        original_repos,
+        total_stars,
+        prs_merged,
+        issues_closed,
+        followers,
+        account_age_days,
+        reputation_level,
+        issued_at,
+        nonce,
+    );
 
     println!("Hashed Message : {:?}", hashed_message);
 
@@ -46,60 +120,287 @@ pub fn sign_dev_badge_metrics(
     )
 }
 
+// Sha256 of a GitHub login, how usernames are stored and signed on-chain
+pub fn hash_username(username: &str) -> [u8; 32] {
+    Sha256::digest(username).into()
+}
+
+// Canonical repo badge message, rebuilt the same way by the program and by co-signing oracles.
+// Variable length fields carry a u16 length so ("foo", "Rust") can't be read as ("fooR", "ust")
+pub fn repo_badge_message(
+    wallet: &[u8; 32],
+    hashed_username: &[u8; 32],
+    repo_name: &str,
+    lang1: &[u8],
+    lang2: &[u8],
+    stars: u32,
+    commits: u32,
+    fork_counts: u32,
+    issues_open_count: u32,
+    is_fork: u8,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"repo_badge");
+    hasher.update(hashed_username);
+    hasher.update(wallet);
+    for field in [repo_name.as_bytes(), lang1, lang2] {
+        hasher.update((field.len() as u16).to_be_bytes());
+        hasher.update(field);
+    }
+    hasher.update(stars.to_be_bytes());
+    hasher.update(commits.to_be_bytes());
+    hasher.update(fork_counts.to_be_bytes());
+    hasher.update(issues_open_count.to_be_bytes());
+    hasher.update([is_fork]);
+    hasher.update(issued_at.to_be_bytes());
+    hasher.update(nonce.to_be_bytes());
+
+    hasher.finalize().into()
+}
+
 pub fn sign_repo_badge_metrics(
+    wallet: &[u8; 32],
     username: &str,
     repo_name: &str,
-    lang1: &Vec<u8>,
-    lang2: &Vec<u8>,
+    lang1: &[u8],
+    lang2: &[u8],
     stars: u32,
     commits: u32,
     fork_counts: u32,
     issues_open_count: u32,
     is_fork: u8,
+    issued_at: i64,
+    nonce: u64,
 ) -> (Vec<u8>, [u8; 32], Vec<u8>) {
-    let signer_hex = env::var("GhostCheck_Signer_Secret").expect("Error parsing env variable");
-    let signer_bytes = hex::decode(signer_hex).unwrap();
+    let signing_key = load_signing_key();
 
-    let signing_key = SigningKey::from_bytes(
-        &signer_bytes
-            .try_into()
-            .expect("signer key : Must be 32 bytes"),
+    // Make the username 32 bytes to match solana program
+    let hashed_username = hash_username(username);
+
+    let hashed_message = repo_badge_message(
+        wallet,
+        &hashed_username,
+        repo_name,
+        lang1,
+        lang2,
+        stars,
+        commits,
+        fork_counts,
+        issues_open_count,
+        is_fork,
+        issued_at,
+        nonce,
     );
 
-    // Make the username 32 bytes to match solana program
+    println!("Hashed Message : {:?}", hashed_message);
+
+    let signature = signing_key.sign(&hashed_message);
+
+    (
+        signature.to_bytes().to_vec(),
+        hashed_username,
+        hashed_message.to_vec(),
+    )
+}
+
+// Canonical contribution badge message
+pub fn contribution_message(
+    wallet: &[u8; 32],
+    hashed_username: &[u8; 32],
+    repo_full_name: &str,
+    prs_merged: u32,
+    commits: u32,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
     let mut hasher = Sha256::new();
-    hasher.update(username);
-    let hashed_username: [u8; 32] = hasher.finalize().into();
+    hasher.update(b"contribution");
+    hasher.update(hashed_username);
+    hasher.update(wallet);
+    hasher.update(repo_full_name.as_bytes());
+    hasher.update(prs_merged.to_be_bytes());
+    hasher.update(commits.to_be_bytes());
+    hasher.update(issued_at.to_be_bytes());
+    hasher.update(nonce.to_be_bytes());
+
+    hasher.finalize().into()
+}
+
+// Attests work merged by `username` into `repo_full_name`, a repo owned by someone else
+pub fn sign_contribution_metrics(
+    wallet: &[u8; 32],
+    username: &str,
+    repo_full_name: &str,
+    prs_merged: u32,
+    commits: u32,
+    issued_at: i64,
+    nonce: u64,
+) -> (Vec<u8>, [u8; 32], Vec<u8>) {
+    let signing_key = load_signing_key();
+
+    let hashed_username = hash_username(username);
+    let hashed_message = contribution_message(
+        wallet,
+        &hashed_username,
+        repo_full_name,
+        prs_merged,
+        commits,
+        issued_at,
+        nonce,
+    );
+
+    (
+        signing_key.sign(&hashed_message).to_bytes().to_vec(),
+        hashed_username,
+        hashed_message.to_vec(),
+    )
+}
+
+// Sha256 of the lowercased org login, GitHub treats org names case-insensitively
+pub fn hash_org(org: &str) -> [u8; 32] {
+    Sha256::digest(org.to_lowercase()).into()
+}
 
-    // Hash the messages
+// Canonical org badge message
+pub fn org_badge_message(
+    wallet: &[u8; 32],
+    hashed_org: &[u8; 32],
+    public_repos: u32,
+    total_stars: u32,
+    member_count: u32,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
     let mut hasher = Sha256::new();
+    hasher.update(b"org_badge");
+    hasher.update(hashed_org);
+    hasher.update(wallet);
+    hasher.update(public_repos.to_be_bytes());
+    hasher.update(total_stars.to_be_bytes());
+    hasher.update(member_count.to_be_bytes());
+    hasher.update(issued_at.to_be_bytes());
+    hasher.update(nonce.to_be_bytes());
+
+    hasher.finalize().into()
+}
+
+// Attests the stats of a GitHub org for the admin wallet minting its org badge
+pub fn sign_org_badge_metrics(
+    wallet: &[u8; 32],
+    org: &str,
+    public_repos: u32,
+    total_stars: u32,
+    member_count: u32,
+    issued_at: i64,
+    nonce: u64,
+) -> (Vec<u8>, [u8; 32], Vec<u8>) {
+    let signing_key = load_signing_key();
+
+    let hashed_org = hash_org(org);
+    let hashed_message = org_badge_message(
+        wallet,
+        &hashed_org,
+        public_repos,
+        total_stars,
+        member_count,
+        issued_at,
+        nonce,
+    );
+
+    (
+        signing_key.sign(&hashed_message).to_bytes().to_vec(),
+        hashed_org,
+        hashed_message.to_vec(),
+    )
+}
+
+// Domains of the identity attestations, they keep a release attestation from being replayed
+// as a migration and back
+pub const IDENTITY_RELEASE: &[u8] = b"identity_release";
+pub const IDENTITY_MIGRATION: &[u8] = b"identity_migration";
+
+// Canonical identity move message: the GitHub account behind `hashed_username` moves from
+// `old_wallet` to `new_wallet`
+pub fn identity_move_message(
+    domain: &[u8],
+    hashed_username: &[u8; 32],
+    old_wallet: &[u8; 32],
+    new_wallet: &[u8; 32],
+    issued_at: i64,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
     hasher.update(hashed_username);
-    hasher.update(repo_name.as_bytes());
-    hasher.update(&lang1);
-    hasher.update(&lang2);
-    hasher.update(&stars.to_be_bytes());
-    hasher.update(&commits.to_be_bytes());
-    hasher.update(fork_counts.to_be_bytes());
-    hasher.update(issues_open_count.to_be_bytes());
-    hasher.update([is_fork]);
-    let hashed_message = hasher.finalize();
+    hasher.update(old_wallet);
+    hasher.update(new_wallet);
+    hasher.update(issued_at.to_be_bytes());
 
-    println!("Hashed Message : {:?}", hashed_message);
+    hasher.finalize().into()
+}
 
-    let signature = signing_key.sign(&hashed_message);
+// Attests that the GitHub account behind `username` moves its dev badge identity
+// from `old_wallet` to `new_wallet`, consumed by the program's release_identity
+pub fn sign_identity_release(
+    username: &str,
+    old_wallet: &[u8; 32],
+    new_wallet: &[u8; 32],
+    issued_at: i64,
+) -> (Vec<u8>, [u8; 32], Vec<u8>) {
+    sign_identity_move(
+        IDENTITY_RELEASE,
+        username,
+        old_wallet,
+        new_wallet,
+        issued_at,
+    )
+}
+
+// Same attestation for the program's request_migration, which carries the dev state,
+// badges and vouches over to `new_wallet` instead of dropping them
+pub fn sign_identity_migration(
+    username: &str,
+    old_wallet: &[u8; 32],
+    new_wallet: &[u8; 32],
+    issued_at: i64,
+) -> (Vec<u8>, [u8; 32], Vec<u8>) {
+    sign_identity_move(
+        IDENTITY_MIGRATION,
+        username,
+        old_wallet,
+        new_wallet,
+        issued_at,
+    )
+}
+
+fn sign_identity_move(
+    domain: &[u8],
+    username: &str,
+    old_wallet: &[u8; 32],
+    new_wallet: &[u8; 32],
+    issued_at: i64,
+) -> (Vec<u8>, [u8; 32], Vec<u8>) {
+    let signing_key = load_signing_key();
+
+    let hashed_username = hash_username(username);
+    let hashed_message =
+        identity_move_message(domain, &hashed_username, old_wallet, new_wallet, issued_at);
 
     (
-        signature.to_bytes().to_vec(),
+        signing_key.sign(&hashed_message).to_bytes().to_vec(),
         hashed_username,
         hashed_message.to_vec(),
     )
 }
 
-pub fn signer_public_key() -> Vec<u8> {
-    let secret_hex = env::var("GhostCheck_Signer_Secret").unwrap();
-    let secret_bytes = hex::decode(secret_hex).unwrap();
+// Signs a message this instance derived itself, used when co-signing another oracle's attestation
+pub fn cosign_message(message: &[u8]) -> Vec<u8> {
+    load_signing_key().sign(message).to_bytes().to_vec()
+}
 
-    let signing_key = SigningKey::from_bytes(&secret_bytes.try_into().expect("Must be 32 bytes"));
+pub fn signer_public_key() -> Vec<u8> {
+    let signing_key = load_signing_key();
     println!("{:?}", signing_key.verifying_key().to_bytes());
     signing_key.verifying_key().as_bytes().to_vec()
 }
@@ -4,7 +4,7 @@ use wasm_bindgen_futures::spawn_local;
 
 use crate::{
     app::{GithubState, WalletState},
-    services::wallet,
+    services::{api::COSIGNERS, wallet},
 };
 
 #[component]
@@ -45,8 +45,14 @@ pub fn Navbar() -> impl IntoView {
             //Github Button
             {move || {
                 if let Some(username) = github.username.get() {
+                    // Co-signing oracles keep their own GitHub session
                     view!{
                         <span class="nav-github-connected">"🐙 "{username}</span>
+                        {COSIGNERS.iter().enumerate().map(|(i, cosigner)| view! {
+                            <a href=format!("{}/api/auth/github", cosigner) class="nav-github-btn">
+                                {format!("🔑 Co-signer {}", i + 1)}
+                            </a>
+                        }).collect_view()}
                     }.into_any()
                 } else {
                     view! {
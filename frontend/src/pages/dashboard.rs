@@ -2,7 +2,7 @@ use leptos::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::{
-    app::GithubState,
+    app::{GithubState, WalletState},
     services::{
         api::{self, DevMetrics, RepoMetrics},
         solana,
@@ -13,6 +13,7 @@ use crate::{
 pub fn Dashboard() -> impl IntoView {
     // Github context
     let github = expect_context::<GithubState>();
+    let wallet = expect_context::<WalletState>();
 
     // Track if user already has a dev badge (hides mint section)
     let (has_dev_badge, set_has_dev_badge) = signal(false);
@@ -35,11 +36,16 @@ pub fn Dashboard() -> impl IntoView {
 
     // Fetch Dev Metrics when the button is clicked
     let fetch_dev = move |_| {
+        // Attestations are bound to the wallet, so it has to be connected first
+        let Some(address) = wallet.address.get() else {
+            set_error.set(Some("Connect your wallet first".to_string()));
+            return;
+        };
         set_loading.set(true);
         set_error.set(None);
 
         spawn_local(async move {
-            match api::fetch_github_metrics().await {
+            match api::fetch_github_metrics(&address).await {
                 Ok(dev_metrics) => {
                     log::info!(
                         "Got metrics: {} repos, {} commits",
@@ -57,44 +63,30 @@ pub fn Dashboard() -> impl IntoView {
         });
     };
 
-    // Mint dev badge — calls JS bridge via wasm-bindgen
+    // Mint dev badge, built by ghost_check_client and signed through the JS bridge
     let mint_dev = move |_| {
         let metrics = dev_metrics.get();
         if metrics.is_none() {
             set_mint_error.set(Some("Fetch metrics first".to_string()));
             return;
         }
+        let Some(address) = wallet.address.get() else {
+            set_mint_error.set(Some("Connect your wallet first".to_string()));
+            return;
+        };
         let m = metrics.unwrap();
         set_minting.set(true);
         set_mint_error.set(None);
         set_mint_result.set(None);
 
         spawn_local(async move {
-            match solana::build_and_send_dev_badge_tx(
-                m.signature.clone(),
-                m.signed_message.clone(),
-                m.public_key_bytes.clone(),
-                m.hashed_username.clone(),
-                m.repo_count,
-                m.owned_repo_count,
-                m.total_stars,
-                m.total_commit,
-                m.prs_merged,
-                m.issues_closed,
-                m.followers,
-                m.account_age_days,
-                m.reputation_level,
-            )
-            .await
-            {
-                Ok(sig) => {
-                    let tx_sig = sig.as_string().unwrap_or("unknown".to_string());
+            match solana::mint_dev_badge(&address, &m).await {
+                Ok(tx_sig) => {
                     log::info!("Dev badge minted! Tx: {}", tx_sig);
                     set_mint_result.set(Some(tx_sig));
                     set_has_dev_badge.set(true); //Hide Mint section
                 }
-                Err(e) => {
-                    let err_msg = format!("{:?}", e);
+                Err(err_msg) => {
                     log::error!("Mint failed: {}", err_msg);
                     set_mint_error.set(Some(err_msg));
                 }
@@ -113,9 +105,14 @@ pub fn Dashboard() -> impl IntoView {
             set_repo_loading.set(false);
             return;
         }
+        let Some(address) = wallet.address.get() else {
+            set_repo_error.set(Some(String::from("Connect your wallet first")));
+            set_repo_loading.set(false);
+            return;
+        };
 
         spawn_local(async move {
-            match api::fetch_repo_metrics(&repo).await {
+            match api::fetch_repo_metrics(&repo, &address).await {
                 Ok(repo_metrics) => {
                     log::info!(
                         "Repo: {:?}, commits: {}, stars: {}",
@@ -141,39 +138,22 @@ pub fn Dashboard() -> impl IntoView {
             set_mint_error.set(Some("Fetch repo metrics first".to_string()));
             return;
         }
+        let Some(address) = wallet.address.get() else {
+            set_mint_error.set(Some("Connect your wallet first".to_string()));
+            return;
+        };
         let m = metrics.unwrap();
         set_minting.set(true);
         set_mint_error.set(None);
         set_mint_result.set(None);
 
         spawn_local(async move {
-            // Pad repo_name_bytes to 32 bytes for the PDA seed
-            let mut repo_name_padded = m.repo_name_bytes.clone();
-            repo_name_padded.resize(32, 0);
-
-            match solana::build_and_send_repo_badge_tx(
-                m.signature.clone(),
-                m.signed_message.clone(),
-                m.public_key_bytes.clone(),
-                repo_name_padded,
-                m.hashed_username.clone(),
-                m.stars,
-                m.commits,
-                m.fork_count,
-                m.issues_open_count,
-                m.is_fork,
-                m.lang1_bytes.clone(),
-                m.lang2_bytes.clone(),
-            )
-            .await
-            {
-                Ok(sig) => {
-                    let tx_sig = sig.as_string().unwrap_or("unknown".to_string());
+            match solana::mint_repo_badge(&address, &m).await {
+                Ok(tx_sig) => {
                     log::info!("Repo badge minted! Tx: {}", tx_sig);
                     set_mint_result.set(Some(tx_sig));
                 }
-                Err(e) => {
-                    let err_msg = format!("{:?}", e);
+                Err(err_msg) => {
                     log::error!("Repo mint failed: {}", err_msg);
                     set_mint_error.set(Some(err_msg));
                 }
@@ -2,7 +2,7 @@ use leptos::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::{
-    app::GithubState,
+    app::{GithubState, WalletState},
     services::{
         api::{self, DevMetrics, RepoMetrics},
         solana,
@@ -12,6 +12,7 @@ use crate::{
 #[component]
 pub fn Profile() -> impl IntoView {
     let github = expect_context::<GithubState>();
+    let wallet = expect_context::<WalletState>();
 
     // Dev data
     let (dev_metrics, set_dev_metrics) = signal(Option::<DevMetrics>::None);
@@ -34,10 +35,14 @@ pub fn Profile() -> impl IntoView {
 
     // Fetch dev metrics on load
     let fetch_dev = move |_| {
+        let Some(address) = wallet.address.get() else {
+            set_error.set(Some("Connect your wallet first".into()));
+            return;
+        };
         set_loading.set(true);
         set_error.set(None);
         spawn_local(async move {
-            match api::fetch_github_metrics().await {
+            match api::fetch_github_metrics(&address).await {
                 Ok(m) => set_dev_metrics.set(Some(m)),
                 Err(e) => set_error.set(Some(e)),
             }
@@ -51,34 +56,19 @@ pub fn Profile() -> impl IntoView {
         if metrics.is_none() {
             return;
         }
+        let Some(address) = wallet.address.get() else {
+            set_update_error.set(Some("Connect your wallet first".into()));
+            return;
+        };
         let m = metrics.unwrap();
         set_updating.set(true);
         set_update_error.set(None);
         set_update_result.set(None);
 
         spawn_local(async move {
-            match solana::build_and_send_update_dev_badge_tx(
-                m.signature.clone(),
-                m.signed_message.clone(),
-                m.public_key_bytes.clone(),
-                m.hashed_username.clone(),
-                m.repo_count,
-                m.owned_repo_count,
-                m.total_stars,
-                m.total_commit,
-                m.prs_merged,
-                m.issues_closed,
-                m.followers,
-                m.account_age_days,
-                m.reputation_level,
-            )
-            .await
-            {
-                Ok(sig) => {
-                    let tx = sig.as_string().unwrap_or("unknown".into());
-                    set_update_result.set(Some(tx));
-                }
-                Err(e) => set_update_error.set(Some(format!("{:?}", e))),
+            match solana::update_dev_badge(&address, &m).await {
+                Ok(tx) => set_update_result.set(Some(tx)),
+                Err(e) => set_update_error.set(Some(e)),
             }
             set_updating.set(false);
         });
@@ -91,23 +81,18 @@ pub fn Profile() -> impl IntoView {
             set_vouch_error.set(Some("Enter a wallet address".into()));
             return;
         }
+        let Some(address) = wallet.address.get() else {
+            set_vouch_error.set(Some("Connect your wallet first".into()));
+            return;
+        };
         set_vouching.set(true);
         set_vouch_error.set(None);
         set_vouch_result.set(None);
 
         spawn_local(async move {
-            // Convert base58 string to bytes
-            // For now we'll pass the raw string bytes — you can improve this later
-            let addr_bytes = bs58_decode(&target);
-            match addr_bytes {
-                None => set_vouch_error.set(Some("Invalid wallet address".into())),
-                Some(bytes) => match solana::build_and_send_vouch_tx(bytes).await {
-                    Ok(sig) => {
-                        let tx = sig.as_string().unwrap_or("unknown".into());
-                        set_vouch_result.set(Some(tx));
-                    }
-                    Err(e) => set_vouch_error.set(Some(format!("{:?}", e))),
-                },
+            match solana::vouch_for_dev(&address, &target).await {
+                Ok(tx) => set_vouch_result.set(Some(tx)),
+                Err(e) => set_vouch_error.set(Some(e)),
             }
             set_vouching.set(false);
         });
@@ -227,41 +212,3 @@ pub fn Profile() -> impl IntoView {
         </section>
     }
 }
-
-// Simple base58 decoder for wallet addresses
-fn bs58_decode(input: &str) -> Option<Vec<u8>> {
-    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
-    let mut result = vec![0u8; 32];
-    let mut scratch = Vec::new();
-
-    for c in input.bytes() {
-        let mut carry = match ALPHABET.iter().position(|&x| x == c) {
-            Some(idx) => idx as u32,
-            None => return None,
-        };
-        for byte in scratch.iter_mut() {
-            carry += 58 * (*byte as u32);
-            *byte = (carry & 0xff) as u8;
-            carry >>= 8;
-        }
-        while carry > 0 {
-            scratch.push((carry & 0xff) as u8);
-            carry >>= 8;
-        }
-    }
-
-    // Add leading zeros
-    for c in input.bytes() {
-        if c == b'1' {
-            scratch.push(0);
-        } else {
-            break;
-        }
-    }
-
-    scratch.reverse();
-    if scratch.len() != 32 {
-        return None;
-    }
-    Some(scratch)
-}
@@ -4,6 +4,18 @@ use web_sys::RequestCredentials;
 
 const BACKEND: &str = "http://localhost:3000";
 
+// Co-signing oracle instances, their signatures go next to the backend's to meet the program's
+// oracle_threshold. Each derives the attestation again from its own GitHub login, so the user
+// authorizes GitHub on every one of them. Empty while the threshold is 1
+pub const COSIGNERS: &[&str] = &[];
+
+// Countersignature returned by a co-signer's /api/oracle/cosign/*
+#[derive(Deserialize, Debug, Clone)]
+pub struct Cosignature {
+    pub signature: Vec<u8>,
+    pub public_key_bytes: Vec<u8>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct DevMetrics {
     pub hashed_username: Vec<u8>,
@@ -16,9 +28,13 @@ pub struct DevMetrics {
     pub followers: u32,
     pub account_age_days: u32,
     pub reputation_level: u8,
+    pub issued_at: i64,
+    pub nonce: u64,
     pub signature: Vec<u8>,
     pub public_key_bytes: Vec<u8>,
     pub signed_message: Vec<u8>,
+    #[serde(default)]
+    pub cosignatures: Vec<Cosignature>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -32,13 +48,17 @@ pub struct RepoMetrics {
     pub fork_count: u32,
     pub issues_open_count: u32,
     pub is_fork: u8,
+    pub issued_at: i64,
+    pub nonce: u64,
     pub signature: Vec<u8>,
     pub public_key_bytes: Vec<u8>,
     pub signed_message: Vec<u8>,
+    #[serde(default)]
+    pub cosignatures: Vec<Cosignature>,
 }
 
-pub async fn fetch_github_metrics() -> Result<DevMetrics, String> {
-    let response = Request::get(&format!("{}/api/metrics/dev", BACKEND))
+pub async fn fetch_github_metrics(wallet: &str) -> Result<DevMetrics, String> {
+    let response = Request::get(&format!("{}/api/metrics/dev?wallet={}", BACKEND, wallet))
         .credentials(RequestCredentials::Include)
         .send()
         .await
@@ -47,26 +67,81 @@ pub async fn fetch_github_metrics() -> Result<DevMetrics, String> {
     if !response.ok() {
         return Err(format!("Request Failed : {}", response.status()));
     }
-    response
+    let mut metrics = response
         .json::<DevMetrics>()
         .await
-        .map_err(|e| format!("Failed to parse error: {:?}", e))
+        .map_err(|e| format!("Failed to parse error: {:?}", e))?;
+
+    metrics.cosignatures = gather_cosignatures(
+        "dev",
+        serde_json::json!({
+            "wallet": wallet,
+            "issued_at": metrics.issued_at,
+            "nonce": metrics.nonce,
+            "signed_message": metrics.signed_message,
+        }),
+    )
+    .await;
+    Ok(metrics)
 }
 
-pub async fn fetch_repo_metrics(repo_name: &str) -> Result<RepoMetrics, String> {
-    let response = Request::get(&format!("{}/api/metrics/repo?repo={}", BACKEND, repo_name))
-        .credentials(RequestCredentials::Include)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request: {}", e))?;
+pub async fn fetch_repo_metrics(repo_name: &str, wallet: &str) -> Result<RepoMetrics, String> {
+    let response = Request::get(&format!(
+        "{}/api/metrics/repo?repo={}&wallet={}",
+        BACKEND, repo_name, wallet
+    ))
+    .credentials(RequestCredentials::Include)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
 
     if !response.ok() {
         return Err(format!("Request Failed: {}", response.status()));
     }
-    response
+    let mut metrics = response
         .json::<RepoMetrics>()
         .await
-        .map_err(|e| format!("Failed to parse response : {}", e))
+        .map_err(|e| format!("Failed to parse response : {}", e))?;
+
+    metrics.cosignatures = gather_cosignatures(
+        "repo",
+        serde_json::json!({
+            "repo": repo_name,
+            "wallet": wallet,
+            "issued_at": metrics.issued_at,
+            "nonce": metrics.nonce,
+            "signed_message": metrics.signed_message,
+        }),
+    )
+    .await;
+    Ok(metrics)
+}
+
+// Asks every co-signer to derive the attestation again and countersign it. A co-signer that
+// is unreachable or saw other metrics is skipped, the program rejects the transaction if too
+// few oracles signed
+async fn gather_cosignatures(kind: &str, body: serde_json::Value) -> Vec<Cosignature> {
+    let mut cosignatures = Vec::new();
+
+    for cosigner in COSIGNERS {
+        let request = Request::post(&format!("{}/api/oracle/cosign/{}", cosigner, kind))
+            .credentials(RequestCredentials::Include)
+            .json(&body);
+        let response = match request {
+            Ok(request) => request.send().await,
+            Err(e) => Err(e),
+        };
+
+        match response {
+            Ok(response) => match response.json::<Cosignature>().await {
+                Ok(cosignature) => cosignatures.push(cosignature),
+                Err(e) => log::warn!("{} did not countersign: {:?}", cosigner, e),
+            },
+            Err(e) => log::warn!("{} unreachable: {}", cosigner, e),
+        }
+    }
+
+    cosignatures
 }
 
 #[derive(Deserialize, Debug, Clone)]
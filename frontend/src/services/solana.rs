@@ -1,74 +1,173 @@
+use std::str::FromStr;
+
+use ghost_check_client::{Instruction, OracleSignature, Pubkey, args, instructions};
+use js_sys::{Array, Object, Reflect, Uint8Array};
 use wasm_bindgen::prelude::*;
 
+use crate::services::api::{Cosignature, DevMetrics, RepoMetrics};
+
 #[wasm_bindgen]
 extern "C" {
-    // Calls window.buildAndSendDevBadgeTx() defined in js/solana.js
-    #[wasm_bindgen(js_name = buildAndSendDevBadgeTx, catch)]
-    pub async fn build_and_send_dev_badge_tx(
-        signature: Vec<u8>,
-        message: Vec<u8>,
-        public_key: Vec<u8>,
-        username: Vec<u8>,
-        repo_count: u32,
-        owned_repo_count: u32,
-        total_stars: u32,
-        total_commits: u32,
-        prs_merged: u32,
-        issues_closed: u32,
-        followers: u32,
-        account_age_days: u32,
-        reputation_level: u8,
-    ) -> Result<JsValue, JsValue>;
+    // Calls window.sendInstructions() defined in js/solana.js, Phantom signs and sends them
+    #[wasm_bindgen(js_name = sendInstructions, catch)]
+    async fn send_instructions(instructions: Array) -> Result<JsValue, JsValue>;
+}
+
+// Instructions are built by ghost_check_client, the JS bridge only wraps them for web3.js
+fn to_js(instruction: &Instruction) -> JsValue {
+    let keys = Array::new();
+    for meta in &instruction.accounts {
+        let key = Object::new();
+        set(
+            &key,
+            "pubkey",
+            Uint8Array::from(meta.pubkey.as_ref()).into(),
+        );
+        set(&key, "isSigner", meta.is_signer.into());
+        set(&key, "isWritable", meta.is_writable.into());
+        keys.push(&key);
+    }
+
+    let object = Object::new();
+    set(
+        &object,
+        "programId",
+        Uint8Array::from(instruction.program_id.as_ref()).into(),
+    );
+    set(&object, "keys", keys.into());
+    set(
+        &object,
+        "data",
+        Uint8Array::from(&instruction.data[..]).into(),
+    );
+    object.into()
+}
+
+fn set(object: &Object, key: &str, value: JsValue) {
+    // Setting a property on a plain object can't fail
+    let _ = Reflect::set(object, &key.into(), &value);
+}
+
+async fn send(instructions: &[Instruction]) -> Result<String, String> {
+    let array = instructions.iter().map(to_js).collect::<Array>();
+    let signature = send_instructions(array)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(signature.as_string().unwrap_or("unknown".to_string()))
+}
 
-    // Calls window.buildAndSendRepoBadgeTx() defined in js/solana.js
-    #[wasm_bindgen(js_name = buildAndSendRepoBadgeTx, catch)]
-    pub async fn build_and_send_repo_badge_tx(
-        signature: Vec<u8>,
-        message: Vec<u8>,
-        public_key: Vec<u8>,
-        repo_name_padded: Vec<u8>,
-        username_hashed: Vec<u8>,
-        stars: u32,
-        commits: u32,
-        forks: u32,
-        open_issues: u32,
-        is_fork: u8,
-        lang1: Vec<u8>,
-        lang2: Vec<u8>,
-    ) -> Result<JsValue, JsValue>;
+fn pubkey(address: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(address).map_err(|_| format!("Invalid wallet address {}", address))
+}
+
+fn bytes32(bytes: &[u8], name: &str) -> Result<[u8; 32], String> {
+    bytes
+        .try_into()
+        .map_err(|_| format!("{} must be 32 bytes", name))
+}
+
+// Backend signature first, then the oracle co-signatures
+fn oracle_signatures(
+    signature: &[u8],
+    public_key: &[u8],
+    cosignatures: &[Cosignature],
+) -> Result<Vec<OracleSignature>, String> {
+    std::iter::once((signature, public_key))
+        .chain(
+            cosignatures
+                .iter()
+                .map(|c| (&c.signature[..], &c.public_key_bytes[..])),
+        )
+        .map(|(signature, public_key)| {
+            Ok(OracleSignature {
+                public_key: bytes32(public_key, "Oracle public key")?,
+                signature: signature
+                    .try_into()
+                    .map_err(|_| "Oracle signature must be 64 bytes".to_string())?,
+            })
+        })
+        .collect()
+}
 
-    #[wasm_bindgen(js_name = buildAndSendUpdateDevBadgeTx, catch)]
-    pub async fn build_and_send_update_dev_badge_tx(
-        signature: Vec<u8>,
-        message: Vec<u8>,
-        public_key: Vec<u8>,
-        username: Vec<u8>,
-        repo_count: u32,
-        owned_repo_count: u32,
-        total_stars: u32,
-        total_commits: u32,
-        prs_merged: u32,
-        issues_closed: u32,
-        followers: u32,
-        account_age_days: u32,
-        reputation_level: u8,
-    ) -> Result<JsValue, JsValue>;
+pub async fn mint_dev_badge(wallet: &str, m: &DevMetrics) -> Result<String, String> {
+    let signatures = oracle_signatures(&m.signature, &m.public_key_bytes, &m.cosignatures)?;
+    let args = args::MintDevBadge {
+        username: bytes32(&m.hashed_username, "Hashed username")?,
+        repo_count: m.repo_count,
+        owned_repo_count: m.owned_repo_count,
+        total_stars: m.total_stars,
+        total_commits: m.total_commit,
+        prs_merged: m.prs_merged,
+        issues_closed: m.issues_closed,
+        followers: m.followers,
+        account_age_days: m.account_age_days,
+        reputation_level: m.reputation_level,
+        issued_at: m.issued_at,
+        nonce: m.nonce,
+    };
+    send(&instructions::mint_dev_badge(
+        &pubkey(wallet)?,
+        args,
+        &signatures,
+    ))
+    .await
+}
+
+pub async fn update_dev_badge(wallet: &str, m: &DevMetrics) -> Result<String, String> {
+    let signatures = oracle_signatures(&m.signature, &m.public_key_bytes, &m.cosignatures)?;
+    let args = args::UpdateDevBadge {
+        username: bytes32(&m.hashed_username, "Hashed username")?,
+        repo_count: m.repo_count,
+        owned_repo_count: m.owned_repo_count,
+        total_stars: m.total_stars,
+        total_commits: m.total_commit,
+        prs_merged: m.prs_merged,
+        issues_closed: m.issues_closed,
+        followers: m.followers,
+        account_age_days: m.account_age_days,
+        reputation_level: m.reputation_level,
+        issued_at: m.issued_at,
+        nonce: m.nonce,
+    };
+    send(&instructions::update_dev_badge(
+        &pubkey(wallet)?,
+        args,
+        &signatures,
+    ))
+    .await
+}
 
-    #[wasm_bindgen(js_name = buildAndSendUpdateRepoBadgeTx, catch)]
-    pub async fn build_and_send_update_repo_badge_tx(
-        signature: Vec<u8>,
-        message: Vec<u8>,
-        public_key: Vec<u8>,
-        repo_name_padded: Vec<u8>,
-        username_hashed: Vec<u8>,
-        stars: u32,
-        commits: u32,
-        forks: u32,
-        open_issues: u32,
-        lang1: Vec<u8>,
-        lang2: Vec<u8>,
-    ) -> Result<JsValue, JsValue>;
+pub async fn mint_repo_badge(wallet: &str, m: &RepoMetrics) -> Result<String, String> {
+    let signatures = oracle_signatures(&m.signature, &m.public_key_bytes, &m.cosignatures)?;
+    // Repo names are zero padded to 32 bytes for the PDA seed
+    let mut repo_name_padded = [0u8; 32];
+    if m.repo_name_bytes.len() > repo_name_padded.len() {
+        return Err("Repo name is longer than 32 bytes".to_string());
+    }
+    repo_name_padded[..m.repo_name_bytes.len()].copy_from_slice(&m.repo_name_bytes);
+
+    let args = args::MintRepoBadge {
+        repo_name_padded,
+        username_hashed: bytes32(&m.hashed_username, "Hashed username")?,
+        stars: m.stars,
+        commits: m.commits,
+        forks: m.fork_count,
+        open_issues: m.issues_open_count,
+        is_fork: m.is_fork,
+        lang1: m.lang1_bytes.clone(),
+        lang2: m.lang2_bytes.clone(),
+        issued_at: m.issued_at,
+        nonce: m.nonce,
+    };
+    send(&instructions::mint_repo_badge(
+        &pubkey(wallet)?,
+        args,
+        &signatures,
+    ))
+    .await
+}
 
-    #[wasm_bindgen(js_name = buildAndSendVouchTx, catch)]
-    pub async fn build_and_send_vouch_tx(target_addr: Vec<u8>) -> Result<JsValue, JsValue>;
+pub async fn vouch_for_dev(wallet: &str, target: &str) -> Result<String, String> {
+    let instruction = instructions::vouch_for_dev(&pubkey(wallet)?, &pubkey(target)?);
+    send(&[instruction]).await
 }
@@ -0,0 +1,72 @@
+// Decoders of the program accounts from their raw data, as fetched over RPC
+use anchor_lang::{error::ErrorCode, AnchorDeserialize, Discriminator, Result};
+use ghost_check::state::{
+    ContributionState, DevState, GhostConfig, IdentityClaim, MigrationRequest, OrgMembership,
+    OrgState, RepoState, Revocation, ScoringParams, Versioned, VouchRecord, VouchTombstone,
+};
+
+pub trait DecodeAccount: Sized {
+    // Checks the discriminator, versioned accounts still on a legacy layout come back upgraded
+    fn decode(data: &[u8]) -> Result<Self>;
+}
+
+// Any program account, picked by its discriminator
+#[derive(Clone)]
+pub enum GhostAccount {
+    GhostConfig(GhostConfig),
+    ScoringParams(ScoringParams),
+    DevState(DevState),
+    RepoState(RepoState),
+    ContributionState(ContributionState),
+    IdentityClaim(IdentityClaim),
+    OrgState(OrgState),
+    OrgMembership(OrgMembership),
+    Revocation(Revocation),
+    VouchRecord(VouchRecord),
+    VouchTombstone(VouchTombstone),
+    MigrationRequest(MigrationRequest),
+}
+
+macro_rules! decode_accounts {
+    ($($account:ident),*) => {
+        $(
+            impl DecodeAccount for $account {
+                fn decode(data: &[u8]) -> Result<Self> {
+                    <$account as Versioned>::try_deserialize_compat(data)
+                }
+            }
+        )*
+
+        impl DecodeAccount for GhostAccount {
+            fn decode(data: &[u8]) -> Result<Self> {
+                $(
+                    if data.starts_with($account::DISCRIMINATOR) {
+                        return $account::decode(data).map(GhostAccount::$account);
+                    }
+                )*
+                Err(ErrorCode::AccountDiscriminatorMismatch.into())
+            }
+        }
+    };
+}
+
+decode_accounts!(
+    GhostConfig,
+    ScoringParams,
+    DevState,
+    RepoState,
+    ContributionState,
+    IdentityClaim,
+    OrgState,
+    OrgMembership,
+    Revocation,
+    VouchRecord,
+    VouchTombstone,
+    MigrationRequest
+);
+
+// Return data of the reads (verify_dev, query_dev, verify_dev_criteria, query_dev_criteria),
+// as found in a simulation's `returnData`. E.g. a CriteriaResult and its `failed()` bitmap
+pub fn decode_return_data<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    T::deserialize(&mut &data[..]).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
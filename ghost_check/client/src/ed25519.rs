@@ -0,0 +1,51 @@
+// Ed25519 precompile instruction carrying the backend signatures the program checks
+use anchor_lang::solana_program::instruction::Instruction;
+use ghost_check::ED25519_PROGRAM_ID;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+// verify_signature only accepts offsets into the precompile instruction itself
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// Signature of an attestation by the backend, or by one oracle seat once set_oracles ran
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleSignature {
+    pub public_key: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+// Every signature is checked against the same message, stored once after the offsets.
+// The instruction must come before the attested one in the same transaction
+pub fn ed25519_instruction(message: &[u8], signatures: &[OracleSignature]) -> Instruction {
+    let message_offset =
+        SIGNATURE_OFFSETS_START + signatures.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let mut data = vec![signatures.len() as u8, 0];
+    let mut payload = message.to_vec();
+
+    for signature in signatures {
+        let public_key_offset = message_offset + payload.len();
+        let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+        payload.extend_from_slice(&signature.public_key);
+        payload.extend_from_slice(&signature.signature);
+
+        for field in [
+            signature_offset as u16,
+            CURRENT_INSTRUCTION,
+            public_key_offset as u16,
+            CURRENT_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    data.extend_from_slice(&payload);
+
+    Instruction {
+        program_id: ED25519_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
@@ -0,0 +1,886 @@
+// Instruction builders, one per program instruction. Attested instructions come with the
+// Ed25519 instruction in front, signing the message the program rebuilds from their arguments.
+// Instructions with many arguments take the program's own argument struct from `args`
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID,
+    },
+    system_program, InstructionData, ToAccountMetas,
+};
+use ghost_check::{
+    accounts, contribution_badge_message, dev_badge_message, identity_migration_message,
+    identity_release_message, instruction as args, org_badge_message, repo_badge_message,
+    state::{ScoringTier, SCORING_TIERS},
+    trim_repo_name, Criteria,
+};
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{ed25519_instruction, pda, OracleSignature, ID};
+
+fn instruction(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+fn attested(
+    message: &[u8],
+    signatures: &[OracleSignature],
+    instruction: Instruction,
+) -> [Instruction; 2] {
+    [ed25519_instruction(message, signatures), instruction]
+}
+
+pub fn init_config(admin: &Pubkey, args: args::InitConfig) -> Instruction {
+    instruction(
+        accounts::InitConfig {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+            system_program: system_program::ID,
+            this_program: ID,
+            program_data: pda::program_data(),
+        },
+        args,
+    )
+}
+
+pub fn init_scoring_params(admin: &Pubkey, tiers: [ScoringTier; SCORING_TIERS]) -> Instruction {
+    instruction(
+        accounts::InitScoringParams {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+            scoring_params: pda::scoring_params(),
+            system_program: system_program::ID,
+        },
+        args::InitScoringParams { tiers },
+    )
+}
+
+pub fn update_scoring_params(admin: &Pubkey, tiers: [ScoringTier; SCORING_TIERS]) -> Instruction {
+    instruction(
+        accounts::UpdateScoringParams {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+            scoring_params: pda::scoring_params(),
+        },
+        args::UpdateScoringParams { tiers },
+    )
+}
+
+pub fn update_config(admin: &Pubkey, args: args::UpdateConfig) -> Instruction {
+    instruction(
+        accounts::UpdateConfig {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+        },
+        args,
+    )
+}
+
+pub fn rotate_backend_key(
+    admin: &Pubkey,
+    new_backend_pubkey: [u8; 32],
+    new_key_id: u32,
+    grace_slots: u64,
+) -> Instruction {
+    instruction(
+        accounts::RotateBackendKey {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+        },
+        args::RotateBackendKey {
+            new_backend_pubkey,
+            new_key_id,
+            grace_slots,
+        },
+    )
+}
+
+pub fn set_oracles(admin: &Pubkey, oracles: Vec<[u8; 32]>, threshold: u8) -> Instruction {
+    instruction(
+        accounts::SetOracles {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+        },
+        args::SetOracles { oracles, threshold },
+    )
+}
+
+pub fn propose_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    instruction(
+        accounts::ProposeAdmin {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+        },
+        args::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn accept_admin(new_admin: &Pubkey) -> Instruction {
+    instruction(
+        accounts::AcceptAdmin {
+            new_admin: *new_admin,
+            ghost_config: pda::ghost_config(),
+        },
+        args::AcceptAdmin {},
+    )
+}
+
+pub fn set_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    instruction(
+        accounts::SetPaused {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+        },
+        args::SetPaused { paused },
+    )
+}
+
+pub fn mint_dev_badge(
+    dev: &Pubkey,
+    args: args::MintDevBadge,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = dev_badge_message(
+        dev,
+        &args.username,
+        args.repo_count,
+        args.total_commits,
+        args.owned_repo_count,
+        args.total_stars,
+        args.prs_merged,
+        args.issues_closed,
+        args.followers,
+        args.account_age_days,
+        args.reputation_level,
+        args.issued_at,
+        args.nonce,
+    );
+    let mint = instruction(
+        accounts::DevBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            identity_claim: pda::identity_claim(&args.username),
+            scoring_params: pda::scoring_params(),
+            revocation: pda::revocation(dev),
+            asset: pda::dev_badge(dev),
+            system_program: system_program::ID,
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+        },
+        args,
+    );
+    attested(&message, signatures, mint)
+}
+
+pub fn update_dev_badge(
+    dev: &Pubkey,
+    args: args::UpdateDevBadge,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = dev_badge_message(
+        dev,
+        &args.username,
+        args.repo_count,
+        args.total_commits,
+        args.owned_repo_count,
+        args.total_stars,
+        args.prs_merged,
+        args.issues_closed,
+        args.followers,
+        args.account_age_days,
+        args.reputation_level,
+        args.issued_at,
+        args.nonce,
+    );
+    let update = instruction(
+        accounts::UpdateDevBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            migration: pda::migration(dev),
+            scoring_params: pda::scoring_params(),
+            dev_badge: pda::dev_badge(dev),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args,
+    );
+    attested(&message, signatures, update)
+}
+
+pub fn burn_dev_badge(dev: &Pubkey, hashed_username: &[u8; 32]) -> Instruction {
+    instruction(
+        accounts::BurnDevBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            identity_claim: pda::identity_claim(hashed_username),
+            dev_badge: pda::dev_badge(dev),
+            core_program: CORE_PROGRAM_ID,
+        },
+        args::BurnDevBadge {},
+    )
+}
+
+pub fn mint_repo_badge(
+    dev: &Pubkey,
+    args: args::MintRepoBadge,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = repo_badge_message(
+        dev,
+        &args.username_hashed,
+        trim_repo_name(&args.repo_name_padded),
+        &args.lang1,
+        &args.lang2,
+        args.stars,
+        args.commits,
+        args.forks,
+        args.open_issues,
+        args.is_fork,
+        args.issued_at,
+        args.nonce,
+    );
+    let mint = instruction(
+        accounts::RepoBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            migration: pda::migration(dev),
+            dev_badge: pda::dev_badge(dev),
+            repo_state: pda::repo_state(dev, &args.repo_name_padded),
+            repo_badge: pda::repo_badge(dev, &args.repo_name_padded),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args,
+    );
+    attested(&message, signatures, mint)
+}
+
+pub fn update_repo_badge(
+    dev: &Pubkey,
+    args: args::UpdateRepoBadge,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    // The program signs the stored is_fork, forks never get a badge so it is always 0
+    let message = repo_badge_message(
+        dev,
+        &args.username_hashed,
+        trim_repo_name(&args.repo_name_padded),
+        &args.lang1,
+        &args.lang2,
+        args.stars,
+        args.commits,
+        args.forks,
+        args.open_issues,
+        0,
+        args.issued_at,
+        args.nonce,
+    );
+    let update = instruction(
+        accounts::UpdateRepoBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            migration: pda::migration(dev),
+            dev_badge: pda::dev_badge(dev),
+            repo_state: pda::repo_state(dev, &args.repo_name_padded),
+            repo_badge: pda::repo_badge(dev, &args.repo_name_padded),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args,
+    );
+    attested(&message, signatures, update)
+}
+
+pub fn burn_repo_badge(dev: &Pubkey, repo_name_padded: [u8; 32]) -> Instruction {
+    instruction(
+        accounts::BurnRepoBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            dev_badge: pda::dev_badge(dev),
+            repo_state: pda::repo_state(dev, &repo_name_padded),
+            repo_badge: pda::repo_badge(dev, &repo_name_padded),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::BurnRepoBadge { repo_name_padded },
+    )
+}
+
+pub fn mint_contribution_badge(
+    dev: &Pubkey,
+    args: args::MintContributionBadge,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = contribution_badge_message(
+        dev,
+        &args.username_padded,
+        &args.repo_full_name,
+        args.prs_merged,
+        args.commits,
+        args.issued_at,
+        args.nonce,
+    );
+    let mint = instruction(
+        accounts::ContributionBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            migration: pda::migration(dev),
+            dev_badge: pda::dev_badge(dev),
+            contribution_state: pda::contribution_state(dev, &args.hashed_repo),
+            contribution_badge: pda::contribution_badge(dev, &args.hashed_repo),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args,
+    );
+    attested(&message, signatures, mint)
+}
+
+pub fn burn_contribution_badge(dev: &Pubkey, hashed_repo: [u8; 32]) -> Instruction {
+    instruction(
+        accounts::BurnContributionBadge {
+            dev: *dev,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            dev_badge: pda::dev_badge(dev),
+            contribution_state: pda::contribution_state(dev, &hashed_repo),
+            contribution_badge: pda::contribution_badge(dev, &hashed_repo),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::BurnContributionBadge { hashed_repo },
+    )
+}
+
+pub fn mint_org_badge(
+    authority: &Pubkey,
+    args: args::MintOrgBadge,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = org_badge_message(
+        authority,
+        &args.hashed_org,
+        args.public_repos,
+        args.total_stars,
+        args.member_count,
+        args.issued_at,
+        args.nonce,
+    );
+    let mint = instruction(
+        accounts::OrgBadge {
+            authority: *authority,
+            ghost_config: pda::ghost_config(),
+            org_state: pda::org_state(&args.hashed_org),
+            org_badge: pda::org_badge(&args.hashed_org),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args,
+    );
+    attested(&message, signatures, mint)
+}
+
+// Signed by both the dev and the org authority
+pub fn link_org_member(dev: &Pubkey, authority: &Pubkey, hashed_org: &[u8; 32]) -> Instruction {
+    instruction(
+        accounts::LinkOrgMember {
+            dev: *dev,
+            authority: *authority,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            migration: pda::migration(dev),
+            org_state: pda::org_state(hashed_org),
+            org_badge: pda::org_badge(hashed_org),
+            org_membership: pda::org_membership(hashed_org, dev),
+            member_badge: pda::org_member_badge(hashed_org, dev),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::LinkOrgMember {},
+    )
+}
+
+pub fn verify_dev(dev: &Pubkey, min_lvl: u8, max_staleness: Option<i64>) -> Instruction {
+    instruction(
+        accounts::VerifyDev {
+            target_dev_state: pda::dev_state(dev),
+            revocation: pda::revocation(dev),
+            migration: pda::migration(dev),
+            ghost_config: pda::ghost_config(),
+        },
+        args::VerifyDev {
+            dev_addr: dev.to_bytes(),
+            min_lvl,
+            max_staleness,
+        },
+    )
+}
+
+// Reads the reputation of `dev`, a revoked dev is reported instead of failing
+pub fn query_dev(dev: &Pubkey) -> Instruction {
+    instruction(
+        accounts::VerifyDev {
+            target_dev_state: pda::dev_state(dev),
+            revocation: pda::revocation(dev),
+            migration: pda::migration(dev),
+            ghost_config: pda::ghost_config(),
+        },
+        args::QueryDev {
+            dev_addr: dev.to_bytes(),
+        },
+    )
+}
+
+pub fn verify_dev_criteria(dev: &Pubkey, criteria: Criteria) -> Instruction {
+    instruction(
+        accounts::VerifyDev {
+            target_dev_state: pda::dev_state(dev),
+            revocation: pda::revocation(dev),
+            migration: pda::migration(dev),
+            ghost_config: pda::ghost_config(),
+        },
+        args::VerifyDevCriteria {
+            _dev_addr: dev.to_bytes(),
+            criteria,
+        },
+    )
+}
+
+// Same criteria as verify_dev_criteria, the result carries the passed bitmap instead of failing
+pub fn query_dev_criteria(dev: &Pubkey, criteria: Criteria) -> Instruction {
+    instruction(
+        accounts::VerifyDev {
+            target_dev_state: pda::dev_state(dev),
+            revocation: pda::revocation(dev),
+            migration: pda::migration(dev),
+            ghost_config: pda::ghost_config(),
+        },
+        args::QueryDevCriteria {
+            dev_addr: dev.to_bytes(),
+            criteria,
+        },
+    )
+}
+
+pub fn vouch_for_dev(voucher: &Pubkey, target: &Pubkey) -> Instruction {
+    instruction(
+        accounts::Vouch {
+            voucher: *voucher,
+            ghost_config: pda::ghost_config(),
+            voucher_dev_state: pda::dev_state(voucher),
+            target_dev_state: pda::dev_state(target),
+            target_dev_badge: pda::dev_badge(target),
+            vouch_record: pda::vouch_record(voucher, target),
+            vouch_tombstone: pda::vouch_tombstone(voucher, target),
+            voucher_migration: pda::migration(voucher),
+            target_migration: pda::migration(target),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::VouchForDev {
+            target_addr: target.to_bytes(),
+        },
+    )
+}
+
+pub fn revoke_vouch(voucher: &Pubkey, target: &Pubkey) -> Instruction {
+    instruction(
+        accounts::RevokeVouch {
+            voucher: *voucher,
+            ghost_config: pda::ghost_config(),
+            voucher_dev_state: pda::dev_state(voucher),
+            target_dev_state: pda::dev_state(target),
+            target_dev_badge: pda::dev_badge(target),
+            vouch_record: pda::vouch_record(voucher, target),
+            vouch_tombstone: pda::vouch_tombstone(voucher, target),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::RevokeVouch {
+            target_addr: target.to_bytes(),
+        },
+    )
+}
+
+// Every repo and contribution badge of the dev has to be passed, Core only burns an empty
+// collection. `repos` are padded repo names, `contributions` hashed "owner/repo" names
+pub fn revoke_dev_badge(
+    admin: &Pubkey,
+    dev: &Pubkey,
+    reason: u8,
+    repos: &[[u8; 32]],
+    contributions: &[[u8; 32]],
+) -> Instruction {
+    let mut revoke = instruction(
+        accounts::RevokeDevBadge {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+            dev_badge: pda::dev_badge(dev),
+            revocation: pda::revocation(dev),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::RevokeDevBadge {
+            dev_addr: dev.to_bytes(),
+            reason,
+        },
+    );
+    for repo_name in repos {
+        revoke.accounts.extend([
+            AccountMeta::new(pda::repo_state(dev, repo_name), false),
+            AccountMeta::new(pda::repo_badge(dev, repo_name), false),
+        ]);
+    }
+    for hashed_repo in contributions {
+        revoke.accounts.extend([
+            AccountMeta::new(pda::contribution_state(dev, hashed_repo), false),
+            AccountMeta::new(pda::contribution_badge(dev, hashed_repo), false),
+        ]);
+    }
+    revoke
+}
+
+// Closes a vouch given by a revoked dev, its target stops counting it
+pub fn prune_vouch(admin: &Pubkey, voucher: &Pubkey, target: &Pubkey) -> Instruction {
+    instruction(
+        accounts::PruneVouch {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+            revocation: pda::revocation(voucher),
+            vouch_record: pda::vouch_record(voucher, target),
+            target_dev_state: pda::dev_state(target),
+            target_dev_badge: pda::dev_badge(target),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::PruneVouch {
+            voucher_addr: voucher.to_bytes(),
+            target_addr: target.to_bytes(),
+        },
+    )
+}
+
+// Sets the vouches given by a dev upgraded from the baseline, counted off-chain by the admin
+pub fn settle_vouches_given(admin: &Pubkey, dev: &Pubkey, vouches_given: u32) -> Instruction {
+    instruction(
+        accounts::SettleVouchesGiven {
+            admin: *admin,
+            ghost_config: pda::ghost_config(),
+            dev_state: pda::dev_state(dev),
+        },
+        args::SettleVouchesGiven {
+            _dev_addr: dev.to_bytes(),
+            vouches_given,
+        },
+    )
+}
+
+// Signed by the new wallet, the backend attests the GitHub account moved to it
+pub fn release_identity(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    username: [u8; 32],
+    issued_at: i64,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = identity_release_message(&username, old_wallet, new_wallet, issued_at);
+    let release = instruction(
+        accounts::ReleaseIdentity {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            identity_claim: pda::identity_claim(&username),
+            old_dev_state: pda::dev_state(old_wallet),
+            old_dev_badge: pda::dev_badge(old_wallet),
+            old_wallet: *old_wallet,
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: CORE_PROGRAM_ID,
+        },
+        args::ReleaseIdentity {
+            username,
+            issued_at,
+        },
+    );
+    attested(&message, signatures, release)
+}
+
+pub fn request_migration(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    username: [u8; 32],
+    issued_at: i64,
+    signatures: &[OracleSignature],
+) -> [Instruction; 2] {
+    let message = identity_migration_message(&username, old_wallet, new_wallet, issued_at);
+    let request = instruction(
+        accounts::RequestMigration {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            identity_claim: pda::identity_claim(&username),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            migration: pda::migration(old_wallet),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            system_program: system_program::ID,
+        },
+        args::RequestMigration {
+            username,
+            issued_at,
+        },
+    );
+    attested(&message, signatures, request)
+}
+
+pub fn approve_migration(old_wallet: &Pubkey) -> Instruction {
+    instruction(
+        accounts::ApproveMigration {
+            old_wallet: *old_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+        },
+        args::ApproveMigration {},
+    )
+}
+
+pub fn cancel_migration(old_wallet: &Pubkey, new_wallet: &Pubkey) -> Instruction {
+    instruction(
+        accounts::CancelMigration {
+            old_wallet: *old_wallet,
+            migration: pda::migration(old_wallet),
+            new_wallet: *new_wallet,
+        },
+        args::CancelMigration {},
+    )
+}
+
+pub fn migrate_dev_identity(new_wallet: &Pubkey, old_wallet: &Pubkey) -> Instruction {
+    instruction(
+        accounts::MigrateDevIdentity {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            new_dev_state: pda::dev_state(new_wallet),
+            revocation: pda::revocation(new_wallet),
+            new_dev_badge: pda::dev_badge(new_wallet),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::MigrateDevIdentity {},
+    )
+}
+
+pub fn migrate_repo_badge(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    repo_name_padded: [u8; 32],
+) -> Instruction {
+    instruction(
+        accounts::MigrateRepoBadge {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            old_dev_badge: pda::dev_badge(old_wallet),
+            old_repo_state: pda::repo_state(old_wallet, &repo_name_padded),
+            old_repo_badge: pda::repo_badge(old_wallet, &repo_name_padded),
+            new_dev_state: pda::dev_state(new_wallet),
+            new_dev_badge: pda::dev_badge(new_wallet),
+            new_repo_state: pda::repo_state(new_wallet, &repo_name_padded),
+            new_repo_badge: pda::repo_badge(new_wallet, &repo_name_padded),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::MigrateRepoBadge { repo_name_padded },
+    )
+}
+
+// `hashed_repo` is the hashed lowercase "owner/repo" name the badge was minted for
+pub fn migrate_contribution_badge(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    hashed_repo: [u8; 32],
+) -> Instruction {
+    instruction(
+        accounts::MigrateContributionBadge {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            old_dev_badge: pda::dev_badge(old_wallet),
+            old_contribution_state: pda::contribution_state(old_wallet, &hashed_repo),
+            old_contribution_badge: pda::contribution_badge(old_wallet, &hashed_repo),
+            new_dev_state: pda::dev_state(new_wallet),
+            new_dev_badge: pda::dev_badge(new_wallet),
+            new_contribution_state: pda::contribution_state(new_wallet, &hashed_repo),
+            new_contribution_badge: pda::contribution_badge(new_wallet, &hashed_repo),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::MigrateContributionBadge { hashed_repo },
+    )
+}
+
+// Address a record of `wallet` lives under once old_wallet moved to new_wallet
+fn forward(old_wallet: &Pubkey, new_wallet: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    if wallet == old_wallet {
+        *new_wallet
+    } else {
+        *wallet
+    }
+}
+
+// The old wallet is either the voucher or the target of the record
+pub fn migrate_vouch_record(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    voucher: &Pubkey,
+    target: &Pubkey,
+) -> Instruction {
+    let counterpart = if voucher == old_wallet {
+        target
+    } else {
+        voucher
+    };
+    instruction(
+        accounts::MigrateVouchRecord {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            new_dev_state: pda::dev_state(new_wallet),
+            counterpart_dev_state: pda::dev_state(counterpart),
+            counterpart_dev_badge: pda::dev_badge(counterpart),
+            old_vouch_record: pda::vouch_record(voucher, target),
+            new_vouch_record: pda::vouch_record(
+                &forward(old_wallet, new_wallet, voucher),
+                &forward(old_wallet, new_wallet, target),
+            ),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::MigrateVouchRecord {
+            voucher: *voucher,
+            target: *target,
+        },
+    )
+}
+
+// Same as migrate_vouch_record for a tombstone left by or for the old wallet
+pub fn migrate_vouch_tombstone(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    voucher: &Pubkey,
+    target: &Pubkey,
+) -> Instruction {
+    let counterpart = if voucher == old_wallet {
+        target
+    } else {
+        voucher
+    };
+    instruction(
+        accounts::MigrateVouchTombstone {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            new_dev_state: pda::dev_state(new_wallet),
+            counterpart_dev_state: pda::dev_state(counterpart),
+            old_vouch_tombstone: pda::vouch_tombstone(voucher, target),
+            new_vouch_tombstone: pda::vouch_tombstone(
+                &forward(old_wallet, new_wallet, voucher),
+                &forward(old_wallet, new_wallet, target),
+            ),
+            system_program: system_program::ID,
+        },
+        args::MigrateVouchTombstone {
+            voucher: *voucher,
+            target: *target,
+        },
+    )
+}
+
+pub fn finish_migration(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    hashed_username: &[u8; 32],
+) -> Instruction {
+    instruction(
+        accounts::FinishMigration {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: pda::dev_state(old_wallet),
+            old_dev_badge: pda::dev_badge(old_wallet),
+            new_dev_state: pda::dev_state(new_wallet),
+            new_dev_badge: pda::dev_badge(new_wallet),
+            identity_claim: pda::identity_claim(hashed_username),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::FinishMigration {},
+    )
+}
+
+// Moves old_wallet's membership of `hashed_org` to new_wallet, before or after finish_migration
+pub fn migrate_org_membership(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    hashed_org: &[u8; 32],
+) -> Instruction {
+    instruction(
+        accounts::MigrateOrgMembership {
+            new_wallet: *new_wallet,
+            ghost_config: pda::ghost_config(),
+            migration: pda::migration(old_wallet),
+            old_wallet: *old_wallet,
+            new_dev_state: pda::dev_state(new_wallet),
+            org_state: pda::org_state(hashed_org),
+            org_badge: pda::org_badge(hashed_org),
+            old_org_membership: pda::org_membership(hashed_org, old_wallet),
+            old_member_badge: pda::org_member_badge(hashed_org, old_wallet),
+            new_org_membership: pda::org_membership(hashed_org, new_wallet),
+            new_member_badge: pda::org_member_badge(hashed_org, new_wallet),
+            core_program: CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        args::MigrateOrgMembership {},
+    )
+}
+
+// Upgrades any versioned account stored with a legacy layout
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    instruction(
+        accounts::MigrateAccount {
+            payer: *payer,
+            account: *account,
+            system_program: system_program::ID,
+        },
+        args::MigrateAccount {},
+    )
+}
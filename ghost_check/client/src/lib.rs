@@ -0,0 +1,20 @@
+// Client side of ghost_check: PDA helpers, instruction builders and account decoders shared by
+// the backend, CLIs and the frontend. Layouts come from the program crate itself, so the
+// client can't drift from it. No native-only dependencies, it builds for wasm32 as well
+pub mod accounts;
+pub use accounts::*;
+
+pub mod ed25519;
+pub use ed25519::*;
+
+pub mod instructions;
+
+pub mod pda;
+
+pub use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+};
+pub use ghost_check::{
+    hash_repo_full_name, instruction as args, state, Criteria, CriteriaResult, DevVerification, ID,
+};
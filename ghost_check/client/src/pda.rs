@@ -0,0 +1,87 @@
+// Addresses of the program accounts, derived with the same seeds as the program
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+
+use crate::ID;
+
+pub fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+// Checked by init_config, only the upgrade authority creates the config
+pub fn program_data() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn ghost_config() -> Pubkey {
+    find(&[b"ghost_config"])
+}
+
+pub fn scoring_params() -> Pubkey {
+    find(&[b"scoring_params"])
+}
+
+pub fn dev_state(dev: &Pubkey) -> Pubkey {
+    find(&[b"dev_state", dev.as_ref()])
+}
+
+pub fn dev_badge(dev: &Pubkey) -> Pubkey {
+    find(&[b"dev_badge", dev.as_ref()])
+}
+
+pub fn identity_claim(hashed_username: &[u8; 32]) -> Pubkey {
+    find(&[b"identity_claim", hashed_username])
+}
+
+pub fn revocation(dev: &Pubkey) -> Pubkey {
+    find(&[b"revocation", dev.as_ref()])
+}
+
+// Repo badges live under the dev collection, `repo_name` is zero padded to 32 bytes
+pub fn repo_state(dev: &Pubkey, repo_name: &[u8; 32]) -> Pubkey {
+    find(&[b"repo_state", dev_badge(dev).as_ref(), repo_name])
+}
+
+pub fn repo_badge(dev: &Pubkey, repo_name: &[u8; 32]) -> Pubkey {
+    find(&[b"repo_badge", dev_badge(dev).as_ref(), repo_name])
+}
+
+pub fn vouch_record(voucher: &Pubkey, target: &Pubkey) -> Pubkey {
+    find(&[b"vouch_record", voucher.as_ref(), target.as_ref()])
+}
+
+pub fn vouch_tombstone(voucher: &Pubkey, target: &Pubkey) -> Pubkey {
+    find(&[b"vouch_tombstone", voucher.as_ref(), target.as_ref()])
+}
+
+pub fn migration(old_wallet: &Pubkey) -> Pubkey {
+    find(&[b"migration", old_wallet.as_ref()])
+}
+
+pub fn org_state(hashed_org: &[u8; 32]) -> Pubkey {
+    find(&[b"org_state", hashed_org])
+}
+
+pub fn org_badge(hashed_org: &[u8; 32]) -> Pubkey {
+    find(&[b"org_badge", hashed_org])
+}
+
+pub fn org_membership(hashed_org: &[u8; 32], dev: &Pubkey) -> Pubkey {
+    find(&[b"org_member", org_badge(hashed_org).as_ref(), dev.as_ref()])
+}
+
+pub fn org_member_badge(hashed_org: &[u8; 32], dev: &Pubkey) -> Pubkey {
+    find(&[
+        b"org_member_badge",
+        org_badge(hashed_org).as_ref(),
+        dev.as_ref(),
+    ])
+}
+
+// `hashed_repo` is `hash_repo_full_name` of the "owner/repo" name
+pub fn contribution_state(dev: &Pubkey, hashed_repo: &[u8; 32]) -> Pubkey {
+    find(&[b"contribution_state", dev_badge(dev).as_ref(), hashed_repo])
+}
+
+pub fn contribution_badge(dev: &Pubkey, hashed_repo: &[u8; 32]) -> Pubkey {
+    find(&[b"contribution_badge", dev_badge(dev).as_ref(), hashed_repo])
+}
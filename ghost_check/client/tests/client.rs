@@ -0,0 +1,168 @@
+use agave_feature_set::FeatureSet;
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, AccountSerialize, AnchorSerialize,
+    Discriminator, InstructionData,
+};
+use ghost_check::dev_badge_message;
+use ghost_check_client::{
+    args, decode_return_data, ed25519_instruction, instructions, pda,
+    state::{IdentityClaim, Versioned, VouchRecord},
+    Criteria, CriteriaResult, DecodeAccount, GhostAccount, OracleSignature, ID,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn sign(signer: &Keypair, message: &[u8]) -> OracleSignature {
+    OracleSignature {
+        public_key: signer.pubkey().to_bytes(),
+        signature: signer.sign_message(message).into(),
+    }
+}
+
+// Runs the Ed25519 precompile the validator runs on the instruction
+fn precompile_accepts(ix: &Instruction) -> bool {
+    agave_precompiles::ed25519::verify(&ix.data, &[&ix.data], &FeatureSet::all_enabled()).is_ok()
+}
+
+fn mint_dev_badge_args(username: [u8; 32]) -> args::MintDevBadge {
+    args::MintDevBadge {
+        username,
+        repo_count: 8,
+        owned_repo_count: 6,
+        total_stars: 120,
+        total_commits: 60,
+        prs_merged: 14,
+        issues_closed: 9,
+        followers: 30,
+        account_age_days: 30,
+        reputation_level: 2,
+        issued_at: 1_700_000_000,
+        nonce: 1,
+    }
+}
+
+#[test]
+fn ed25519_instruction_passes_the_precompile() {
+    let message = [7u8; 32];
+    let oracles = [Keypair::new(), Keypair::new()];
+    let signatures: Vec<_> = oracles.iter().map(|o| sign(o, &message)).collect();
+
+    let ix = ed25519_instruction(&message, &signatures);
+    assert_eq!(ix.data[0], 2);
+    assert!(precompile_accepts(&ix));
+
+    // Signed by the oracles, but over another message
+    let other = ed25519_instruction(&[8u8; 32], &signatures);
+    assert!(!precompile_accepts(&other));
+}
+
+#[test]
+fn attested_builders_sign_the_program_message() {
+    let backend = Keypair::new();
+    let dev = Pubkey::new_unique();
+    let args = mint_dev_badge_args([3u8; 32]);
+    let message = dev_badge_message(
+        &dev,
+        &args.username,
+        args.repo_count,
+        args.total_commits,
+        args.owned_repo_count,
+        args.total_stars,
+        args.prs_merged,
+        args.issues_closed,
+        args.followers,
+        args.account_age_days,
+        args.reputation_level,
+        args.issued_at,
+        args.nonce,
+    );
+
+    let [verify, mint] = instructions::mint_dev_badge(
+        &dev,
+        mint_dev_badge_args([3u8; 32]),
+        &[sign(&backend, &message)],
+    );
+    assert!(precompile_accepts(&verify));
+
+    assert_eq!(mint.program_id, ID);
+    assert_eq!(mint.data, mint_dev_badge_args([3u8; 32]).data());
+    assert!(mint.accounts[0].is_signer && mint.accounts[0].pubkey == dev);
+    assert_eq!(mint.accounts[2].pubkey, pda::dev_state(&dev));
+    assert_eq!(mint.accounts[3].pubkey, pda::identity_claim(&[3u8; 32]));
+    assert_eq!(mint.accounts[6].pubkey, pda::dev_badge(&dev));
+}
+
+#[test]
+fn revoke_passes_badge_pairs_as_remaining_accounts() {
+    let (admin, dev) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let repo = [1u8; 32];
+    let contribution = [2u8; 32];
+
+    let revoke = instructions::revoke_dev_badge(&admin, &dev, 2, &[repo], &[contribution]);
+    let remaining: Vec<_> = revoke.accounts[7..].iter().map(|a| a.pubkey).collect();
+    assert_eq!(
+        remaining,
+        [
+            pda::repo_state(&dev, &repo),
+            pda::repo_badge(&dev, &repo),
+            pda::contribution_state(&dev, &contribution),
+            pda::contribution_badge(&dev, &contribution),
+        ]
+    );
+    assert!(revoke.accounts[7..].iter().all(|a| a.is_writable));
+}
+
+#[test]
+fn decode_accounts() {
+    let claim = IdentityClaim {
+        hashed_username: [3u8; 32],
+        wallet: Pubkey::new_unique(),
+        claimed_at: 1_700_000_000,
+        bump: 254,
+        version: IdentityClaim::VERSION,
+    };
+    let mut data = Vec::new();
+    claim.try_serialize(&mut data).unwrap();
+    let Ok(GhostAccount::IdentityClaim(decoded)) = GhostAccount::decode(&data) else {
+        panic!("not decoded as an IdentityClaim");
+    };
+    assert_eq!(decoded.wallet, claim.wallet);
+    assert!(VouchRecord::decode(&data).is_err());
+
+    // Written before the version byte, decoded into the current layout
+    let (voucher, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut data = VouchRecord::DISCRIMINATOR.to_vec();
+    (voucher, 3u8, target, 1_700_000_000i64, 255u8)
+        .serialize(&mut data)
+        .unwrap();
+    let Ok(GhostAccount::VouchRecord(record)) = GhostAccount::decode(&data) else {
+        panic!("not decoded as a VouchRecord");
+    };
+    assert_eq!(record.version, VouchRecord::VERSION);
+    assert_eq!((record.voucher, record.target), (voucher, target));
+    assert_eq!(record.voucher_level, 3);
+
+    assert!(GhostAccount::decode(&[0u8; 16]).is_err());
+}
+
+#[test]
+fn decode_criteria_result() {
+    let criteria = Criteria {
+        min_level: Some(3),
+        min_stars: Some(100),
+        max_staleness: Some(3600),
+        ..Default::default()
+    };
+    assert_eq!(criteria.bits(), 0b1000101);
+
+    let result = CriteriaResult {
+        dev_addr: Pubkey::new_unique(),
+        checked: criteria.bits(),
+        passed: 0b1000001,
+        revoked: false,
+    };
+    let decoded: CriteriaResult = decode_return_data(&result.try_to_vec().unwrap()).unwrap();
+    assert_eq!(decoded, result);
+    assert_eq!(decoded.failed(), 0b0000100);
+    assert!(!decoded.met());
+    assert!(decode_return_data::<CriteriaResult>(&[0u8; 8]).is_err());
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{cpi::accounts::VerifyDev, Criteria, CriteriaResult, DevVerification};
+
+// For programs gating on a GhostCheck reputation, build the CpiContext with the dev_state,
+// revocation and migration PDAs of `dev_addr`, ghost_config and the ghost_check program.
+// A dev moving to another wallet fails with MigrationInProgress until the migration finishes.
+// `max_staleness` is the max seconds since the dev badge was last updated, None applies
+// GhostConfig's max_staleness and 0 disables the check; a stale badge fails with StaleBadge
+pub fn require_dev_level<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, VerifyDev<'info>>,
+    dev_addr: Pubkey,
+    min_lvl: u8,
+    max_staleness: Option<i64>,
+) -> Result<DevVerification> {
+    Ok(crate::cpi::verify_dev(ctx, dev_addr.to_bytes(), min_lvl, max_staleness)?.get())
+}
+
+// Same accounts as `require_dev_level`, fails with CriteriaNotMet and the failed bitmap.
+// Returns `passed == checked`, use `query_dev_criteria` to branch on single criteria
+pub fn require_dev_criteria<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, VerifyDev<'info>>,
+    dev_addr: Pubkey,
+    criteria: Criteria,
+) -> Result<CriteriaResult> {
+    Ok(crate::cpi::verify_dev_criteria(ctx, dev_addr.to_bytes(), criteria)?.get())
+}
+
+// Doesn't fail on unmet criteria, `passed` and `failed()` tell which ones the dev meets
+// and `revoked` is set instead of failing with DevRevoked
+pub fn query_dev_criteria<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, VerifyDev<'info>>,
+    dev_addr: Pubkey,
+    criteria: Criteria,
+) -> Result<CriteriaResult> {
+    Ok(crate::cpi::query_dev_criteria(ctx, dev_addr.to_bytes(), criteria)?.get())
+}
@@ -34,4 +34,104 @@ pub enum GhostErrors {
     ReputationLvlInvalid,
     #[msg("Voucher tried to vouch for himself")]
     SelfVouchDenied,
+    #[msg("Ed25519 signature offsets are out of bounds or point outside the Ed25519 instruction")]
+    Ed25519OffsetsInvalid,
+    #[msg("Message signed by the backend does not match the instruction arguments or the signing wallet")]
+    SignedMessageMismatch,
+    #[msg("Attestation is older than the max_attestation_age set in config")]
+    AttestationExpired,
+    #[msg("Attestation issued_at is ahead of the cluster clock")]
+    AttestationFromFuture,
+    #[msg("Attestation nonce was already consumed")]
+    NonceAlreadyUsed,
+    #[msg("max_attestation_age must be greater than 0")]
+    InvalidAttestationAge,
+    #[msg("Signer is not the admin stored in ghost_config")]
+    AdminMismatch,
+    #[msg("New backend key must differ from the current one")]
+    BackendKeyUnchanged,
+    #[msg("Fewer distinct oracles than oracle_threshold signed the attestation")]
+    OracleThresholdNotMet,
+    #[msg("Oracle set is too large, contains duplicates or the backend key")]
+    InvalidOracleSet,
+    #[msg("Oracle threshold must be between 1 and the number of signers")]
+    InvalidOracleThreshold,
+    #[msg("Attested GitHub identity does not match the one claimed by this dev badge")]
+    IdentityMismatch,
+    #[msg("Identity can only be released to a different wallet")]
+    SameWalletRelease,
+    #[msg("Dev badge of this wallet was revoked by the protocol")]
+    DevRevoked,
+    #[msg("No dev badge found for this wallet")]
+    DevBadgeNotFound,
+    #[msg("Every repo badge of the dev must be passed as (repo_state, repo_badge) pairs")]
+    RepoBadgesRemaining,
+    #[msg("Repo badge passed does not match the repo_state PDA")]
+    RepoBadgeMismatch,
+    #[msg("Vouch was revoked before and can't be given again")]
+    VouchRevoked,
+    #[msg("Voucher has used up the vouch budget of its level")]
+    VouchBudgetExhausted,
+    #[msg("Minimum voucher level must be between 1 and 5")]
+    InvalidVouchConfig,
+    #[msg("Dev badge was not updated recently enough")]
+    StaleBadge,
+    #[msg("Dev does not meet the eligibility criteria")]
+    CriteriaNotMet,
+    #[msg("Protocol is paused by the admin")]
+    ProtocolPaused,
+    #[msg("Signer is not the pending admin")]
+    PendingAdminMismatch,
+    #[msg("Vouches given by this dev must be revoked first")]
+    ActiveVouchesRemaining,
+    #[msg("Migration is still timelocked and not approved by the old wallet")]
+    MigrationLocked,
+    #[msg("Wallet is being migrated, its badges can only move to the new wallet")]
+    MigrationInProgress,
+    #[msg("Dev badge has not been migrated to the new wallet yet")]
+    MigrationNotStarted,
+    #[msg("Migration is already completed")]
+    MigrationCompleted,
+    #[msg("Vouch record does not involve the migrating wallet")]
+    VouchRecordNotMigrating,
+    #[msg("Account has no versioned layout")]
+    UnversionedAccount,
+    #[msg("Account already uses the current layout")]
+    AccountUpToDate,
+    #[msg("Metadata base URI is too long")]
+    MetadataUriTooLong,
+    #[msg("Signer is not the org badge authority")]
+    OrgAuthorityMismatch,
+    #[msg("Repo hash does not match the repo name")]
+    RepoHashMismatch,
+    #[msg("No merged PRs or commits by the dev in this repo")]
+    NoContributions,
+    #[msg("Scoring tiers must not decrease from one level to the next")]
+    InvalidScoringParams,
+    #[msg("Reputation level does not match the on-chain scoring table")]
+    ReputationLevelMismatch,
+    #[msg("Badge was updated too recently, wait for the update interval")]
+    UpdateCooldown,
+    #[msg("Update interval and max staleness can't be negative")]
+    InvalidFreshnessConfig,
+    #[msg("Previous backend key is still in its grace window, wait for it to expire")]
+    KeyGracePeriodActive,
+    #[msg("Dev badge collection predates the burn delegate, its badges only burn with the dev signing")]
+    BurnDelegateMissing,
+    #[msg("Vouch budget must not decrease from one level to the next")]
+    InvalidVouchBudget,
+    #[msg("Wallet has a pending migration, vouches to or from it are frozen")]
+    MigrationPending,
+    #[msg("Vouch records and tombstones of the old wallet must be migrated first")]
+    VouchesNotMigrated,
+    #[msg("Vouch tombstone does not involve the migrating wallet")]
+    VouchTombstoneNotMigrating,
+    #[msg("Contribution badges must be burned or migrated first")]
+    ContributionBadgesRemaining,
+    #[msg("Metadata base URI is not set, the admin sets it with update_config")]
+    MetadataUriNotSet,
+    #[msg("Vouches given by this dev are already counted")]
+    VouchesGivenCounted,
+    #[msg("Remaining accounts must come in (state, badge) pairs")]
+    InvalidRemainingAccounts,
 }
@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ScoringTier, SCORING_TIERS};
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub ghost_config: Pubkey,
+    pub backend_pubkey: [u8; 32],
+    pub max_attestation_age: i64,
+    pub min_voucher_level: u8,
+    pub metadata_base_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub max_attestation_age: i64,
+    pub min_voucher_level: u8,
+    pub vouch_budget: [u16; 5],
+    pub metadata_base_uri: String,
+    pub min_update_interval: i64,
+    pub max_staleness: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScoringParamsUpdated {
+    pub admin: Pubkey,
+    pub tiers: [ScoringTier; SCORING_TIERS],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseSet {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackendKeyRotated {
+    pub admin: Pubkey,
+    pub new_backend_pubkey: [u8; 32],
+    pub new_key_id: u32,
+    pub previous_backend_pubkey: [u8; 32],
+    pub previous_key_expiry_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OraclesUpdated {
+    pub admin: Pubkey,
+    pub oracles: Vec<[u8; 32]>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevBadgeMinted {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub dev_state: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub reputation_level: u8,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevBadgeUpdated {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub dev_state: Pubkey,
+    pub old_level: u8,
+    pub new_level: u8,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RepoBadgeMinted {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub repo_badge: Pubkey,
+    pub repo_state: Pubkey,
+    pub repo_name: [u8; 32],
+    pub stars: u32,
+    pub commits: u32,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionBadgeMinted {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub contribution_badge: Pubkey,
+    pub contribution_state: Pubkey,
+    pub hashed_repo: [u8; 32],
+    pub prs_merged: u32,
+    pub commits: u32,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RepoBadgeUpdated {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub repo_badge: Pubkey,
+    pub repo_state: Pubkey,
+    pub repo_name: [u8; 32],
+    pub stars: u32,
+    pub commits: u32,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevVouched {
+    pub voucher: Pubkey,
+    pub target: Pubkey,
+    pub vouch_record: Pubkey,
+    pub voucher_level: u8,
+    pub target_vouch_count: u64,
+    pub target_vouch_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevVerified {
+    pub dev: Pubkey,
+    pub dev_state: Pubkey,
+    pub min_lvl: u8,
+    pub reputation_level: u8,
+    pub revoked: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevCriteriaVerified {
+    pub dev: Pubkey,
+    pub dev_state: Pubkey,
+    pub checked: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IdentityReleased {
+    pub hashed_username: [u8; 32],
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RepoBadgeBurned {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub repo_badge: Pubkey,
+    pub repo_name: [u8; 32],
+    pub verified_repos: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionBadgeBurned {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub contribution_badge: Pubkey,
+    pub hashed_repo: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevBadgeBurned {
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationRequested {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub unlock_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationApproved {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationCancelled {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevBadgeMigrated {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub old_dev_badge: Pubkey,
+    pub new_dev_badge: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RepoBadgeMigrated {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub old_repo_badge: Pubkey,
+    pub new_repo_badge: Pubkey,
+    pub repo_name: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionBadgeMigrated {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub old_contribution_badge: Pubkey,
+    pub new_contribution_badge: Pubkey,
+    pub hashed_repo: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchRecordMigrated {
+    pub old_vouch_record: Pubkey,
+    pub new_vouch_record: Pubkey,
+    pub voucher: Pubkey,
+    pub target: Pubkey,
+    pub merged: bool, // The new wallet already had this vouch, the old record was only closed
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchTombstoneMigrated {
+    pub old_vouch_tombstone: Pubkey,
+    pub new_vouch_tombstone: Pubkey,
+    pub voucher: Pubkey,
+    pub target: Pubkey,
+    pub merged: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevIdentityMigrated {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub new_dev_badge: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DevBadgeRevoked {
+    pub dev: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub dev_badge: Pubkey,
+    pub reason: u8,
+    pub repo_badges_burned: u64,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchRevoked {
+    pub voucher: Pubkey,
+    pub target: Pubkey,
+    pub voucher_level: u8,
+    pub target_vouch_count: u64,
+    pub target_vouch_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchPruned {
+    pub voucher: Pubkey,
+    pub target: Pubkey,
+    pub voucher_level: u8,
+    pub target_vouch_count: u64,
+    pub target_vouch_score: u64,
+    pub pruned_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub discriminator: [u8; 8],
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrgBadgeMinted {
+    pub authority: Pubkey,
+    pub org_badge: Pubkey,
+    pub org_state: Pubkey,
+    pub hashed_org: [u8; 32],
+    pub public_repos: u32,
+    pub total_stars: u32,
+    pub member_count: u32,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrgMemberLinked {
+    pub org_badge: Pubkey,
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub member_badge: Pubkey,
+    pub linked_members: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchesGivenSettled {
+    pub admin: Pubkey,
+    pub dev: Pubkey,
+    pub vouches_given: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrgMembershipMigrated {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub org_badge: Pubkey,
+    pub old_member_badge: Pubkey,
+    pub new_member_badge: Pubkey,
+    pub merged: bool, // The org already linked the new wallet, the old membership was only closed
+    pub timestamp: i64,
+}
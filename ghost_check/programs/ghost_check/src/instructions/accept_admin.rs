@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::GhostErrors, events::AdminTransferred, state::GhostConfig};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = ghost_config.pending_admin == Some(new_admin.key()) @GhostErrors::PendingAdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let previous_admin = self.ghost_config.admin;
+
+        self.ghost_config.admin = self.new_admin.key();
+        self.ghost_config.pending_admin = None;
+
+        emit!(AdminTransferred {
+            previous_admin,
+            new_admin: self.new_admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
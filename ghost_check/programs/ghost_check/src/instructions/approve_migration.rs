@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::MigrationApproved,
+    state::{GhostConfig, MigrationRequest},
+};
+
+#[derive(Accounts)]
+pub struct ApproveMigration<'info> {
+    // A co-signature from the old wallet skips the timelock
+    pub old_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = !migration.completed @GhostErrors::MigrationCompleted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+}
+
+impl<'info> ApproveMigration<'info> {
+    pub fn approve_migration(&mut self) -> Result<()> {
+        self.migration.approved = true;
+
+        emit!(MigrationApproved {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.migration.new_wallet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_owned_asset,
+    errors::GhostErrors,
+    events::ContributionBadgeBurned,
+    state::{ContributionState, DevState, GhostConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(hashed_repo: [u8; 32])]
+pub struct BurnContributionBadge<'info> {
+    #[account(mut)]
+    pub dev: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", dev.key().as_ref()],
+        bump = dev_state.bump,
+        constraint = dev_state.dev_addr == dev.key() @GhostErrors::IncorrectDevState,
+    )]
+    pub dev_state: Account<'info, DevState>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", dev.key().as_ref()],
+        bump = dev_state.collection_asset_bump,
+        address = dev_state.asset_address,
+    )]
+    pub dev_badge: UncheckedAccount<'info>,
+
+    // Rent goes back to the dev
+    #[account(
+        mut,
+        close = dev,
+        seeds = [b"contribution_state", dev_badge.key().as_ref(), &hashed_repo],
+        bump = contribution_state.bump,
+        constraint = contribution_state.dev_badge == dev_badge.key() @GhostErrors::DevBadgeMismatch,
+    )]
+    pub contribution_state: Account<'info, ContributionState>,
+
+    /// CHECK: This is being verified by the seeds and by the core program
+    #[account(
+        mut,
+        seeds = [b"contribution_badge", dev_badge.key().as_ref(), &hashed_repo],
+        bump = contribution_state.badge_bump,
+    )]
+    pub contribution_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BurnContributionBadge<'info> {
+    pub fn burn_contribution_badge(&mut self, hashed_repo: [u8; 32]) -> Result<()> {
+        // Burned like repo badges, both live in the dev collection
+        burn_owned_asset(
+            &self.contribution_badge,
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+        )?;
+
+        self.dev_state.contribution_badges = self.dev_state.contribution_badges.saturating_sub(1);
+
+        emit!(ContributionBadgeBurned {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            contribution_badge: self.contribution_badge.key(),
+            hashed_repo,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_dev_collection,
+    errors::GhostErrors,
+    events::DevBadgeBurned,
+    state::{DevState, GhostConfig, IdentityClaim},
+};
+
+#[derive(Accounts)]
+pub struct BurnDevBadge<'info> {
+    #[account(mut)]
+    pub dev: Signer<'info>,
+
+    // Writable, Core's BurnCollectionV1 takes the burn authority as a writable signer
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    // Repo and contribution badges are burned first with burn_repo_badge and
+    // burn_contribution_badge, Core only burns an empty collection.
+    // Vouches given must be revoked too, otherwise a fresh badge would reset the vouch budget
+    #[account(
+        mut,
+        close = dev,
+        seeds = [b"dev_state", dev.key().as_ref()],
+        bump = dev_state.bump,
+        constraint = dev_state.dev_addr == dev.key() @GhostErrors::IncorrectDevState,
+        constraint = dev_state.verified_repos == 0 @GhostErrors::RepoBadgesRemaining,
+        constraint = dev_state.contribution_badges == 0 @GhostErrors::ContributionBadgesRemaining,
+        constraint = dev_state.vouches_given == 0 @GhostErrors::ActiveVouchesRemaining,
+    )]
+    pub dev_state: Account<'info, DevState>,
+
+    // Frees the GitHub identity, it can mint again from any wallet
+    #[account(
+        mut,
+        close = dev,
+        seeds = [b"identity_claim", dev_state.hashed_username.as_ref()],
+        bump = identity_claim.bump,
+        constraint = identity_claim.wallet == dev.key() @GhostErrors::IdentityMismatch,
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", dev.key().as_ref()],
+        bump = dev_state.collection_asset_bump,
+        address = dev_state.asset_address,
+    )]
+    pub dev_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> BurnDevBadge<'info> {
+    pub fn burn_dev_badge(&mut self) -> Result<()> {
+        burn_dev_collection(
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+        )?;
+
+        emit!(DevBadgeBurned {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            hashed_username: self.dev_state.hashed_username,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
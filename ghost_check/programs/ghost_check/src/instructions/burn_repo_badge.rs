@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_owned_asset,
+    errors::GhostErrors,
+    events::RepoBadgeBurned,
+    state::{DevState, GhostConfig, RepoState},
+};
+
+#[derive(Accounts)]
+#[instruction(repo_name_padded: [u8; 32])]
+pub struct BurnRepoBadge<'info> {
+    #[account(mut)]
+    pub dev: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", dev.key().as_ref()],
+        bump = dev_state.bump,
+        constraint = dev_state.dev_addr == dev.key() @GhostErrors::IncorrectDevState,
+    )]
+    pub dev_state: Account<'info, DevState>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", dev.key().as_ref()],
+        bump = dev_state.collection_asset_bump,
+        address = dev_state.asset_address,
+    )]
+    pub dev_badge: UncheckedAccount<'info>,
+
+    // Rent goes back to the dev
+    #[account(
+        mut,
+        close = dev,
+        seeds = [b"repo_state", dev_badge.key().as_ref(), &repo_name_padded],
+        bump = repo_state.bump,
+        constraint = repo_state.dev_badge == dev_badge.key() @GhostErrors::DevBadgeMismatch,
+    )]
+    pub repo_state: Account<'info, RepoState>,
+
+    /// CHECK: This is being verified by the seeds and by the core program
+    #[account(
+        mut,
+        seeds = [b"repo_badge", dev_badge.key().as_ref(), &repo_name_padded],
+        bump = repo_state.badge_bump,
+    )]
+    pub repo_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BurnRepoBadge<'info> {
+    pub fn burn_repo_badge(&mut self, repo_name_padded: [u8; 32]) -> Result<()> {
+        // The asset is permanently frozen, ghost_config's burn delegate removes it. Older
+        // collections have no delegate, ghost_config thaws the asset and the dev burns it
+        burn_owned_asset(
+            &self.repo_badge,
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+        )?;
+
+        self.dev_state.verified_repos = self.dev_state.verified_repos.saturating_sub(1);
+
+        emit!(RepoBadgeBurned {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            repo_badge: self.repo_badge.key(),
+            repo_name: repo_name_padded,
+            verified_repos: self.dev_state.verified_repos,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
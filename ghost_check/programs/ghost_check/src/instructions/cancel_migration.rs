@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::GhostErrors, events::MigrationCancelled, state::MigrationRequest};
+
+#[derive(Accounts)]
+pub struct CancelMigration<'info> {
+    // The old wallet can stop a migration it didn't ask for while the timelock runs,
+    // also while the protocol is paused so the timelock can't run out meanwhile
+    pub old_wallet: Signer<'info>,
+
+    // Rent goes back to the new wallet that paid for the request
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = !migration.dev_migrated @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Receives the rent back, must be the wallet that requested the migration
+    #[account(mut, address = migration.new_wallet @GhostErrors::IncorrectDevState)]
+    pub new_wallet: UncheckedAccount<'info>,
+}
+
+impl<'info> CancelMigration<'info> {
+    pub fn cancel_migration(&mut self) -> Result<()> {
+        emit!(MigrationCancelled {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
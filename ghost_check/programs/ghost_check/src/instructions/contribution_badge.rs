@@ -0,0 +1,165 @@
+use crate::errors::GhostErrors;
+use crate::events::ContributionBadgeMinted;
+use crate::state::{ContributionState, DevState, GhostConfig, Versioned};
+use crate::{
+    contribution_badge_message, create_contribution_asset, hash_repo_full_name,
+    migration_in_progress, verify_attestation_age, verify_signature,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as SYSVAR_INSTRUCTION_ID;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+#[derive(Accounts)]
+#[instruction(hashed_repo: [u8; 32])]
+pub struct ContributionBadge<'info> {
+    #[account(mut)]
+    pub dev: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", dev.key().as_ref()],
+        bump = dev_state.bump,
+    )]
+    pub dev_state: Account<'info, DevState>,
+
+    /// CHECK: Only has data once the dev asked to move to another wallet
+    #[account(
+        seeds = [b"migration", dev.key().as_ref()],
+        bump,
+        constraint = !migration_in_progress(&migration) @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: UncheckedAccount<'info>,
+
+    /// CHECK: This is being verifed by the contraints
+    #[account(
+        mut,
+        seeds = [b"dev_badge", dev.key().as_ref()],
+        bump = dev_state.collection_asset_bump,
+        constraint = !dev_badge.data_is_empty() @GhostErrors::CollectionNotInitialized,
+    )]
+    pub dev_badge: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = dev,
+        space = ContributionState::DISCRIMINATOR.len() + ContributionState::INIT_SPACE,
+        seeds = [b"contribution_state", dev_badge.key().as_ref(), hashed_repo.as_ref()],  // One contribution state per dev per repo
+        bump,
+    )]
+    pub contribution_state: Account<'info, ContributionState>,
+
+    /// CHECK: This will be checked and initialized by the core program
+    #[account(
+        mut,
+        seeds = [b"contribution_badge", dev_badge.key().as_ref(), hashed_repo.as_ref()],
+        bump,
+    )]
+    pub contribution_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Sysvar instruction checked by address
+    #[account(
+        address = SYSVAR_INSTRUCTION_ID,
+    )]
+    pub instruction_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex core program
+    #[account(
+        address = CORE_PROGRAM_ID,
+    )]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ContributionBadge<'info> {
+    pub fn mint_contribution_badge(
+        &mut self,
+        hashed_repo: [u8; 32],
+        repo_full_name: Vec<u8>,
+        username_padded: [u8; 32],
+        prs_merged: u32,
+        commits: u32,
+        issued_at: i64,
+        nonce: u64,
+        bumps: &ContributionBadgeBumps,
+    ) -> Result<()> {
+        require!(
+            hash_repo_full_name(&repo_full_name) == hashed_repo,
+            GhostErrors::RepoHashMismatch
+        );
+
+        // Verify that the message( contribution stats ) is signed by the backend signer for this wallet
+        let message = contribution_badge_message(
+            &self.dev.key(),
+            &username_padded,
+            &repo_full_name,
+            prs_merged,
+            commits,
+            issued_at,
+            nonce,
+        );
+        verify_signature(
+            &self.instruction_sysvar.to_account_info(),
+            &self.ghost_config,
+            &message,
+        )?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
+
+        // Attested for the identity the dev badge was minted with
+        require!(
+            username_padded == self.dev_state.hashed_username,
+            GhostErrors::IdentityMismatch
+        );
+
+        require!(prs_merged > 0 || commits > 0, GhostErrors::NoContributions);
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.contribution_state.set_inner(ContributionState {
+            contributor: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            hashed_username: username_padded,
+            hashed_repo,
+            repo_full_name,
+            prs_merged,
+            commits,
+            last_updated: time_now,
+            nonce,
+            bump: bumps.contribution_state,
+            badge_bump: bumps.contribution_badge,
+            version: ContributionState::VERSION,
+        });
+
+        create_contribution_asset(
+            &self.contribution_badge,
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+            &self.contribution_state,
+        )?;
+
+        self.dev_state.contribution_badges += 1;
+
+        emit!(ContributionBadgeMinted {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            contribution_badge: self.contribution_badge.key(),
+            contribution_state: self.contribution_state.key(),
+            hashed_repo,
+            prs_merged,
+            commits,
+            nonce,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
@@ -1,20 +1,15 @@
 use anchor_lang::prelude::*;
-use mpl_core::{
-    instructions::CreateCollectionV2CpiBuilder,
-    types::{
-        Attribute, Attributes, PermanentFreezeDelegate, Plugin, PluginAuthority,
-        PluginAuthorityPair, UpdateDelegate,
-    },
-    ID as CORE_PROGRAM_ID,
-};
+use mpl_core::ID as CORE_PROGRAM_ID;
 
-use crate::verify_signature;
+use crate::{create_dev_collection, dev_badge_message, verify_attestation_age, verify_signature};
 use crate::{
     errors::GhostErrors,
-    state::{DevState, GhostConfig},
+    events::DevBadgeMinted,
+    state::{DevState, GhostConfig, IdentityClaim, ScoringParams, Versioned},
 };
 
 #[derive(Accounts)]
+#[instruction(username: [u8; 32])]
 pub struct DevBadge<'info> {
     #[account(mut)]
     pub dev: Signer<'info>,
@@ -23,6 +18,7 @@ pub struct DevBadge<'info> {
         mut,
         seeds = [b"ghost_config"],
         bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
     )]
     pub ghost_config: Account<'info, GhostConfig>,
 
@@ -35,6 +31,31 @@ pub struct DevBadge<'info> {
     )]
     pub dev_state: Account<'info, DevState>,
 
+    // Fails to init when this GitHub identity already holds a dev badge on another wallet
+    #[account(
+        init,
+        payer = dev,
+        space = IdentityClaim::DISCRIMINATOR.len() + IdentityClaim::INIT_SPACE,
+        seeds = [b"identity_claim", username.as_ref()],
+        bump,
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+
+    // The level in the attestation must follow from its metrics under this table
+    #[account(
+        seeds = [b"scoring_params"],
+        bump = scoring_params.bump,
+    )]
+    pub scoring_params: Account<'info, ScoringParams>,
+
+    /// CHECK: Only has data when the protocol revoked this wallet's badge
+    #[account(
+        seeds = [b"revocation", dev.key().as_ref()],
+        bump,
+        constraint = revocation.data_is_empty() @GhostErrors::DevRevoked,
+    )]
+    pub revocation: UncheckedAccount<'info>,
+
     /// CHECK: Core will create this
     #[account(
         mut,
@@ -69,64 +90,46 @@ impl<'info> DevBadge<'info> {
         followers: u32,
         account_age_days: u32,
         reputation_level: u8,
+        issued_at: i64,
+        nonce: u64,
         bumps: &DevBadgeBumps,
     ) -> Result<()> {
-        // Verify that the message(dev stats) is signed by the backend signer
+        // Verify that the message(dev stats) is signed by the backend signer for this wallet
+        let message = dev_badge_message(
+            &self.dev.key(),
+            username,
+            repo_count,
+            total_commits,
+            owned_repo_count,
+            total_stars,
+            prs_merged,
+            issues_closed,
+            followers,
+            account_age_days,
+            reputation_level,
+            issued_at,
+            nonce,
+        );
         verify_signature(
             &self.instruction_sysvar.to_account_info(),
-            &self.ghost_config.backend_pubkey,
+            &self.ghost_config,
+            &message,
         )?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
 
-        // Create Collection Asset for new Dev
-        let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[self.ghost_config.bump]]];
-        let asset_seeds: &[&[&[u8]]] =
-            &[&[b"dev_badge", &self.dev.key().to_bytes(), &[bumps.asset]]];
-
-        CreateCollectionV2CpiBuilder::new(&self.core_program.to_account_info())
-            .collection(&self.asset.to_account_info())
-            .payer(&self.dev.to_account_info())
-            .update_authority(Some(&self.ghost_config.to_account_info()))
-            .system_program(&self.system_program)
-            .name("Dev_Badge".to_string())
-            .uri("https://GhostCheck/metadata/dev".to_string())
-            .plugins(vec![
-                PluginAuthorityPair {
-                    plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
-                        frozen: true,
-                    }),
-                    authority: Some(PluginAuthority::Address {
-                        address: self.ghost_config.key(),
-                    }),
-                },
-                PluginAuthorityPair {
-                    plugin: Plugin::UpdateDelegate(UpdateDelegate {
-                        additional_delegates: vec![],
-                    }),
-                    authority: Some(PluginAuthority::Address {
-                        address: self.ghost_config.key(),
-                    }),
-                },
-                PluginAuthorityPair {
-                    plugin: Plugin::Attributes(Attributes {
-                        attribute_list: vec![Attribute {
-                            key: String::from("Dev"),
-                            value: self.dev.key().to_string(),
-                        }],
-                    }),
-                    authority: Some(PluginAuthority::UpdateAuthority),
-                },
-            ])
-            .external_plugin_adapters(vec![])
-            .invoke_signed(&[config_seeds[0], asset_seeds[0]])?;
-
-        self.ghost_config.dev_badges_minted += 1;
+        require_eq!(
+            reputation_level,
+            self.scoring_params
+                .reputation_level(repo_count, total_commits, account_age_days),
+            GhostErrors::ReputationLevelMismatch
+        );
 
         let current_time = Clock::get()?.unix_timestamp;
 
         self.dev_state.set_inner(DevState {
             dev_addr: self.dev.key(),
             asset_address: self.asset.key(),
-            hashed_username: username.clone(),
+            hashed_username: *username,
             repo_count,
             owned_repo_count,
             total_stars,
@@ -138,10 +141,48 @@ impl<'info> DevBadge<'info> {
             reputation_level,
             verified_repos: 0,
             vouch_count: 0,
+            vouch_score: 0,
+            vouches_given: 0,
             last_updated: current_time,
+            nonce,
             bump: bumps.dev_state,
             collection_asset_bump: bumps.asset,
+            version: DevState::VERSION,
+            vouch_tombstones: 0,
+            contribution_badges: 0,
+        });
+
+        // Create Collection Asset for new Dev, its attributes mirror the dev state
+        create_dev_collection(
+            &self.asset,
+            bumps.asset,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+            &self.dev_state,
+        )?;
+
+        self.ghost_config.dev_badges_minted += 1;
+
+        self.identity_claim.set_inner(IdentityClaim {
+            hashed_username: *username,
+            wallet: self.dev.key(),
+            claimed_at: current_time,
+            bump: bumps.identity_claim,
+            version: IdentityClaim::VERSION,
         });
+
+        emit!(DevBadgeMinted {
+            dev: self.dev.key(),
+            dev_badge: self.asset.key(),
+            dev_state: self.dev_state.key(),
+            hashed_username: *username,
+            reputation_level,
+            nonce,
+            timestamp: current_time,
+        });
+
         Ok(())
     }
 }
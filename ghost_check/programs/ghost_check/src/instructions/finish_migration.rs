@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_dev_collection,
+    errors::GhostErrors,
+    events::DevIdentityMigrated,
+    state::{DevState, GhostConfig, IdentityClaim, MigrationRequest},
+    sync_dev_badge_attributes,
+};
+
+#[derive(Accounts)]
+pub struct FinishMigration<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    // Writable, Core's BurnCollectionV1 takes the burn authority as a writable signer
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    // Kept once completed, vouches to or from the old wallet stay rejected
+    #[account(
+        mut,
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = migration.dev_migrated @GhostErrors::MigrationNotStarted,
+        constraint = !migration.completed @GhostErrors::MigrationCompleted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    // Repo and contribution badges are moved first with migrate_repo_badge and
+    // migrate_contribution_badge, Core only burns an empty collection. Vouch records and
+    // tombstones are moved first too, each taking its counters along
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+        constraint = old_dev_state.verified_repos == 0 @GhostErrors::RepoBadgesRemaining,
+        constraint = old_dev_state.contribution_badges == 0 @GhostErrors::ContributionBadgesRemaining,
+        constraint = old_dev_state.vouch_count == 0 @GhostErrors::VouchesNotMigrated,
+        constraint = old_dev_state.vouches_given == 0 @GhostErrors::VouchesNotMigrated,
+        constraint = old_dev_state.vouch_tombstones == 0 @GhostErrors::VouchesNotMigrated,
+    )]
+    pub old_dev_state: Account<'info, DevState>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", old_wallet.key().as_ref()],
+        bump = old_dev_state.collection_asset_bump,
+        address = old_dev_state.asset_address,
+    )]
+    pub old_dev_badge: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump = new_dev_state.bump,
+    )]
+    pub new_dev_state: Account<'info, DevState>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", new_wallet.key().as_ref()],
+        bump = new_dev_state.collection_asset_bump,
+        address = new_dev_state.asset_address,
+    )]
+    pub new_dev_badge: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"identity_claim", migration.hashed_username.as_ref()],
+        bump = identity_claim.bump,
+        constraint = identity_claim.wallet == old_wallet.key() @GhostErrors::IdentityMismatch,
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FinishMigration<'info> {
+    pub fn finish_migration(&mut self) -> Result<()> {
+        burn_dev_collection(
+            &self.old_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+        )?;
+
+        // Picks up the vouch counts moved with each record
+        sync_dev_badge_attributes(
+            &self.new_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+            &self.new_dev_state,
+        )?;
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.identity_claim.wallet = self.new_wallet.key();
+        self.identity_claim.claimed_at = time_now;
+        self.migration.completed = true;
+
+        emit!(DevIdentityMigrated {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            hashed_username: self.migration.hashed_username,
+            new_dev_badge: self.new_dev_badge.key(),
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
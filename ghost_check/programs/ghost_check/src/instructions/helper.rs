@@ -1,27 +1,880 @@
-use crate::errors::GhostErrors;
+use crate::{
+    errors::GhostErrors,
+    state::{
+        ContributionState, DevState, GhostConfig, MigrationRequest, OrgState, RepoState, Versioned,
+    },
+};
 use anchor_lang::prelude::{
     sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
     *,
 };
+use mpl_core::{
+    instructions::{
+        BurnCollectionV1CpiBuilder, BurnV1CpiBuilder, CreateCollectionV2CpiBuilder,
+        CreateV2CpiBuilder, UpdateCollectionPluginV1CpiBuilder, UpdatePluginV1CpiBuilder,
+    },
+    types::{
+        Attribute, Attributes, PermanentBurnDelegate, PermanentFreezeDelegate, Plugin,
+        PluginAuthority, PluginAuthorityPair, PluginType, UpdateDelegate,
+    },
+};
+use solana_sha256_hasher::hashv;
+
+// Ed25519 precompile, the instruction carrying the attestation signatures
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+// Layout of the Ed25519 program instruction data:
+// [num_signatures: u8][padding: u8][Ed25519SignatureOffsets; num_signatures][payload ...]
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+// Instruction index value the Ed25519 program uses for "this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// Seconds the backend clock may run ahead of the cluster clock
+const MAX_CLOCK_DRIFT: i64 = 60;
+
+struct Ed25519SignatureOffsets {
+    signature_offset: u16,
+    signature_instruction_index: u16,
+    public_key_offset: u16,
+    public_key_instruction_index: u16,
+    message_data_offset: u16,
+    message_data_size: u16,
+    message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    fn parse(data: &[u8], index: usize) -> Result<Self> {
+        let start = SIGNATURE_OFFSETS_START + index * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let bytes = data
+            .get(start..start + SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+            .ok_or(GhostErrors::Ed25519OffsetsInvalid)?;
+        let read = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+
+        Ok(Self {
+            signature_offset: read(0),
+            signature_instruction_index: read(2),
+            public_key_offset: read(4),
+            public_key_instruction_index: read(6),
+            message_data_offset: read(8),
+            message_data_size: read(10),
+            message_instruction_index: read(12),
+        })
+    }
+
+    // Signature, pubkey and message must all live inside the Ed25519 instruction itself,
+    // otherwise the precompile could have verified bytes we never look at
+    fn points_to_current_instruction(&self) -> bool {
+        self.signature_instruction_index == CURRENT_INSTRUCTION
+            && self.public_key_instruction_index == CURRENT_INSTRUCTION
+            && self.message_instruction_index == CURRENT_INSTRUCTION
+    }
+}
+
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or(GhostErrors::Ed25519OffsetsInvalid.into())
+}
+
+// Looks through every Ed25519 program instruction of the transaction and counts the distinct
+// accepted signers (backend key, current or previous within its grace window, and configured
+// oracles) that signed exactly `expected_message`, `oracle_threshold` of them are required
+pub fn verify_signature(
+    instruction_sysvar: &AccountInfo,
+    ghost_config: &GhostConfig,
+    expected_message: &[u8],
+) -> Result<()> {
+    let current_ix = load_current_index_checked(instruction_sysvar)? as usize;
+    let current_slot = Clock::get()?.slot;
+
+    let mut found_ed25519_ix = false;
+    let mut found_backend_key = false;
+    let mut found_message = false;
+    // Bit i set once the signer in oracle seat i signed the expected message
+    let mut signed_seats: u32 = 0;
 
-pub fn verify_signature(instruction_sysvar: &AccountInfo, backend_pubkey: &[u8; 32]) -> Result<()> {
-    let current_ix = load_current_index_checked(instruction_sysvar)?;
-    let ix = load_instruction_at_checked((current_ix as usize) - 1, instruction_sysvar)?;
+    for index in 0.. {
+        let Ok(ix) = load_instruction_at_checked(index, instruction_sysvar) else {
+            break;
+        };
+        if index == current_ix || ix.program_id != ED25519_PROGRAM_ID {
+            continue;
+        }
+        found_ed25519_ix = true;
 
-    let ed_25519_id: Pubkey =
-        Pubkey::new_from_array(solana_program::ed25519_program::ID.to_bytes());
+        let data = ix.data;
+        let num_signatures = *data.first().ok_or(GhostErrors::Ed25519OffsetsInvalid)? as usize;
 
-    require!(ix.program_id == ed_25519_id, GhostErrors::InvalidSignature);
+        for i in 0..num_signatures {
+            let offsets = Ed25519SignatureOffsets::parse(&data, i)?;
+            require!(
+                offsets.points_to_current_instruction(),
+                GhostErrors::Ed25519OffsetsInvalid
+            );
 
-    let data = ix.data;
-    let pubkey_bytes: [u8; 32] = data[16..48]
-        .try_into()
-        .map_err(|_| GhostErrors::PubkeyParseFailed)?;
+            // Signature bytes are checked by the precompile, we only make sure they are in bounds
+            slice_at(&data, offsets.signature_offset, SIGNATURE_SERIALIZED_SIZE)?;
 
+            let pubkey_bytes: [u8; 32] =
+                slice_at(&data, offsets.public_key_offset, PUBKEY_SERIALIZED_SIZE)?
+                    .try_into()
+                    .map_err(|_| GhostErrors::PubkeyParseFailed)?;
+            let Some(seat) = ghost_config.oracle_seat(&pubkey_bytes, current_slot) else {
+                continue;
+            };
+            found_backend_key = true;
+
+            let message = slice_at(
+                &data,
+                offsets.message_data_offset,
+                offsets.message_data_size as usize,
+            )?;
+            if message == expected_message {
+                found_message = true;
+                signed_seats |= 1 << seat;
+            }
+        }
+    }
+
+    require!(found_ed25519_ix, GhostErrors::InvalidSignature);
+    require!(found_backend_key, GhostErrors::BackendPubkeyMismatch);
+    require!(found_message, GhostErrors::SignedMessageMismatch);
+    require!(
+        signed_seats.count_ones() >= ghost_config.oracle_threshold as u32,
+        GhostErrors::OracleThresholdNotMet
+    );
+
+    Ok(())
+}
+
+// Rejects attestations issued more than `max_attestation_age` seconds ago
+pub fn verify_attestation_age(issued_at: i64, max_attestation_age: i64) -> Result<()> {
+    let time_now = Clock::get()?.unix_timestamp;
+
+    require!(
+        issued_at <= time_now.saturating_add(MAX_CLOCK_DRIFT),
+        GhostErrors::AttestationFromFuture
+    );
     require!(
-        &pubkey_bytes == backend_pubkey,
-        GhostErrors::BackendPubkeyMismatch
+        time_now.saturating_sub(issued_at) <= max_attestation_age,
+        GhostErrors::AttestationExpired
     );
 
     Ok(())
 }
+
+// Rebuilds the message signed by the backend in `sign_dev_badge_metrics`,
+// `dev` binds the attestation to the wallet that requested it
+pub fn dev_badge_message(
+    dev: &Pubkey,
+    hashed_username: &[u8; 32],
+    repo_count: u32,
+    total_commits: u32,
+    owned_repo_count: u32,
+    total_stars: u32,
+    prs_merged: u32,
+    issues_closed: u32,
+    followers: u32,
+    account_age_days: u32,
+    reputation_level: u8,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
+    hashv(&[
+        b"dev_badge",
+        hashed_username.as_ref(),
+        dev.as_ref(),
+        &repo_count.to_be_bytes(),
+        &total_commits.to_be_bytes(),
+        &owned_repo_count.to_be_bytes(),
+        &total_stars.to_be_bytes(),
+        &prs_merged.to_be_bytes(),
+        &issues_closed.to_be_bytes(),
+        &followers.to_be_bytes(),
+        &account_age_days.to_be_bytes(),
+        &[reputation_level],
+        &issued_at.to_be_bytes(),
+        &nonce.to_be_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Rebuilds the message signed by the backend in `sign_org_badge_metrics`,
+// `authority` is the wallet of the GitHub org admin that requested it
+pub fn org_badge_message(
+    authority: &Pubkey,
+    hashed_org: &[u8; 32],
+    public_repos: u32,
+    total_stars: u32,
+    member_count: u32,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
+    hashv(&[
+        b"org_badge",
+        hashed_org.as_ref(),
+        authority.as_ref(),
+        &public_repos.to_be_bytes(),
+        &total_stars.to_be_bytes(),
+        &member_count.to_be_bytes(),
+        &issued_at.to_be_bytes(),
+        &nonce.to_be_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Rebuilds the message signed by the backend in `sign_repo_badge_metrics`.
+// Variable length fields carry a u16 length so ("foo", "Rust") can't be read as ("fooR", "ust")
+pub fn repo_badge_message(
+    dev: &Pubkey,
+    hashed_username: &[u8; 32],
+    repo_name: &[u8],
+    lang1: &[u8],
+    lang2: &[u8],
+    stars: u32,
+    commits: u32,
+    forks: u32,
+    open_issues: u32,
+    is_fork: u8,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
+    hashv(&[
+        b"repo_badge",
+        hashed_username.as_ref(),
+        dev.as_ref(),
+        &length_prefix(repo_name),
+        repo_name,
+        &length_prefix(lang1),
+        lang1,
+        &length_prefix(lang2),
+        lang2,
+        &stars.to_be_bytes(),
+        &commits.to_be_bytes(),
+        &forks.to_be_bytes(),
+        &open_issues.to_be_bytes(),
+        &[is_fork],
+        &issued_at.to_be_bytes(),
+        &nonce.to_be_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Instruction data is capped by the transaction size, a field never reaches u16::MAX
+fn length_prefix(field: &[u8]) -> [u8; 2] {
+    (field.len() as u16).to_be_bytes()
+}
+
+// Rebuilds the message signed by the backend in `sign_contribution_metrics`
+pub fn contribution_badge_message(
+    dev: &Pubkey,
+    hashed_username: &[u8; 32],
+    repo_full_name: &[u8],
+    prs_merged: u32,
+    commits: u32,
+    issued_at: i64,
+    nonce: u64,
+) -> [u8; 32] {
+    hashv(&[
+        b"contribution",
+        hashed_username.as_ref(),
+        dev.as_ref(),
+        repo_full_name,
+        &prs_merged.to_be_bytes(),
+        &commits.to_be_bytes(),
+        &issued_at.to_be_bytes(),
+        &nonce.to_be_bytes(),
+    ])
+    .to_bytes()
+}
+
+// Seed of a contribution badge, GitHub matches "owner/repo" case-insensitively
+pub fn hash_repo_full_name(repo_full_name: &[u8]) -> [u8; 32] {
+    hashv(&[&repo_full_name.to_ascii_lowercase()]).to_bytes()
+}
+
+// Rebuilds the message signed by the backend in `sign_identity_release`
+pub fn identity_release_message(
+    hashed_username: &[u8; 32],
+    old_wallet: &Pubkey,
+    new_wallet: &Pubkey,
+    issued_at: i64,
+) -> [u8; 32] {
+    identity_move_message(
+        b"identity_release",
+        hashed_username,
+        old_wallet,
+        new_wallet,
+        issued_at,
+    )
+}
+
+// Rebuilds the message signed by the backend in `sign_identity_migration`
+pub fn identity_migration_message(
+    hashed_username: &[u8; 32],
+    old_wallet: &Pubkey,
+    new_wallet: &Pubkey,
+    issued_at: i64,
+) -> [u8; 32] {
+    identity_move_message(
+        b"identity_migration",
+        hashed_username,
+        old_wallet,
+        new_wallet,
+        issued_at,
+    )
+}
+
+// The domain keeps a release attestation from being replayed as a migration and back
+fn identity_move_message(
+    domain: &[u8],
+    hashed_username: &[u8; 32],
+    old_wallet: &Pubkey,
+    new_wallet: &Pubkey,
+    issued_at: i64,
+) -> [u8; 32] {
+    hashv(&[
+        domain,
+        hashed_username.as_ref(),
+        old_wallet.as_ref(),
+        new_wallet.as_ref(),
+        &issued_at.to_be_bytes(),
+    ])
+    .to_bytes()
+}
+
+// The backend signs the plain repo name, the seed is the same name zero padded to 32 bytes
+pub fn trim_repo_name(repo_name_padded: &[u8]) -> &[u8] {
+    let len = repo_name_padded
+        .iter()
+        .rposition(|b| *b != 0)
+        .map_or(0, |i| i + 1);
+    &repo_name_padded[..len]
+}
+
+// `migration` is the `[b"migration", wallet]` PDA. From the request until finish_migration the
+// wallet's dev state only moves to the new wallet, it can't be minted to, updated or verified
+pub fn migration_in_progress(migration: &AccountInfo) -> bool {
+    if migration.data_is_empty() {
+        return false;
+    }
+    let Ok(data) = migration.try_borrow_data() else {
+        return true;
+    };
+    MigrationRequest::try_deserialize_compat(&data).map_or(true, |migration| !migration.completed)
+}
+
+// Core only accepts permanent plugins when the collection is created, so dev collections minted
+// before PermanentBurnDelegate was added can't get it later
+pub fn has_burn_delegate(dev_badge: &AccountInfo, ghost_config: &Account<GhostConfig>) -> bool {
+    let delegate = mpl_core::fetch_collection_plugin::<PermanentBurnDelegate>(
+        dev_badge,
+        PluginType::PermanentBurnDelegate,
+    );
+    matches!(
+        delegate,
+        Ok((PluginAuthority::Address { address }, _, _)) if address == ghost_config.key()
+    )
+}
+
+// Without the delegate, frozen badges only burn with their owner signing, see burn_owned_asset.
+// Fails clearly instead of deep inside Core when the owner isn't there
+pub fn require_burn_delegate(
+    dev_badge: &AccountInfo,
+    ghost_config: &Account<GhostConfig>,
+) -> Result<()> {
+    require!(
+        has_burn_delegate(dev_badge, ghost_config),
+        GhostErrors::BurnDelegateMissing
+    );
+
+    Ok(())
+}
+
+// Burns a repo or contribution badge through ghost_config's PermanentBurnDelegate on the dev
+// collection, or a member badge through the one on its org collection
+pub fn burn_repo_asset<'info>(
+    repo_badge: &AccountInfo<'info>,
+    dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require_burn_delegate(dev_badge, ghost_config)?;
+    let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[ghost_config.bump]]];
+
+    BurnV1CpiBuilder::new(core_program)
+        .asset(repo_badge)
+        .collection(Some(dev_badge))
+        .payer(payer)
+        .authority(Some(&ghost_config.to_account_info()))
+        .system_program(Some(system_program))
+        .invoke_signed(config_seeds)?;
+
+    Ok(())
+}
+
+// Burns a badge its owner signed for. In collections without the burn delegate ghost_config
+// thaws the badge, its PermanentFreezeDelegate answers to the update authority, and the owner burns it
+pub fn burn_owned_asset<'info>(
+    badge: &AccountInfo<'info>,
+    dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    owner: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if has_burn_delegate(dev_badge, ghost_config) {
+        return burn_repo_asset(
+            badge,
+            dev_badge,
+            ghost_config,
+            owner,
+            core_program,
+            system_program,
+        );
+    }
+    let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[ghost_config.bump]]];
+
+    UpdatePluginV1CpiBuilder::new(core_program)
+        .asset(badge)
+        .collection(Some(dev_badge))
+        .payer(owner)
+        .authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .plugin(Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+            frozen: false,
+        }))
+        .invoke_signed(config_seeds)?;
+
+    BurnV1CpiBuilder::new(core_program)
+        .asset(badge)
+        .collection(Some(dev_badge))
+        .payer(owner)
+        .authority(Some(owner))
+        .system_program(Some(system_program))
+        .invoke()?;
+
+    Ok(())
+}
+
+// Burns an empty dev collection, ghost_config is its update authority so collections minted
+// before PermanentBurnDelegate burn too
+pub fn burn_dev_collection<'info>(
+    dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[ghost_config.bump]]];
+
+    BurnCollectionV1CpiBuilder::new(core_program)
+        .collection(dev_badge)
+        .payer(payer)
+        .authority(Some(&ghost_config.to_account_info()))
+        .invoke_signed(config_seeds)?;
+
+    Ok(())
+}
+
+fn attribute(key: &str, value: String) -> Attribute {
+    Attribute {
+        key: key.to_string(),
+        value,
+    }
+}
+
+// Attributes of the Dev_Badge collection, what wallets and marketplaces render from DevState
+pub fn dev_badge_attributes(dev_state: &DevState) -> Attributes {
+    Attributes {
+        attribute_list: vec![
+            attribute("Dev", dev_state.dev_addr.to_string()),
+            attribute("reputation_level", dev_state.reputation_level.to_string()),
+            attribute("commits", dev_state.total_commits.to_string()),
+            attribute("stars", dev_state.total_stars.to_string()),
+            attribute("prs_merged", dev_state.prs_merged.to_string()),
+            attribute("vouch_count", dev_state.vouch_count.to_string()),
+            attribute("last_updated", dev_state.last_updated.to_string()),
+        ],
+    }
+}
+
+// Rewrites the Dev_Badge collection attributes from `dev_state`, ghost_config is the update authority
+pub fn sync_dev_badge_attributes<'info>(
+    dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    dev_state: &DevState,
+) -> Result<()> {
+    let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[ghost_config.bump]]];
+
+    UpdateCollectionPluginV1CpiBuilder::new(core_program)
+        .collection(dev_badge)
+        .payer(payer)
+        .authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .plugin(Plugin::Attributes(dev_badge_attributes(dev_state)))
+        .invoke_signed(config_seeds)?;
+
+    Ok(())
+}
+
+// Applies `f` to a dev state that may be closed already, returns None once it is. A closed
+// dev state's dev_badge collection is burned and can't be minted again, so the address never
+// gets a dev state that missed the update
+pub fn update_dev_state(
+    dev_state: &AccountInfo,
+    f: impl FnOnce(&mut DevState),
+) -> Result<Option<DevState>> {
+    if dev_state.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*dev_state.owner, crate::ID, GhostErrors::IncorrectDevState);
+
+    let mut state = DevState::try_deserialize(&mut &dev_state.try_borrow_data()?[..])?;
+    f(&mut state);
+    state.try_serialize(&mut &mut dev_state.try_borrow_mut_data()?[..])?;
+
+    Ok(Some(state))
+}
+
+// Takes a closed vouch off its target and refreshes the target's attributes. A revoked vouch
+// leaves a tombstone the target carries through a migration. Returns the target's dev state,
+// None once it's closed
+pub fn remove_vouch_from_target<'info>(
+    target_dev_state: &AccountInfo<'info>,
+    target_dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    voucher_level: u8,
+    tombstoned: bool,
+) -> Result<Option<DevState>> {
+    let Some(dev_state) = update_dev_state(target_dev_state, |target| {
+        target.vouch_count = target.vouch_count.saturating_sub(1);
+        // Weighted by the level recorded at vouch time, not the voucher's current level
+        target.vouch_score = target.vouch_score.saturating_sub(voucher_level as u64);
+        if tombstoned {
+            target.vouch_tombstones += 1;
+        }
+    })?
+    else {
+        return Ok(None);
+    };
+
+    sync_dev_badge_attributes(
+        target_dev_badge,
+        ghost_config,
+        payer,
+        core_program,
+        system_program,
+        &dev_state,
+    )?;
+
+    Ok(Some(dev_state))
+}
+
+// Attributes of a repo badge, what wallets and marketplaces render from RepoState
+pub fn repo_badge_attributes(repo_state: &RepoState) -> Attributes {
+    Attributes {
+        attribute_list: vec![
+            attribute("repo_name", repo_badge_name(repo_state)),
+            attribute("stars", repo_state.stars.to_string()),
+            attribute("commits", repo_state.commits.to_string()),
+            attribute("forks", repo_state.forks.to_string()),
+            attribute("open_issues", repo_state.open_issues.to_string()),
+            attribute("is_fork", (repo_state.is_fork == 1).to_string()),
+            attribute(
+                "lang1",
+                String::from_utf8_lossy(&repo_state.lang1).into_owned(),
+            ),
+            attribute(
+                "lang2",
+                String::from_utf8_lossy(&repo_state.lang2).into_owned(),
+            ),
+            attribute("last_updated", repo_state.last_updated.to_string()),
+        ],
+    }
+}
+
+// Asset name of a repo badge, the repository name without the seed padding
+pub fn repo_badge_name(repo_state: &RepoState) -> String {
+    String::from_utf8_lossy(trim_repo_name(&repo_state.repo_name)).into_owned()
+}
+
+// Attributes of a contribution badge, what wallets and marketplaces render from ContributionState
+pub fn contribution_badge_attributes(contribution_state: &ContributionState) -> Attributes {
+    Attributes {
+        attribute_list: vec![
+            attribute("repo", contribution_badge_name(contribution_state)),
+            attribute("prs_merged", contribution_state.prs_merged.to_string()),
+            attribute("commits", contribution_state.commits.to_string()),
+            attribute("last_updated", contribution_state.last_updated.to_string()),
+        ],
+    }
+}
+
+// Asset name of a contribution badge, the "owner/repo" contributed to
+pub fn contribution_badge_name(contribution_state: &ContributionState) -> String {
+    String::from_utf8_lossy(&contribution_state.repo_full_name).into_owned()
+}
+
+// Creates the Dev_Badge collection at the `[b"dev_badge", dev]` PDA, ghost_config holds every authority
+pub fn create_dev_collection<'info>(
+    dev_badge: &AccountInfo<'info>,
+    dev_badge_bump: u8,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    dev_state: &DevState,
+) -> Result<()> {
+    let config_seeds: &[&[u8]] = &[b"ghost_config", &[ghost_config.bump]];
+    let asset_seeds: &[&[u8]] = &[b"dev_badge", dev_state.dev_addr.as_ref(), &[dev_badge_bump]];
+
+    CreateCollectionV2CpiBuilder::new(core_program)
+        .collection(dev_badge)
+        .payer(payer)
+        .update_authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .name("Dev_Badge".to_string())
+        .uri(ghost_config.metadata_uri("dev", dev_badge.key)?)
+        .plugins(vec![
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+                authority: Some(PluginAuthority::Address {
+                    address: ghost_config.key(),
+                }),
+            },
+            // Lets ghost_config burn the collection and every repo badge in it on revocation
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
+                authority: Some(PluginAuthority::Address {
+                    address: ghost_config.key(),
+                }),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::UpdateDelegate(UpdateDelegate {
+                    additional_delegates: vec![],
+                }),
+                authority: Some(PluginAuthority::Address {
+                    address: ghost_config.key(),
+                }),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::Attributes(dev_badge_attributes(dev_state)),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+        ])
+        .external_plugin_adapters(vec![])
+        .invoke_signed(&[config_seeds, asset_seeds])?;
+
+    Ok(())
+}
+
+// Creates a repo badge at the `[b"repo_badge", dev_badge, repo_name]` PDA inside the dev collection
+pub fn create_repo_asset<'info>(
+    repo_badge: &AccountInfo<'info>,
+    dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    repo_state: &RepoState,
+) -> Result<()> {
+    let config_seeds: &[&[u8]] = &[b"ghost_config", &[ghost_config.bump]];
+    let repo_name_padded = repo_state.padded_name();
+    let repo_badge_seeds: &[&[u8]] = &[
+        b"repo_badge",
+        repo_state.dev_badge.as_ref(),
+        &repo_name_padded,
+        &[repo_state.badge_bump],
+    ];
+
+    CreateV2CpiBuilder::new(core_program)
+        .asset(repo_badge)
+        .payer(payer)
+        .collection(Some(dev_badge))
+        .authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .name(repo_badge_name(repo_state))
+        .uri(ghost_config.metadata_uri("repo", repo_badge.key)?)
+        .plugins(vec![
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::UpdateDelegate(UpdateDelegate {
+                    additional_delegates: vec![],
+                }),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::Attributes(repo_badge_attributes(repo_state)),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+        ])
+        .external_plugin_adapters(vec![])
+        .invoke_signed(&[config_seeds, repo_badge_seeds])?;
+
+    Ok(())
+}
+
+// Creates a contribution badge at the `[b"contribution_badge", dev_badge, hashed_repo]` PDA inside the dev collection
+pub fn create_contribution_asset<'info>(
+    contribution_badge: &AccountInfo<'info>,
+    dev_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    contribution_state: &ContributionState,
+) -> Result<()> {
+    let config_seeds: &[&[u8]] = &[b"ghost_config", &[ghost_config.bump]];
+    let contribution_badge_seeds: &[&[u8]] = &[
+        b"contribution_badge",
+        contribution_state.dev_badge.as_ref(),
+        &contribution_state.hashed_repo,
+        &[contribution_state.badge_bump],
+    ];
+
+    CreateV2CpiBuilder::new(core_program)
+        .asset(contribution_badge)
+        .payer(payer)
+        .collection(Some(dev_badge))
+        .authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .name(contribution_badge_name(contribution_state))
+        .uri(ghost_config.metadata_uri("contribution", contribution_badge.key)?)
+        .plugins(vec![
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::Attributes(contribution_badge_attributes(contribution_state)),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+        ])
+        .external_plugin_adapters(vec![])
+        .invoke_signed(&[config_seeds, contribution_badge_seeds])?;
+
+    Ok(())
+}
+
+// Attributes of the Org_Badge collection, what wallets and marketplaces render from OrgState
+pub fn org_badge_attributes(org_state: &OrgState) -> Attributes {
+    Attributes {
+        attribute_list: vec![
+            attribute("Org_Admin", org_state.authority.to_string()),
+            attribute("public_repos", org_state.public_repos.to_string()),
+            attribute("stars", org_state.total_stars.to_string()),
+            attribute("members", org_state.member_count.to_string()),
+            attribute("last_updated", org_state.last_updated.to_string()),
+        ],
+    }
+}
+
+// Creates the Org_Badge collection at the `[b"org_badge", hashed_org]` PDA, ghost_config holds every authority
+pub fn create_org_collection<'info>(
+    org_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    payer: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    org_state: &OrgState,
+) -> Result<()> {
+    let config_seeds: &[&[u8]] = &[b"ghost_config", &[ghost_config.bump]];
+    let org_badge_seeds: &[&[u8]] = &[
+        b"org_badge",
+        org_state.hashed_org.as_ref(),
+        &[org_state.badge_bump],
+    ];
+
+    CreateCollectionV2CpiBuilder::new(core_program)
+        .collection(org_badge)
+        .payer(payer)
+        .update_authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .name("Org_Badge".to_string())
+        .uri(ghost_config.metadata_uri("org", org_badge.key)?)
+        .plugins(vec![
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+                authority: Some(PluginAuthority::Address {
+                    address: ghost_config.key(),
+                }),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentBurnDelegate(PermanentBurnDelegate {}),
+                authority: Some(PluginAuthority::Address {
+                    address: ghost_config.key(),
+                }),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::Attributes(org_badge_attributes(org_state)),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+        ])
+        .external_plugin_adapters(vec![])
+        .invoke_signed(&[config_seeds, org_badge_seeds])?;
+
+    Ok(())
+}
+
+// Mints a member badge to `dev` at the `[b"org_member_badge", org_badge, dev]` PDA inside the org collection
+pub fn create_org_member_asset<'info>(
+    member_badge: &AccountInfo<'info>,
+    member_badge_bump: u8,
+    org_badge: &AccountInfo<'info>,
+    ghost_config: &Account<'info, GhostConfig>,
+    dev: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    dev_state: &DevState,
+) -> Result<()> {
+    let config_seeds: &[&[u8]] = &[b"ghost_config", &[ghost_config.bump]];
+    let member_badge_seeds: &[&[u8]] = &[
+        b"org_member_badge",
+        org_badge.key.as_ref(),
+        dev.key.as_ref(),
+        &[member_badge_bump],
+    ];
+    CreateV2CpiBuilder::new(core_program)
+        .asset(member_badge)
+        .payer(dev)
+        .collection(Some(org_badge))
+        .authority(Some(&ghost_config.to_account_info()))
+        .system_program(system_program)
+        .name("Org_Member".to_string())
+        .uri(ghost_config.metadata_uri("org_member", member_badge.key)?)
+        .plugins(vec![
+            PluginAuthorityPair {
+                plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+            PluginAuthorityPair {
+                plugin: Plugin::Attributes(Attributes {
+                    attribute_list: vec![
+                        attribute("Dev", dev_state.dev_addr.to_string()),
+                        attribute("dev_badge", dev_state.asset_address.to_string()),
+                        attribute("reputation_level", dev_state.reputation_level.to_string()),
+                    ],
+                }),
+                authority: Some(PluginAuthority::UpdateAuthority),
+            },
+        ])
+        .external_plugin_adapters(vec![])
+        .invoke_signed(&[config_seeds, member_badge_seeds])?;
+
+    Ok(())
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::GhostErrors, program::GhostCheck, state::GhostConfig};
+use crate::{
+    errors::GhostErrors,
+    events::ConfigInitialized,
+    program::GhostCheck,
+    state::{GhostConfig, Versioned, MAX_METADATA_BASE_URI_LEN},
+};
 
 #[derive(Accounts)]
 pub struct InitConfig<'info> {
@@ -26,14 +31,60 @@ pub struct InitConfig<'info> {
 }
 
 impl<'info> InitConfig<'info> {
-    pub fn init_config(&mut self, backend_pubkey: [u8; 32], bumps: &InitConfigBumps) -> Result<()> {
+    pub fn init_config(
+        &mut self,
+        backend_pubkey: [u8; 32],
+        max_attestation_age: i64,
+        min_voucher_level: u8,
+        vouch_budget: [u16; 5],
+        metadata_base_uri: String,
+        bumps: &InitConfigBumps,
+    ) -> Result<()> {
+        require!(max_attestation_age > 0, GhostErrors::InvalidAttestationAge);
+        require!(
+            metadata_base_uri.len() <= MAX_METADATA_BASE_URI_LEN,
+            GhostErrors::MetadataUriTooLong
+        );
+        require!(
+            (1..=5).contains(&min_voucher_level),
+            GhostErrors::InvalidVouchConfig
+        );
+        require!(
+            GhostConfig::vouch_budget_is_valid(&vouch_budget),
+            GhostErrors::InvalidVouchBudget
+        );
+
         self.ghost_config.set_inner(GhostConfig {
             admin: self.admin.key(),
+            pending_admin: None,
+            paused: false,
             backend_pubkey,
+            backend_key_id: 0,
+            previous_backend_pubkey: None,
+            previous_key_expiry_slot: 0,
             dev_badges_minted: 0,
             repo_badges_minted: 0,
             vouches_count: 0,
+            min_voucher_level,
+            vouch_budget,
+            max_attestation_age,
+            oracles: vec![],
+            oracle_threshold: 1,
             bump: bumps.ghost_config,
+            version: GhostConfig::VERSION,
+            metadata_base_uri,
+            min_update_interval: 0,
+            max_staleness: 0,
+        });
+
+        emit!(ConfigInitialized {
+            admin: self.admin.key(),
+            ghost_config: self.ghost_config.key(),
+            backend_pubkey,
+            max_attestation_age,
+            min_voucher_level,
+            metadata_base_uri: self.ghost_config.metadata_base_uri.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
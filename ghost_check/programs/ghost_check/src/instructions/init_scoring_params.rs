@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::ScoringParamsUpdated,
+    state::{GhostConfig, ScoringParams, ScoringTier, Versioned, SCORING_TIERS},
+};
+
+#[derive(Accounts)]
+pub struct InitScoringParams<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ScoringParams::DISCRIMINATOR.len() + ScoringParams::INIT_SPACE,
+        seeds = [b"scoring_params"],
+        bump,
+    )]
+    pub scoring_params: Account<'info, ScoringParams>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitScoringParams<'info> {
+    pub fn init_scoring_params(
+        &mut self,
+        tiers: [ScoringTier; SCORING_TIERS],
+        bumps: &InitScoringParamsBumps,
+    ) -> Result<()> {
+        require!(
+            ScoringParams::tiers_are_ordered(&tiers),
+            GhostErrors::InvalidScoringParams
+        );
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.scoring_params.set_inner(ScoringParams {
+            tiers,
+            updated_at: time_now,
+            bump: bumps.scoring_params,
+            version: ScoringParams::VERSION,
+        });
+
+        emit!(ScoringParamsUpdated {
+            admin: self.admin.key(),
+            tiers,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
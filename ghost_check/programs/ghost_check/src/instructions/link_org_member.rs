@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    create_org_member_asset,
+    errors::GhostErrors,
+    events::OrgMemberLinked,
+    migration_in_progress,
+    state::{DevState, GhostConfig, OrgMembership, OrgState, Versioned},
+};
+
+#[derive(Accounts)]
+pub struct LinkOrgMember<'info> {
+    // Member holding a dev badge, pays for and owns the member badge
+    #[account(mut)]
+    pub dev: Signer<'info>,
+
+    // The org admin co-signs, it knows who actually belongs to the org
+    #[account(address = org_state.authority @GhostErrors::OrgAuthorityMismatch)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"dev_state", dev.key().as_ref()],
+        bump = dev_state.bump,
+        constraint = dev_state.dev_addr == dev.key() @GhostErrors::IncorrectDevState,
+    )]
+    pub dev_state: Account<'info, DevState>,
+
+    /// CHECK: Only has data once the dev asked to move to another wallet
+    #[account(
+        seeds = [b"migration", dev.key().as_ref()],
+        bump,
+        constraint = !migration_in_progress(&migration) @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"org_state", org_state.hashed_org.as_ref()],
+        bump = org_state.bump,
+    )]
+    pub org_state: Account<'info, OrgState>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"org_badge", org_state.hashed_org.as_ref()],
+        bump = org_state.badge_bump,
+        address = org_state.org_badge,
+    )]
+    pub org_badge: UncheckedAccount<'info>,
+
+    // Fails to init when the dev is already linked to this org
+    #[account(
+        init,
+        payer = dev,
+        space = OrgMembership::DISCRIMINATOR.len() + OrgMembership::INIT_SPACE,
+        seeds = [b"org_member", org_badge.key().as_ref(), dev.key().as_ref()],
+        bump,
+    )]
+    pub org_membership: Account<'info, OrgMembership>,
+
+    /// CHECK: Core will create this
+    #[account(
+        mut,
+        seeds = [b"org_member_badge", org_badge.key().as_ref(), dev.key().as_ref()],
+        bump,
+    )]
+    pub member_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> LinkOrgMember<'info> {
+    pub fn link_org_member(&mut self, bumps: &LinkOrgMemberBumps) -> Result<()> {
+        create_org_member_asset(
+            &self.member_badge,
+            bumps.member_badge,
+            &self.org_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+            &self.dev_state,
+        )?;
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.org_membership.set_inner(OrgMembership {
+            org_badge: self.org_badge.key(),
+            dev: self.dev.key(),
+            dev_badge: self.dev_state.asset_address,
+            member_badge: self.member_badge.key(),
+            linked_at: time_now,
+            bump: bumps.org_membership,
+            member_badge_bump: bumps.member_badge,
+            version: OrgMembership::VERSION,
+        });
+        self.org_state.linked_members += 1;
+
+        emit!(OrgMemberLinked {
+            org_badge: self.org_badge.key(),
+            dev: self.dev.key(),
+            dev_badge: self.dev_state.asset_address,
+            member_badge: self.member_badge.key(),
+            linked_members: self.org_state.linked_members,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
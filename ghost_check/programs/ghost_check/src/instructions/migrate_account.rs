@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    errors::GhostErrors,
+    events::AccountMigrated,
+    state::{
+        ContributionState, DevState, GhostConfig, IdentityClaim, MigrationRequest, OrgMembership,
+        OrgState, RepoState, Revocation, ScoringParams, Versioned, VouchRecord, VouchTombstone,
+    },
+};
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    // Anyone can upgrade a layout, the payer only tops up rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Any versioned ghost_check account, the discriminator picks its layout
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAccount<'info> {
+    pub fn migrate_account(&mut self) -> Result<()> {
+        let discriminator: [u8; 8] = {
+            let data = self.account.try_borrow_data()?;
+            data.get(..8)
+                .and_then(|d| d.try_into().ok())
+                .ok_or(GhostErrors::UnversionedAccount)?
+        };
+
+        let (from_version, to_version) = match discriminator.as_ref() {
+            d if d == GhostConfig::DISCRIMINATOR => self.upgrade::<GhostConfig>()?,
+            d if d == DevState::DISCRIMINATOR => self.upgrade::<DevState>()?,
+            d if d == RepoState::DISCRIMINATOR => self.upgrade::<RepoState>()?,
+            d if d == VouchRecord::DISCRIMINATOR => self.upgrade::<VouchRecord>()?,
+            // No older layout yet, listed so a new version only needs its Legacy
+            d if d == ScoringParams::DISCRIMINATOR => self.upgrade::<ScoringParams>()?,
+            d if d == ContributionState::DISCRIMINATOR => self.upgrade::<ContributionState>()?,
+            d if d == OrgState::DISCRIMINATOR => self.upgrade::<OrgState>()?,
+            d if d == OrgMembership::DISCRIMINATOR => self.upgrade::<OrgMembership>()?,
+            d if d == IdentityClaim::DISCRIMINATOR => self.upgrade::<IdentityClaim>()?,
+            d if d == Revocation::DISCRIMINATOR => self.upgrade::<Revocation>()?,
+            d if d == VouchTombstone::DISCRIMINATOR => self.upgrade::<VouchTombstone>()?,
+            d if d == MigrationRequest::DISCRIMINATOR => self.upgrade::<MigrationRequest>()?,
+            _ => return err!(GhostErrors::UnversionedAccount),
+        };
+
+        emit!(AccountMigrated {
+            account: self.account.key(),
+            discriminator,
+            from_version,
+            to_version,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn upgrade<T: Versioned>(&self) -> Result<(u8, u8)> {
+        let (from_version, upgraded) = {
+            let data = self.account.try_borrow_data()?;
+            (T::stored_version(&data), T::try_deserialize_compat(&data)?)
+        };
+        require!(from_version < T::VERSION, GhostErrors::AccountUpToDate);
+
+        // Grow to the current layout, the upgraded account is rewritten from the start
+        let space = T::DISCRIMINATOR.len() + T::INIT_SPACE;
+        if self.account.data_len() < space {
+            let rent = Rent::get()?.minimum_balance(space);
+            let lamports = self.account.lamports();
+            if rent > lamports {
+                transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.payer.to_account_info(),
+                            to: self.account.to_account_info(),
+                        },
+                    ),
+                    rent - lamports,
+                )?;
+            }
+            self.account.resize(space)?;
+        }
+
+        let mut data = self.account.try_borrow_mut_data()?;
+        upgraded.try_serialize(&mut &mut data[..])?;
+
+        Ok((from_version, T::VERSION))
+    }
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_repo_asset, create_contribution_asset,
+    errors::GhostErrors,
+    events::ContributionBadgeMigrated,
+    state::{ContributionState, DevState, GhostConfig, MigrationRequest, Versioned},
+};
+
+#[derive(Accounts)]
+#[instruction(hashed_repo: [u8; 32])]
+pub struct MigrateContributionBadge<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = migration.dev_migrated @GhostErrors::MigrationNotStarted,
+        constraint = !migration.completed @GhostErrors::MigrationCompleted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+    )]
+    pub old_dev_state: Box<Account<'info, DevState>>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", old_wallet.key().as_ref()],
+        bump = old_dev_state.collection_asset_bump,
+        address = old_dev_state.asset_address,
+    )]
+    pub old_dev_badge: UncheckedAccount<'info>,
+
+    // Rent goes to the new wallet, it pays for the new contribution state.
+    // Dev and contribution states are boxed, four of them don't fit the BPF stack frame
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"contribution_state", old_dev_badge.key().as_ref(), &hashed_repo],
+        bump = old_contribution_state.bump,
+        constraint = old_contribution_state.dev_badge == old_dev_badge.key() @GhostErrors::DevBadgeMismatch,
+    )]
+    pub old_contribution_state: Box<Account<'info, ContributionState>>,
+
+    /// CHECK: This is being verified by the seeds and by the core program
+    #[account(
+        mut,
+        seeds = [b"contribution_badge", old_dev_badge.key().as_ref(), &hashed_repo],
+        bump = old_contribution_state.badge_bump,
+    )]
+    pub old_contribution_badge: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump = new_dev_state.bump,
+    )]
+    pub new_dev_state: Box<Account<'info, DevState>>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", new_wallet.key().as_ref()],
+        bump = new_dev_state.collection_asset_bump,
+        address = new_dev_state.asset_address,
+    )]
+    pub new_dev_badge: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = new_wallet,
+        space = ContributionState::DISCRIMINATOR.len() + ContributionState::INIT_SPACE,
+        seeds = [b"contribution_state", new_dev_badge.key().as_ref(), &hashed_repo],
+        bump,
+    )]
+    pub new_contribution_state: Box<Account<'info, ContributionState>>,
+
+    /// CHECK: Core will create this
+    #[account(
+        mut,
+        seeds = [b"contribution_badge", new_dev_badge.key().as_ref(), &hashed_repo],
+        bump,
+    )]
+    pub new_contribution_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateContributionBadge<'info> {
+    pub fn migrate_contribution_badge(
+        &mut self,
+        hashed_repo: [u8; 32],
+        bumps: &MigrateContributionBadgeBumps,
+    ) -> Result<()> {
+        // Burned and minted again in the new collection, like a repo badge
+        burn_repo_asset(
+            &self.old_contribution_badge,
+            &self.old_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+        )?;
+
+        let old = &self.old_contribution_state;
+        self.new_contribution_state.set_inner(ContributionState {
+            contributor: self.new_wallet.key(),
+            dev_badge: self.new_dev_badge.key(),
+            hashed_username: old.hashed_username,
+            hashed_repo,
+            repo_full_name: old.repo_full_name.clone(),
+            prs_merged: old.prs_merged,
+            commits: old.commits,
+            last_updated: old.last_updated,
+            nonce: old.nonce,
+            bump: bumps.new_contribution_state,
+            badge_bump: bumps.new_contribution_badge,
+            version: ContributionState::VERSION,
+        });
+
+        create_contribution_asset(
+            &self.new_contribution_badge,
+            &self.new_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+            &self.new_contribution_state,
+        )?;
+
+        self.old_dev_state.contribution_badges =
+            self.old_dev_state.contribution_badges.saturating_sub(1);
+        self.new_dev_state.contribution_badges += 1;
+
+        emit!(ContributionBadgeMigrated {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            old_contribution_badge: self.old_contribution_badge.key(),
+            new_contribution_badge: self.new_contribution_badge.key(),
+            hashed_repo,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    create_dev_collection,
+    errors::GhostErrors,
+    events::DevBadgeMigrated,
+    state::{DevState, GhostConfig, MigrationRequest, Versioned},
+};
+
+#[derive(Accounts)]
+pub struct MigrateDevIdentity<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = !migration.dev_migrated @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+        constraint = old_dev_state.hashed_username == migration.hashed_username @GhostErrors::IdentityMismatch,
+    )]
+    pub old_dev_state: Account<'info, DevState>,
+
+    // Fails to init when the new wallet already holds a dev badge
+    #[account(
+        init,
+        payer = new_wallet,
+        space = DevState::DISCRIMINATOR.len() + DevState::INIT_SPACE,
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump,
+    )]
+    pub new_dev_state: Account<'info, DevState>,
+
+    /// CHECK: Only has data when the protocol revoked the new wallet's badge
+    #[account(
+        seeds = [b"revocation", new_wallet.key().as_ref()],
+        bump,
+        constraint = revocation.data_is_empty() @GhostErrors::DevRevoked,
+    )]
+    pub revocation: UncheckedAccount<'info>,
+
+    /// CHECK: Core will create this
+    #[account(
+        mut,
+        seeds = [b"dev_badge", new_wallet.key().as_ref()],
+        bump,
+        constraint = new_dev_badge.data_is_empty() @GhostErrors::CollectionAlreadyInitialized,
+    )]
+    pub new_dev_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateDevIdentity<'info> {
+    pub fn migrate_dev_identity(&mut self, bumps: &MigrateDevIdentityBumps) -> Result<()> {
+        let time_now = Clock::get()?.unix_timestamp;
+        require!(
+            self.migration.is_unlocked(time_now),
+            GhostErrors::MigrationLocked
+        );
+
+        // Stats carry over as attested, last_updated too so staleness checks stay honest.
+        // Repo badges, vouch records and tombstones follow in their own instructions, each
+        // moving its share of the counters
+        let old = &self.old_dev_state;
+        self.new_dev_state.set_inner(DevState {
+            dev_addr: self.new_wallet.key(),
+            asset_address: self.new_dev_badge.key(),
+            hashed_username: old.hashed_username,
+            repo_count: old.repo_count,
+            owned_repo_count: old.owned_repo_count,
+            total_stars: old.total_stars,
+            total_commits: old.total_commits,
+            prs_merged: old.prs_merged,
+            issues_closed: old.issues_closed,
+            followers: old.followers,
+            account_age_days: old.account_age_days,
+            reputation_level: old.reputation_level,
+            verified_repos: 0,
+            vouch_count: 0,
+            vouch_score: 0,
+            vouches_given: 0,
+            last_updated: old.last_updated,
+            nonce: old.nonce,
+            bump: bumps.new_dev_state,
+            collection_asset_bump: bumps.new_dev_badge,
+            version: DevState::VERSION,
+            vouch_tombstones: 0,
+            contribution_badges: 0,
+        });
+
+        create_dev_collection(
+            &self.new_dev_badge,
+            bumps.new_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+            &self.new_dev_state,
+        )?;
+
+        self.migration.dev_migrated = true;
+
+        emit!(DevBadgeMigrated {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            old_dev_badge: self.old_dev_state.asset_address,
+            new_dev_badge: self.new_dev_badge.key(),
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_repo_asset, create_org_member_asset,
+    errors::GhostErrors,
+    events::OrgMembershipMigrated,
+    state::{DevState, GhostConfig, MigrationRequest, OrgMembership, OrgState, Versioned},
+};
+
+#[derive(Accounts)]
+pub struct MigrateOrgMembership<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    // Memberships live outside the dev collection and aren't counted on the dev state, they can
+    // still be moved once finish_migration completed the migration
+    #[account(
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = migration.dev_migrated @GhostErrors::MigrationNotStarted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump = new_dev_state.bump,
+    )]
+    pub new_dev_state: Box<Account<'info, DevState>>,
+
+    // Writable, a merged membership no longer counts towards linked_members
+    #[account(
+        mut,
+        seeds = [b"org_state", org_state.hashed_org.as_ref()],
+        bump = org_state.bump,
+    )]
+    pub org_state: Box<Account<'info, OrgState>>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"org_badge", org_state.hashed_org.as_ref()],
+        bump = org_state.badge_bump,
+        address = org_state.org_badge,
+    )]
+    pub org_badge: UncheckedAccount<'info>,
+
+    // Rent goes to the new wallet, it pays for the new membership
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"org_member", org_badge.key().as_ref(), old_wallet.key().as_ref()],
+        bump = old_org_membership.bump,
+    )]
+    pub old_org_membership: Box<Account<'info, OrgMembership>>,
+
+    /// CHECK: This is being verified by the membership and by the core program
+    #[account(
+        mut,
+        address = old_org_membership.member_badge,
+    )]
+    pub old_member_badge: UncheckedAccount<'info>,
+
+    // Already exists when the org linked the new wallet too
+    #[account(
+        init_if_needed,
+        payer = new_wallet,
+        space = OrgMembership::DISCRIMINATOR.len() + OrgMembership::INIT_SPACE,
+        seeds = [b"org_member", org_badge.key().as_ref(), new_wallet.key().as_ref()],
+        bump,
+    )]
+    pub new_org_membership: Box<Account<'info, OrgMembership>>,
+
+    /// CHECK: Core will create this unless the membership is merged
+    #[account(
+        mut,
+        seeds = [b"org_member_badge", org_badge.key().as_ref(), new_wallet.key().as_ref()],
+        bump,
+    )]
+    pub new_member_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateOrgMembership<'info> {
+    pub fn migrate_org_membership(&mut self, bumps: &MigrateOrgMembershipBumps) -> Result<()> {
+        // Burned through the org collection's delegate, the member badge is minted again below
+        burn_repo_asset(
+            &self.old_member_badge,
+            &self.org_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+        )?;
+
+        // A fresh membership is still zeroed, an existing one already counts the new wallet
+        let merged = self.new_org_membership.dev != Pubkey::default();
+        if merged {
+            self.org_state.linked_members = self.org_state.linked_members.saturating_sub(1);
+        } else {
+            create_org_member_asset(
+                &self.new_member_badge,
+                bumps.new_member_badge,
+                &self.org_badge,
+                &self.ghost_config,
+                &self.new_wallet,
+                &self.core_program,
+                &self.system_program,
+                &self.new_dev_state,
+            )?;
+
+            self.new_org_membership.set_inner(OrgMembership {
+                org_badge: self.org_badge.key(),
+                dev: self.new_wallet.key(),
+                dev_badge: self.new_dev_state.asset_address,
+                member_badge: self.new_member_badge.key(),
+                linked_at: self.old_org_membership.linked_at,
+                bump: bumps.new_org_membership,
+                member_badge_bump: bumps.new_member_badge,
+                version: OrgMembership::VERSION,
+            });
+        }
+
+        emit!(OrgMembershipMigrated {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            org_badge: self.org_badge.key(),
+            old_member_badge: self.old_member_badge.key(),
+            new_member_badge: self.new_member_badge.key(),
+            merged,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_repo_asset, create_repo_asset,
+    errors::GhostErrors,
+    events::RepoBadgeMigrated,
+    state::{DevState, GhostConfig, MigrationRequest, RepoState, Versioned},
+};
+
+#[derive(Accounts)]
+#[instruction(repo_name_padded: [u8; 32])]
+pub struct MigrateRepoBadge<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = migration.dev_migrated @GhostErrors::MigrationNotStarted,
+        constraint = !migration.completed @GhostErrors::MigrationCompleted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+    )]
+    pub old_dev_state: Box<Account<'info, DevState>>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", old_wallet.key().as_ref()],
+        bump = old_dev_state.collection_asset_bump,
+        address = old_dev_state.asset_address,
+    )]
+    pub old_dev_badge: UncheckedAccount<'info>,
+
+    // Rent goes to the new wallet, it pays for the new repo state.
+    // Dev and repo states are boxed, four of them don't fit the BPF stack frame
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"repo_state", old_dev_badge.key().as_ref(), &repo_name_padded],
+        bump = old_repo_state.bump,
+        constraint = old_repo_state.dev_badge == old_dev_badge.key() @GhostErrors::DevBadgeMismatch,
+    )]
+    pub old_repo_state: Box<Account<'info, RepoState>>,
+
+    /// CHECK: This is being verified by the seeds and by the core program
+    #[account(
+        mut,
+        seeds = [b"repo_badge", old_dev_badge.key().as_ref(), &repo_name_padded],
+        bump = old_repo_state.badge_bump,
+    )]
+    pub old_repo_badge: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump = new_dev_state.bump,
+    )]
+    pub new_dev_state: Box<Account<'info, DevState>>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", new_wallet.key().as_ref()],
+        bump = new_dev_state.collection_asset_bump,
+        address = new_dev_state.asset_address,
+    )]
+    pub new_dev_badge: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = new_wallet,
+        space = RepoState::DISCRIMINATOR.len() + RepoState::INIT_SPACE,
+        seeds = [b"repo_state", new_dev_badge.key().as_ref(), &repo_name_padded],
+        bump,
+    )]
+    pub new_repo_state: Box<Account<'info, RepoState>>,
+
+    /// CHECK: Core will create this
+    #[account(
+        mut,
+        seeds = [b"repo_badge", new_dev_badge.key().as_ref(), &repo_name_padded],
+        bump,
+    )]
+    pub new_repo_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateRepoBadge<'info> {
+    pub fn migrate_repo_badge(
+        &mut self,
+        repo_name_padded: [u8; 32],
+        bumps: &MigrateRepoBadgeBumps,
+    ) -> Result<()> {
+        // Assets can't be transferred, the badge is burned and minted again in the new collection
+        burn_repo_asset(
+            &self.old_repo_badge,
+            &self.old_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+        )?;
+
+        let old = &self.old_repo_state;
+        self.new_repo_state.set_inner(RepoState {
+            owner: self.new_wallet.key(),
+            dev_badge: self.new_dev_badge.key(),
+            hashed_username: old.hashed_username,
+            repo_name: old.repo_name.clone(),
+            stars: old.stars,
+            commits: old.commits,
+            forks: old.forks,
+            open_issues: old.open_issues,
+            is_fork: old.is_fork,
+            lang1: old.lang1.clone(),
+            lang2: old.lang2.clone(),
+            last_updated: old.last_updated,
+            nonce: old.nonce,
+            bump: bumps.new_repo_state,
+            badge_bump: bumps.new_repo_badge,
+            version: RepoState::VERSION,
+        });
+
+        create_repo_asset(
+            &self.new_repo_badge,
+            &self.new_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+            &self.system_program,
+            &self.new_repo_state,
+        )?;
+
+        self.old_dev_state.verified_repos = self.old_dev_state.verified_repos.saturating_sub(1);
+        self.new_dev_state.verified_repos += 1;
+
+        emit!(RepoBadgeMigrated {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            old_repo_badge: self.old_repo_badge.key(),
+            new_repo_badge: self.new_repo_badge.key(),
+            repo_name: repo_name_padded,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    errors::GhostErrors,
+    events::VouchRecordMigrated,
+    remove_vouch_from_target,
+    state::{DevState, GhostConfig, MigrationRequest, Versioned, VouchRecord},
+    update_dev_state,
+};
+
+#[derive(Accounts)]
+#[instruction(voucher: Pubkey, target: Pubkey)]
+pub struct MigrateVouchRecord<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    // Writable, a merged record no longer counts towards vouches_count
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = migration.dev_migrated @GhostErrors::MigrationNotStarted,
+        constraint = !migration.completed @GhostErrors::MigrationCompleted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+    )]
+    pub old_dev_state: Box<Account<'info, DevState>>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump = new_dev_state.bump,
+    )]
+    pub new_dev_state: Box<Account<'info, DevState>>,
+
+    /// CHECK: Deserialized in the handler, only updated when the record is merged and may be
+    /// closed by then
+    #[account(
+        mut,
+        seeds = [b"dev_state", migration.counterpart(&voucher, &target).as_ref()],
+        bump,
+    )]
+    pub counterpart_dev_state: UncheckedAccount<'info>,
+
+    /// CHECK: Counterpart dev collection, its vouch_count attribute is refreshed by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", migration.counterpart(&voucher, &target).as_ref()],
+        bump,
+    )]
+    pub counterpart_dev_badge: UncheckedAccount<'info>,
+
+    // Given or received by the old wallet, rent goes to the new wallet paying for the copy
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"vouch_record", voucher.as_ref(), target.as_ref()],
+        bump = old_vouch_record.bump,
+        constraint = voucher == old_wallet.key() || target == old_wallet.key() @GhostErrors::VouchRecordNotMigrating,
+    )]
+    pub old_vouch_record: Account<'info, VouchRecord>,
+
+    // Already exists when the counterpart vouched for, or was vouched by, both wallets
+    #[account(
+        init_if_needed,
+        payer = new_wallet,
+        space = VouchRecord::DISCRIMINATOR.len() + VouchRecord::INIT_SPACE,
+        seeds = [
+            b"vouch_record",
+            migration.forward(&voucher).as_ref(),
+            migration.forward(&target).as_ref(),
+        ],
+        bump,
+    )]
+    pub new_vouch_record: Account<'info, VouchRecord>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateVouchRecord<'info> {
+    pub fn migrate_vouch_record(
+        &mut self,
+        voucher: Pubkey,
+        target: Pubkey,
+        bumps: &MigrateVouchRecordBumps,
+    ) -> Result<()> {
+        let voucher_level = self.old_vouch_record.voucher_level;
+        let received = target == self.migration.old_wallet;
+
+        // The record's share of the counters leaves the old wallet, finish_migration waits for 0
+        let old = &mut self.old_dev_state;
+        if received {
+            old.vouch_count = old.vouch_count.saturating_sub(1);
+            old.vouch_score = old.vouch_score.saturating_sub(voucher_level as u64);
+        } else {
+            old.vouches_given = old.vouches_given.saturating_sub(1);
+        }
+
+        // A fresh record is still zeroed, an existing one means the vouch is already counted on
+        // the new wallet and the counterpart counted it twice
+        let merged = self.new_vouch_record.voucher != Pubkey::default();
+        if merged {
+            self.ghost_config.vouches_count = self.ghost_config.vouches_count.saturating_sub(1);
+            if received {
+                update_dev_state(&self.counterpart_dev_state, |voucher| {
+                    voucher.vouches_given = voucher.vouches_given.saturating_sub(1);
+                })?;
+            } else {
+                remove_vouch_from_target(
+                    &self.counterpart_dev_state,
+                    &self.counterpart_dev_badge,
+                    &self.ghost_config,
+                    &self.new_wallet,
+                    &self.core_program,
+                    &self.system_program,
+                    voucher_level,
+                    false,
+                )?;
+            }
+        } else {
+            self.new_vouch_record.set_inner(VouchRecord {
+                voucher: self.migration.forward(&voucher),
+                voucher_level,
+                target: self.migration.forward(&target),
+                timestamp: self.old_vouch_record.timestamp,
+                bump: bumps.new_vouch_record,
+                version: VouchRecord::VERSION,
+            });
+
+            // Badge attributes are synced once in finish_migration
+            let new = &mut self.new_dev_state;
+            if received {
+                new.vouch_count += 1;
+                new.vouch_score += voucher_level as u64;
+            } else {
+                new.vouches_given += 1;
+            }
+        }
+
+        emit!(VouchRecordMigrated {
+            old_vouch_record: self.old_vouch_record.key(),
+            new_vouch_record: self.new_vouch_record.key(),
+            voucher: self.migration.forward(&voucher),
+            target: self.migration.forward(&target),
+            merged,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
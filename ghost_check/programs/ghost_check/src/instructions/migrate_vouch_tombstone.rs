@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::VouchTombstoneMigrated,
+    state::{DevState, GhostConfig, MigrationRequest, Versioned, VouchTombstone},
+    update_dev_state,
+};
+
+#[derive(Accounts)]
+#[instruction(voucher: Pubkey, target: Pubkey)]
+pub struct MigrateVouchTombstone<'info> {
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump = migration.bump,
+        constraint = migration.new_wallet == new_wallet.key() @GhostErrors::IncorrectDevState,
+        constraint = migration.dev_migrated @GhostErrors::MigrationNotStarted,
+        constraint = !migration.completed @GhostErrors::MigrationCompleted,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Only used for the seeds, checked against the migration request
+    #[account(address = migration.old_wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+    )]
+    pub old_dev_state: Box<Account<'info, DevState>>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", new_wallet.key().as_ref()],
+        bump = new_dev_state.bump,
+    )]
+    pub new_dev_state: Box<Account<'info, DevState>>,
+
+    /// CHECK: Deserialized in the handler, only updated when the tombstone is merged and may be
+    /// closed by then
+    #[account(
+        mut,
+        seeds = [b"dev_state", migration.counterpart(&voucher, &target).as_ref()],
+        bump,
+    )]
+    pub counterpart_dev_state: UncheckedAccount<'info>,
+
+    // Left by or for the old wallet, it keeps blocking the same vouch from the new wallet
+    #[account(
+        mut,
+        close = new_wallet,
+        seeds = [b"vouch_tombstone", voucher.as_ref(), target.as_ref()],
+        bump = old_vouch_tombstone.bump,
+        constraint = voucher == old_wallet.key() || target == old_wallet.key() @GhostErrors::VouchTombstoneNotMigrating,
+    )]
+    pub old_vouch_tombstone: Account<'info, VouchTombstone>,
+
+    // Already exists when the same vouch was also revoked on the new wallet
+    #[account(
+        init_if_needed,
+        payer = new_wallet,
+        space = VouchTombstone::DISCRIMINATOR.len() + VouchTombstone::INIT_SPACE,
+        seeds = [
+            b"vouch_tombstone",
+            migration.forward(&voucher).as_ref(),
+            migration.forward(&target).as_ref(),
+        ],
+        bump,
+    )]
+    pub new_vouch_tombstone: Account<'info, VouchTombstone>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateVouchTombstone<'info> {
+    pub fn migrate_vouch_tombstone(
+        &mut self,
+        voucher: Pubkey,
+        target: Pubkey,
+        bumps: &MigrateVouchTombstoneBumps,
+    ) -> Result<()> {
+        self.old_dev_state.vouch_tombstones = self.old_dev_state.vouch_tombstones.saturating_sub(1);
+
+        // A fresh tombstone is still zeroed, an existing one is already counted on both sides
+        let merged = self.new_vouch_tombstone.voucher != Pubkey::default();
+        if merged {
+            update_dev_state(&self.counterpart_dev_state, |counterpart| {
+                counterpart.vouch_tombstones = counterpart.vouch_tombstones.saturating_sub(1);
+            })?;
+        } else {
+            self.new_vouch_tombstone.set_inner(VouchTombstone {
+                voucher: self.migration.forward(&voucher),
+                target: self.migration.forward(&target),
+                revoked_at: self.old_vouch_tombstone.revoked_at,
+                bump: bumps.new_vouch_tombstone,
+                version: VouchTombstone::VERSION,
+            });
+            self.new_dev_state.vouch_tombstones += 1;
+        }
+
+        emit!(VouchTombstoneMigrated {
+            old_vouch_tombstone: self.old_vouch_tombstone.key(),
+            new_vouch_tombstone: self.new_vouch_tombstone.key(),
+            voucher: self.migration.forward(&voucher),
+            target: self.migration.forward(&target),
+            merged,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -19,5 +19,98 @@ pub use helper::*;
 pub mod verify_dev;
 pub use verify_dev::*;
 
+pub mod verify_dev_criteria;
+pub use verify_dev_criteria::*;
+
 pub mod vouch_for_dev;
 pub use vouch_for_dev::*;
+
+pub mod rotate_backend_key;
+pub use rotate_backend_key::*;
+
+pub mod set_oracles;
+pub use set_oracles::*;
+
+pub mod release_identity;
+pub use release_identity::*;
+
+pub mod revoke_dev_badge;
+pub use revoke_dev_badge::*;
+
+pub mod revoke_vouch;
+pub use revoke_vouch::*;
+
+pub mod prune_vouch;
+pub use prune_vouch::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod propose_admin;
+pub use propose_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod burn_repo_badge;
+pub use burn_repo_badge::*;
+
+pub mod burn_dev_badge;
+pub use burn_dev_badge::*;
+
+pub mod request_migration;
+pub use request_migration::*;
+
+pub mod approve_migration;
+pub use approve_migration::*;
+
+pub mod cancel_migration;
+pub use cancel_migration::*;
+
+pub mod migrate_dev_identity;
+pub use migrate_dev_identity::*;
+
+pub mod migrate_repo_badge;
+pub use migrate_repo_badge::*;
+
+pub mod migrate_contribution_badge;
+pub use migrate_contribution_badge::*;
+
+pub mod migrate_vouch_record;
+pub use migrate_vouch_record::*;
+
+pub mod migrate_vouch_tombstone;
+pub use migrate_vouch_tombstone::*;
+
+pub mod finish_migration;
+pub use finish_migration::*;
+
+pub mod migrate_account;
+pub use migrate_account::*;
+
+pub mod org_badge;
+pub use org_badge::*;
+
+pub mod link_org_member;
+pub use link_org_member::*;
+
+pub mod contribution_badge;
+pub use contribution_badge::*;
+
+pub mod burn_contribution_badge;
+pub use burn_contribution_badge::*;
+
+pub mod init_scoring_params;
+pub use init_scoring_params::*;
+
+pub mod update_scoring_params;
+pub use update_scoring_params::*;
+
+pub mod settle_vouches_given;
+pub use settle_vouches_given::*;
+
+pub mod migrate_org_membership;
+pub use migrate_org_membership::*;
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    create_org_collection,
+    errors::GhostErrors,
+    events::OrgBadgeMinted,
+    org_badge_message,
+    state::{GhostConfig, OrgState, Versioned},
+    verify_attestation_age, verify_signature,
+};
+
+#[derive(Accounts)]
+#[instruction(hashed_org: [u8; 32])]
+pub struct OrgBadge<'info> {
+    // Admin of the GitHub org, checked by the backend before it signs
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    // One org badge per GitHub org
+    #[account(
+        init,
+        payer = authority,
+        space = OrgState::DISCRIMINATOR.len() + OrgState::INIT_SPACE,
+        seeds = [b"org_state", hashed_org.as_ref()],
+        bump,
+    )]
+    pub org_state: Account<'info, OrgState>,
+
+    /// CHECK: Core will create this
+    #[account(
+        mut,
+        seeds = [b"org_badge", hashed_org.as_ref()],
+        bump,
+        constraint = org_badge.data_is_empty() @GhostErrors::CollectionAlreadyInitialized,
+    )]
+    pub org_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Needed for instruction introspection
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instruction_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OrgBadge<'info> {
+    pub fn mint_org_badge(
+        &mut self,
+        hashed_org: [u8; 32],
+        public_repos: u32,
+        total_stars: u32,
+        member_count: u32,
+        issued_at: i64,
+        nonce: u64,
+        bumps: &OrgBadgeBumps,
+    ) -> Result<()> {
+        // Verify that the message(org stats) is signed by the backend signer for this wallet
+        let message = org_badge_message(
+            &self.authority.key(),
+            &hashed_org,
+            public_repos,
+            total_stars,
+            member_count,
+            issued_at,
+            nonce,
+        );
+        verify_signature(&self.instruction_sysvar, &self.ghost_config, &message)?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.org_state.set_inner(OrgState {
+            authority: self.authority.key(),
+            org_badge: self.org_badge.key(),
+            hashed_org,
+            public_repos,
+            total_stars,
+            member_count,
+            linked_members: 0,
+            last_updated: time_now,
+            nonce,
+            bump: bumps.org_state,
+            badge_bump: bumps.org_badge,
+            version: OrgState::VERSION,
+        });
+
+        create_org_collection(
+            &self.org_badge,
+            &self.ghost_config,
+            &self.authority,
+            &self.core_program,
+            &self.system_program,
+            &self.org_state,
+        )?;
+
+        emit!(OrgBadgeMinted {
+            authority: self.authority.key(),
+            org_badge: self.org_badge.key(),
+            org_state: self.org_state.key(),
+            hashed_org,
+            public_repos,
+            total_stars,
+            member_count,
+            nonce,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::GhostErrors, events::AdminProposed, state::GhostConfig};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    // The admin only changes once the new key signs accept_admin, so a typo can't lock the config.
+    // Proposing again replaces the pending admin
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.ghost_config.pending_admin = Some(new_admin);
+
+        emit!(AdminProposed {
+            admin: self.admin.key(),
+            pending_admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
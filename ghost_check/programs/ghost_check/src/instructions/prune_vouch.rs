@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    errors::GhostErrors,
+    events::VouchPruned,
+    remove_vouch_from_target,
+    state::{GhostConfig, Revocation, VouchRecord},
+};
+
+// Vouches given by a revoked dev stop counting for their targets, one record per call
+#[derive(Accounts)]
+#[instruction(voucher_addr: [u8; 32], target_addr: [u8; 32])]
+pub struct PruneVouch<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"revocation", voucher_addr.as_ref()],
+        bump = revocation.bump,
+    )]
+    pub revocation: Account<'info, Revocation>,
+
+    // Slashed like the rest of the voucher's accounts: the rent goes to the admin
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"vouch_record", &voucher_addr, &target_addr],
+        bump = vouch_record.bump,
+    )]
+    pub vouch_record: Account<'info, VouchRecord>,
+
+    /// CHECK: Deserialized in the handler, it is closed if the target lost its badge too
+    #[account(
+        mut,
+        seeds = [b"dev_state", &target_addr],
+        bump,
+    )]
+    pub target_dev_state: UncheckedAccount<'info>,
+
+    /// CHECK: Target dev collection, its vouch attributes are refreshed by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", &target_addr],
+        bump,
+    )]
+    pub target_dev_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PruneVouch<'info> {
+    pub fn prune_vouch(&mut self, voucher_addr: [u8; 32], target_addr: [u8; 32]) -> Result<()> {
+        self.ghost_config.vouches_count = self.ghost_config.vouches_count.saturating_sub(1);
+
+        // A target without a dev state anymore has nothing to count
+        let (target_vouch_count, target_vouch_score) = remove_vouch_from_target(
+            &self.target_dev_state,
+            &self.target_dev_badge,
+            &self.ghost_config,
+            &self.admin,
+            &self.core_program,
+            &self.system_program,
+            self.vouch_record.voucher_level,
+            false,
+        )?
+        .map_or((0, 0), |target| (target.vouch_count, target.vouch_score));
+
+        emit!(VouchPruned {
+            voucher: Pubkey::from(voucher_addr),
+            target: Pubkey::from(target_addr),
+            voucher_level: self.vouch_record.voucher_level,
+            target_vouch_count,
+            target_vouch_score,
+            pruned_by: self.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_dev_collection,
+    errors::GhostErrors,
+    events::IdentityReleased,
+    identity_release_message,
+    state::{DevState, GhostConfig, IdentityClaim},
+    verify_attestation_age, verify_signature,
+};
+
+#[derive(Accounts)]
+#[instruction(username: [u8; 32])]
+pub struct ReleaseIdentity<'info> {
+    // Wallet the GitHub account is moving to, it mints a fresh dev badge afterwards
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    // Writable, Core's BurnCollectionV1 takes the burn authority as a writable signer
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        close = old_wallet,
+        seeds = [b"identity_claim", username.as_ref()],
+        bump = identity_claim.bump,
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+
+    // Closed too, otherwise the old wallet would keep a second badge for the same identity.
+    // Only an empty identity is released, like in burn_dev_badge: repo and contribution badges
+    // and vouches given would be left behind on the old wallet. request_migration carries those over instead
+    #[account(
+        mut,
+        close = old_wallet,
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+        constraint = old_dev_state.hashed_username == username @GhostErrors::IdentityMismatch,
+        constraint = old_dev_state.verified_repos == 0 @GhostErrors::RepoBadgesRemaining,
+        constraint = old_dev_state.contribution_badges == 0 @GhostErrors::ContributionBadgesRemaining,
+        constraint = old_dev_state.vouches_given == 0 @GhostErrors::ActiveVouchesRemaining,
+    )]
+    pub old_dev_state: Account<'info, DevState>,
+
+    /// CHECK: Dev badge collection of the old wallet, burned with the dev state
+    #[account(
+        mut,
+        seeds = [b"dev_badge", old_wallet.key().as_ref()],
+        bump = old_dev_state.collection_asset_bump,
+        address = old_dev_state.asset_address,
+    )]
+    pub old_dev_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the rent back, must be the wallet holding the claim
+    #[account(mut, address = identity_claim.wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Needed for instruction introspection
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instruction_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+}
+
+impl<'info> ReleaseIdentity<'info> {
+    pub fn release_identity(&mut self, username: [u8; 32], issued_at: i64) -> Result<()> {
+        require!(
+            self.new_wallet.key() != self.old_wallet.key(),
+            GhostErrors::SameWalletRelease
+        );
+
+        // The backend signs only after the GitHub owner logged in and asked to move wallets
+        let message = identity_release_message(
+            &username,
+            &self.old_wallet.key(),
+            &self.new_wallet.key(),
+            issued_at,
+        );
+        verify_signature(&self.instruction_sysvar, &self.ghost_config, &message)?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
+
+        // The old wallet may be lost, ghost_config burns the collection as its update authority
+        burn_dev_collection(
+            &self.old_dev_badge,
+            &self.ghost_config,
+            &self.new_wallet,
+            &self.core_program,
+        )?;
+
+        emit!(IdentityReleased {
+            hashed_username: username,
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
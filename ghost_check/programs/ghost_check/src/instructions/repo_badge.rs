@@ -1,13 +1,13 @@
 use crate::errors::GhostErrors;
-use crate::state::{DevState, GhostConfig, RepoState};
-use crate::verify_signature;
+use crate::events::RepoBadgeMinted;
+use crate::state::{DevState, GhostConfig, RepoState, Versioned};
+use crate::{
+    create_repo_asset, migration_in_progress, repo_badge_message, trim_repo_name,
+    verify_attestation_age, verify_signature,
+};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions::ID as SYSVAR_INSTRUCTION_ID;
-use mpl_core::types::{
-    Attribute, Attributes, PermanentFreezeDelegate, Plugin, PluginAuthority, PluginAuthorityPair,
-    UpdateDelegate,
-};
-use mpl_core::{instructions::CreateV2CpiBuilder, ID as CORE_PROGRAM_ID};
+use mpl_core::ID as CORE_PROGRAM_ID;
 
 #[derive(Accounts)]
 #[instruction(repo_name_padded: [u8;32])]
@@ -19,6 +19,7 @@ pub struct RepoBadge<'info> {
         mut,
         seeds = [b"ghost_config"],
         bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
     )]
     pub ghost_config: Account<'info, GhostConfig>,
 
@@ -29,6 +30,14 @@ pub struct RepoBadge<'info> {
     )]
     pub dev_state: Account<'info, DevState>,
 
+    /// CHECK: Only has data once the dev asked to move to another wallet
+    #[account(
+        seeds = [b"migration", dev.key().as_ref()],
+        bump,
+        constraint = !migration_in_progress(&migration) @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: UncheckedAccount<'info>,
+
     /// CHECK: This is being verifed by the contraints
     #[account(
         mut,
@@ -81,77 +90,41 @@ impl<'info> RepoBadge<'info> {
         is_fork: u8,
         lang1: Vec<u8>,
         lang2: Vec<u8>,
+        issued_at: i64,
+        nonce: u64,
         bumps: &RepoBadgeBumps,
     ) -> Result<()> {
-        // Verify that the message( Repo stats ) is signed by the backend signer
+        // Verify that the message( Repo stats ) is signed by the backend signer for this wallet
+        let message = repo_badge_message(
+            &self.dev.key(),
+            &username_padded,
+            trim_repo_name(&repo_name_padded),
+            &lang1,
+            &lang2,
+            stars,
+            commits,
+            forks,
+            open_issues,
+            is_fork,
+            issued_at,
+            nonce,
+        );
         verify_signature(
             &self.instruction_sysvar.to_account_info(),
-            &self.ghost_config.backend_pubkey,
+            &self.ghost_config,
+            &message,
         )?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
+
+        // Attested for the identity the dev badge was minted with
+        require!(
+            username_padded == self.dev_state.hashed_username,
+            GhostErrors::IdentityMismatch
+        );
 
         // Minting repo badges for forked repo not allowed
         require!(is_fork != 1, GhostErrors::ForkedRepo);
 
-        let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[self.ghost_config.bump]]];
-        let repo_badge_seeds: &[&[&[u8]]] = &[&[
-            b"repo_badge",
-            &self.dev_badge.key().to_bytes(),
-            repo_name_padded.as_ref(),
-            &[bumps.repo_badge],
-        ]];
-
-        CreateV2CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.repo_badge.to_account_info())
-            .payer(&self.dev.to_account_info())
-            .collection(Some(&self.dev_badge.to_account_info()))
-            .authority(Some(&self.ghost_config.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .name("AMM-Repo".to_string())
-            .uri("https://ghostcheck/metadata/repo-image/dev".to_string())
-            .plugins(vec![
-                PluginAuthorityPair {
-                    plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
-                        frozen: true,
-                    }),
-                    authority: Some(PluginAuthority::UpdateAuthority),
-                },
-                PluginAuthorityPair {
-                    plugin: Plugin::UpdateDelegate(UpdateDelegate {
-                        additional_delegates: vec![],
-                    }),
-                    authority: Some(PluginAuthority::UpdateAuthority),
-                },
-                PluginAuthorityPair {
-                    plugin: Plugin::Attributes(Attributes {
-                        attribute_list: vec![
-                            Attribute {
-                                key: "repo_name".to_string(),
-                                value: String::from_utf8(repo_name_padded.to_vec()).unwrap(),
-                            },
-                            Attribute {
-                                key: "stars".to_string(),
-                                value: stars.to_string(),
-                            },
-                            Attribute {
-                                key: "commits".to_string(),
-                                value: commits.to_string(),
-                            },
-                            Attribute {
-                                key: "forks".to_string(),
-                                value: forks.to_string(),
-                            },
-                        ],
-                    }),
-                    authority: Some(PluginAuthority::UpdateAuthority),
-                },
-            ])
-            .external_plugin_adapters(vec![])
-            .invoke_signed(&[config_seeds[0], repo_badge_seeds[0]])?;
-
-        // Update the parent collection state
-        self.dev_state.verified_repos += 1;
-        self.ghost_config.repo_badges_minted += 1;
-
         // Get current time
         let time_now = Clock::get()?.unix_timestamp;
 
@@ -159,7 +132,7 @@ impl<'info> RepoBadge<'info> {
         self.repo_state.set_inner(RepoState {
             owner: self.dev.key(),
             dev_badge: self.dev_badge.key(),
-            repo_name: repo_name_padded.to_vec(),
+            repo_name: trim_repo_name(&repo_name_padded).to_vec(),
             hashed_username: username_padded,
             stars,
             commits,
@@ -169,8 +142,36 @@ impl<'info> RepoBadge<'info> {
             lang1,
             lang2,
             last_updated: time_now,
+            nonce,
             bump: bumps.repo_state,
             badge_bump: bumps.repo_badge,
+            version: RepoState::VERSION,
+        });
+
+        create_repo_asset(
+            &self.repo_badge,
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+            &self.repo_state,
+        )?;
+
+        // Update the parent collection state
+        self.dev_state.verified_repos += 1;
+        self.ghost_config.repo_badges_minted += 1;
+
+        emit!(RepoBadgeMinted {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            repo_badge: self.repo_badge.key(),
+            repo_state: self.repo_state.key(),
+            repo_name: repo_name_padded,
+            stars,
+            commits,
+            nonce,
+            timestamp: time_now,
         });
 
         Ok(())
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::MigrationRequested,
+    identity_migration_message,
+    state::{
+        DevState, GhostConfig, IdentityClaim, MigrationRequest, Versioned, MIGRATION_TIMELOCK,
+    },
+    verify_attestation_age, verify_signature,
+};
+
+#[derive(Accounts)]
+#[instruction(username: [u8; 32])]
+pub struct RequestMigration<'info> {
+    // Wallet the dev identity moves to, it pays for and drives every migration step
+    #[account(mut)]
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        seeds = [b"identity_claim", username.as_ref()],
+        bump = identity_claim.bump,
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+
+    /// CHECK: Must be the wallet holding the claim
+    #[account(address = identity_claim.wallet @GhostErrors::IncorrectDevState)]
+    pub old_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"dev_state", old_wallet.key().as_ref()],
+        bump = old_dev_state.bump,
+        constraint = old_dev_state.hashed_username == username @GhostErrors::IdentityMismatch,
+    )]
+    pub old_dev_state: Account<'info, DevState>,
+
+    // One open migration per wallet, cancel_migration closes it
+    #[account(
+        init,
+        payer = new_wallet,
+        space = MigrationRequest::DISCRIMINATOR.len() + MigrationRequest::INIT_SPACE,
+        seeds = [b"migration", old_wallet.key().as_ref()],
+        bump,
+    )]
+    pub migration: Account<'info, MigrationRequest>,
+
+    /// CHECK: Needed for instruction introspection
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instruction_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestMigration<'info> {
+    pub fn request_migration(
+        &mut self,
+        username: [u8; 32],
+        issued_at: i64,
+        bumps: &RequestMigrationBumps,
+    ) -> Result<()> {
+        require!(
+            self.new_wallet.key() != self.old_wallet.key(),
+            GhostErrors::SameWalletRelease
+        );
+
+        // The backend signs only after the GitHub owner logged in and asked to migrate wallets
+        let message = identity_migration_message(
+            &username,
+            &self.old_wallet.key(),
+            &self.new_wallet.key(),
+            issued_at,
+        );
+        verify_signature(&self.instruction_sysvar, &self.ghost_config, &message)?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.migration.set_inner(MigrationRequest {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            hashed_username: username,
+            requested_at: time_now,
+            unlock_at: time_now + MIGRATION_TIMELOCK,
+            approved: false,
+            dev_migrated: false,
+            completed: false,
+            bump: bumps.migration,
+            version: MigrationRequest::VERSION,
+        });
+
+        emit!(MigrationRequested {
+            old_wallet: self.old_wallet.key(),
+            new_wallet: self.new_wallet.key(),
+            hashed_username: username,
+            unlock_at: self.migration.unlock_at,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
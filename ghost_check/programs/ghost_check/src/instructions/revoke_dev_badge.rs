@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    burn_dev_collection, burn_repo_asset,
+    errors::GhostErrors,
+    events::DevBadgeRevoked,
+    state::{ContributionState, DevState, GhostConfig, RepoState, Revocation, Versioned},
+};
+
+#[derive(Accounts)]
+#[instruction(dev_addr: [u8; 32])]
+pub struct RevokeDevBadge<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // Writable, Core's BurnCollectionV1 takes the burn authority as a writable signer
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    // Slashed: the rent of the revoked accounts goes to the admin
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"dev_state", &dev_addr],
+        bump = dev_state.bump,
+    )]
+    pub dev_state: Account<'info, DevState>,
+
+    /// CHECK: This is being verifed by the contraints and by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", &dev_addr],
+        bump = dev_state.collection_asset_bump,
+        address = dev_state.asset_address,
+    )]
+    pub dev_badge: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Revocation::DISCRIMINATOR.len() + Revocation::INIT_SPACE,
+        seeds = [b"revocation", dev_addr.as_ref()],
+        bump,
+    )]
+    pub revocation: Account<'info, Revocation>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (repo_state, repo_badge) pair for every repo badge of the dev,
+    // (contribution_state, contribution_badge) pair for every contribution badge
+}
+
+impl<'info> RevokeDevBadge<'info> {
+    pub fn revoke_dev_badge(
+        &mut self,
+        dev_addr: [u8; 32],
+        reason: u8,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bumps: &RevokeDevBadgeBumps,
+    ) -> Result<()> {
+        require!(
+            remaining_accounts.len() % 2 == 0,
+            GhostErrors::InvalidRemainingAccounts
+        );
+
+        // Repo and contribution badges first, Core only burns an empty collection.
+        // Their state accounts are closed once every CPI is done: the rent goes to the admin,
+        // who is in the burn CPIs, and lamports moved before a CPI unbalance the instruction
+        let mut repo_states = Vec::new();
+        let mut contribution_states = Vec::new();
+        for pair in remaining_accounts.chunks(2) {
+            let badge = &pair[1];
+
+            // Contribution badges live in the same collection, told apart by their state account
+            let expected_badge = if pair[0]
+                .try_borrow_data()?
+                .starts_with(ContributionState::DISCRIMINATOR)
+            {
+                let contribution_state = Account::<ContributionState>::try_from(&pair[0])?;
+                require_keys_eq!(
+                    contribution_state.dev_badge,
+                    self.dev_badge.key(),
+                    GhostErrors::DevBadgeMismatch
+                );
+                let expected_badge = Pubkey::create_program_address(
+                    &[
+                        b"contribution_badge",
+                        self.dev_badge.key().as_ref(),
+                        &contribution_state.hashed_repo,
+                        &[contribution_state.badge_bump],
+                    ],
+                    &crate::ID,
+                )
+                .map_err(|_| GhostErrors::RepoBadgeMismatch)?;
+                contribution_states.push(contribution_state);
+                expected_badge
+            } else {
+                let repo_state = Account::<RepoState>::try_from(&pair[0])?;
+                require_keys_eq!(
+                    repo_state.dev_badge,
+                    self.dev_badge.key(),
+                    GhostErrors::DevBadgeMismatch
+                );
+                let expected_badge = Pubkey::create_program_address(
+                    &[
+                        b"repo_badge",
+                        self.dev_badge.key().as_ref(),
+                        &repo_state.padded_name(),
+                        &[repo_state.badge_bump],
+                    ],
+                    &crate::ID,
+                )
+                .map_err(|_| GhostErrors::RepoBadgeMismatch)?;
+                repo_states.push(repo_state);
+                expected_badge
+            };
+            require_keys_eq!(expected_badge, badge.key(), GhostErrors::RepoBadgeMismatch);
+
+            burn_repo_asset(
+                badge,
+                &self.dev_badge,
+                &self.ghost_config,
+                &self.admin,
+                &self.core_program,
+                &self.system_program,
+            )?;
+        }
+        let repo_badges_burned = repo_states.len() as u64;
+        require!(
+            repo_badges_burned == self.dev_state.verified_repos,
+            GhostErrors::RepoBadgesRemaining
+        );
+        require!(
+            contribution_states.len() as u32 == self.dev_state.contribution_badges,
+            GhostErrors::ContributionBadgesRemaining
+        );
+
+        burn_dev_collection(
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.admin,
+            &self.core_program,
+        )?;
+
+        for repo_state in repo_states {
+            repo_state.close(self.admin.to_account_info())?;
+        }
+        for contribution_state in contribution_states {
+            contribution_state.close(self.admin.to_account_info())?;
+        }
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        // The identity_claim is kept so the same GitHub account can't mint again elsewhere
+        self.revocation.set_inner(Revocation {
+            dev_addr: Pubkey::from(dev_addr),
+            hashed_username: self.dev_state.hashed_username,
+            dev_badge: self.dev_badge.key(),
+            reason,
+            revoked_by: self.admin.key(),
+            timestamp: time_now,
+            bump: bumps.revocation,
+            version: Revocation::VERSION,
+        });
+
+        emit!(DevBadgeRevoked {
+            dev: Pubkey::from(dev_addr),
+            hashed_username: self.dev_state.hashed_username,
+            dev_badge: self.dev_badge.key(),
+            reason,
+            repo_badges_burned,
+            revoked_by: self.admin.key(),
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
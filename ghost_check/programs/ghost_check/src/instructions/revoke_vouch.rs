@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+use crate::{
+    errors::GhostErrors,
+    events::VouchRevoked,
+    remove_vouch_from_target,
+    state::{DevState, GhostConfig, Versioned, VouchRecord, VouchTombstone},
+};
+
+#[derive(Accounts)]
+#[instruction(target_addr: [u8;32])]
+pub struct RevokeVouch<'info> {
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", voucher.key().as_ref()],
+        bump = voucher_dev_state.bump,
+    )]
+    pub voucher_dev_state: Account<'info, DevState>,
+
+    /// CHECK: Deserialized in the handler, the vouch can still be revoked once the target's
+    /// badge is burned, released or revoked and this is closed
+    #[account(
+        mut,
+        seeds = [b"dev_state", &target_addr],
+        bump,
+    )]
+    pub target_dev_state: UncheckedAccount<'info>,
+
+    /// CHECK: Target dev collection, its vouch_count attribute is refreshed by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", &target_addr],
+        bump,
+    )]
+    pub target_dev_badge: UncheckedAccount<'info>,
+
+    // Rent goes back to the voucher
+    #[account(
+        mut,
+        close = voucher,
+        seeds = [b"vouch_record", voucher.key().as_ref(), &target_addr],
+        bump = vouch_record.bump,
+    )]
+    pub vouch_record: Account<'info, VouchRecord>,
+
+    #[account(
+        init,
+        payer = voucher,
+        space = VouchTombstone::DISCRIMINATOR.len() + VouchTombstone::INIT_SPACE,
+        seeds = [b"vouch_tombstone", voucher.key().as_ref(), &target_addr],
+        bump,
+    )]
+    pub vouch_tombstone: Account<'info, VouchTombstone>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RevokeVouch<'info> {
+    pub fn revoke_vouch(&mut self, target_addr: [u8; 32], bumps: &RevokeVouchBumps) -> Result<()> {
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.vouch_tombstone.set_inner(VouchTombstone {
+            voucher: self.voucher.key(),
+            target: Pubkey::from(target_addr),
+            revoked_at: time_now,
+            bump: bumps.vouch_tombstone,
+            version: VouchTombstone::VERSION,
+        });
+
+        // Update config and vouched dev state
+        self.ghost_config.vouches_count = self.ghost_config.vouches_count.saturating_sub(1);
+        // Frees the budget, the tombstone still blocks vouching this dev again
+        self.voucher_dev_state.vouches_given =
+            self.voucher_dev_state.vouches_given.saturating_sub(1);
+        // Counted on both sides, a migration of either wallet has to forward it
+        self.voucher_dev_state.vouch_tombstones += 1;
+
+        // A target without a dev state anymore has nothing to count
+        let (target_vouch_count, target_vouch_score) = remove_vouch_from_target(
+            &self.target_dev_state,
+            &self.target_dev_badge,
+            &self.ghost_config,
+            &self.voucher,
+            &self.core_program,
+            &self.system_program,
+            self.vouch_record.voucher_level,
+            true,
+        )?
+        .map_or((0, 0), |target| (target.vouch_count, target.vouch_score));
+
+        emit!(VouchRevoked {
+            voucher: self.voucher.key(),
+            target: Pubkey::from(target_addr),
+            voucher_level: self.vouch_record.voucher_level,
+            target_vouch_count,
+            target_vouch_score,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::GhostErrors, events::BackendKeyRotated, state::GhostConfig};
+
+#[derive(Accounts)]
+pub struct RotateBackendKey<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+}
+
+impl<'info> RotateBackendKey<'info> {
+    pub fn rotate_backend_key(
+        &mut self,
+        new_backend_pubkey: [u8; 32],
+        new_key_id: u32,
+        grace_slots: u64,
+    ) -> Result<()> {
+        require!(
+            new_backend_pubkey != self.ghost_config.backend_pubkey,
+            GhostErrors::BackendKeyUnchanged
+        );
+        // An oracle promoted to backend key would sit in two oracle seats
+        require!(
+            !self.ghost_config.oracles.contains(&new_backend_pubkey),
+            GhostErrors::InvalidOracleSet
+        );
+
+        let clock = Clock::get()?;
+        // Only one previous key is kept, rotating again would drop it before its grace ends
+        require!(
+            !self.ghost_config.in_key_grace_period(clock.slot),
+            GhostErrors::KeyGracePeriodActive
+        );
+        let previous_backend_pubkey = self.ghost_config.backend_pubkey;
+
+        // Keep the old key valid for grace_slots so attestations already handed out can land
+        let ghost_config = &mut self.ghost_config;
+        ghost_config.previous_backend_pubkey = Some(previous_backend_pubkey);
+        ghost_config.previous_key_expiry_slot = clock.slot.saturating_add(grace_slots);
+        ghost_config.backend_pubkey = new_backend_pubkey;
+        ghost_config.backend_key_id = new_key_id;
+
+        emit!(BackendKeyRotated {
+            admin: self.admin.key(),
+            new_backend_pubkey,
+            new_key_id,
+            previous_backend_pubkey,
+            previous_key_expiry_slot: self.ghost_config.previous_key_expiry_slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
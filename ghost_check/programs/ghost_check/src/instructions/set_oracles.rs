@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::OraclesUpdated,
+    state::{GhostConfig, MAX_ORACLES},
+};
+
+#[derive(Accounts)]
+pub struct SetOracles<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+}
+
+impl<'info> SetOracles<'info> {
+    pub fn set_oracles(&mut self, oracles: Vec<[u8; 32]>, threshold: u8) -> Result<()> {
+        require!(oracles.len() <= MAX_ORACLES, GhostErrors::InvalidOracleSet);
+
+        // Each oracle must hold its own seat, the backend key already has seat 0
+        for (i, oracle) in oracles.iter().enumerate() {
+            require!(
+                oracle != &self.ghost_config.backend_pubkey
+                    && self.ghost_config.previous_backend_pubkey.as_ref() != Some(oracle)
+                    && !oracles[..i].contains(oracle),
+                GhostErrors::InvalidOracleSet
+            );
+        }
+
+        // Backend + oracles is the total number of possible signers
+        require!(
+            threshold >= 1 && threshold as usize <= oracles.len() + 1,
+            GhostErrors::InvalidOracleThreshold
+        );
+
+        emit!(OraclesUpdated {
+            admin: self.admin.key(),
+            oracles: oracles.clone(),
+            threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        self.ghost_config.oracles = oracles;
+        self.ghost_config.oracle_threshold = threshold;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::GhostErrors, events::PauseSet, state::GhostConfig};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+}
+
+impl<'info> SetPaused<'info> {
+    // Emergency switch, e.g. for a compromised oracle. Only these keep working while paused:
+    // admin instructions, reads (verify_dev, query_dev, verify_dev_criteria, query_dev_criteria),
+    // migrate_account, and the ways out of a position: burning badges and cancel_migration
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.ghost_config.paused = paused;
+
+        emit!(PauseSet {
+            admin: self.admin.key(),
+            paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::VouchesGivenSettled,
+    state::{DevState, GhostConfig},
+};
+
+// Baseline dev states didn't count the vouches they gave. The admin counts the dev's vouch
+// records off-chain (voucher == dev_addr) and sets the result once
+#[derive(Accounts)]
+#[instruction(dev_addr: [u8; 32])]
+pub struct SettleVouchesGiven<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dev_state", &dev_addr],
+        bump = dev_state.bump,
+        constraint = !dev_state.vouches_given_counted() @GhostErrors::VouchesGivenCounted,
+    )]
+    pub dev_state: Account<'info, DevState>,
+}
+
+impl<'info> SettleVouchesGiven<'info> {
+    pub fn settle_vouches_given(&mut self, vouches_given: u32) -> Result<()> {
+        self.dev_state.vouches_given = vouches_given;
+
+        emit!(VouchesGivenSettled {
+            admin: self.admin.key(),
+            dev: self.dev_state.dev_addr,
+            vouches_given,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
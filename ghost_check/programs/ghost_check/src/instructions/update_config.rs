@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::ConfigUpdated,
+    state::{GhostConfig, MAX_METADATA_BASE_URI_LEN},
+};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    // `None` keeps the current value, backend keys and oracles have their own instructions
+    pub fn update_config(
+        &mut self,
+        max_attestation_age: Option<i64>,
+        min_voucher_level: Option<u8>,
+        vouch_budget: Option<[u16; 5]>,
+        metadata_base_uri: Option<String>,
+        min_update_interval: Option<i64>,
+        max_staleness: Option<i64>,
+    ) -> Result<()> {
+        let ghost_config = &mut self.ghost_config;
+
+        if let Some(max_attestation_age) = max_attestation_age {
+            require!(max_attestation_age > 0, GhostErrors::InvalidAttestationAge);
+            ghost_config.max_attestation_age = max_attestation_age;
+        }
+        if let Some(min_voucher_level) = min_voucher_level {
+            require!(
+                (1..=5).contains(&min_voucher_level),
+                GhostErrors::InvalidVouchConfig
+            );
+            ghost_config.min_voucher_level = min_voucher_level;
+        }
+        if let Some(vouch_budget) = vouch_budget {
+            require!(
+                GhostConfig::vouch_budget_is_valid(&vouch_budget),
+                GhostErrors::InvalidVouchBudget
+            );
+            ghost_config.vouch_budget = vouch_budget;
+        }
+        // Existing badges pick the new URI up on their next update
+        if let Some(metadata_base_uri) = metadata_base_uri {
+            require!(
+                metadata_base_uri.len() <= MAX_METADATA_BASE_URI_LEN,
+                GhostErrors::MetadataUriTooLong
+            );
+            ghost_config.metadata_base_uri = metadata_base_uri;
+        }
+        if let Some(min_update_interval) = min_update_interval {
+            require!(
+                min_update_interval >= 0,
+                GhostErrors::InvalidFreshnessConfig
+            );
+            ghost_config.min_update_interval = min_update_interval;
+        }
+        if let Some(max_staleness) = max_staleness {
+            require!(max_staleness >= 0, GhostErrors::InvalidFreshnessConfig);
+            ghost_config.max_staleness = max_staleness;
+        }
+
+        emit!(ConfigUpdated {
+            admin: self.admin.key(),
+            max_attestation_age: ghost_config.max_attestation_age,
+            min_voucher_level: ghost_config.min_voucher_level,
+            vouch_budget: ghost_config.vouch_budget,
+            metadata_base_uri: ghost_config.metadata_base_uri.clone(),
+            min_update_interval: ghost_config.min_update_interval,
+            max_staleness: ghost_config.max_staleness,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseCollectionV1, instructions::UpdateCollectionV1CpiBuilder, ID as CORE_PROGRAM_ID,
+};
 
 use crate::{
+    dev_badge_message,
     errors::GhostErrors,
-    state::{DevState, GhostConfig},
-    verify_signature,
+    events::DevBadgeUpdated,
+    migration_in_progress,
+    state::{DevState, GhostConfig, ScoringParams},
+    sync_dev_badge_attributes, verify_attestation_age, verify_signature,
 };
 
 #[derive(Accounts)]
@@ -15,6 +21,7 @@ pub struct UpdateDevBadge<'info> {
         mut,
         seeds = [b"ghost_config"],
         bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
     )]
     pub ghost_config: Account<'info, GhostConfig>,
 
@@ -26,6 +33,21 @@ pub struct UpdateDevBadge<'info> {
     )]
     pub dev_state: Account<'info, DevState>,
 
+    /// CHECK: Only has data once the dev asked to move to another wallet
+    #[account(
+        seeds = [b"migration", dev.key().as_ref()],
+        bump,
+        constraint = !migration_in_progress(&migration) @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: UncheckedAccount<'info>,
+
+    // The level in the attestation must follow from its metrics under this table
+    #[account(
+        seeds = [b"scoring_params"],
+        bump = scoring_params.bump,
+    )]
+    pub scoring_params: Account<'info, ScoringParams>,
+
     /// CHECK: This is being verifed by the contraints and by the core program
     #[account(
         mut,
@@ -38,6 +60,10 @@ pub struct UpdateDevBadge<'info> {
     /// CHECK: instruction sysvar instruction intro account should be passed
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     pub instruction_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -54,18 +80,58 @@ impl<'info> UpdateDevBadge<'info> {
         followers: u32,
         account_age_days: u32,
         reputation_level: u8,
+        issued_at: i64,
+        nonce: u64,
     ) -> Result<()> {
         // Verify the message( updated dev stats ) passed in signed by backend
+        let message = dev_badge_message(
+            &self.dev.key(),
+            username,
+            repo_count,
+            total_commits,
+            owned_repo_count,
+            total_stars,
+            prs_merged,
+            issues_closed,
+            followers,
+            account_age_days,
+            reputation_level,
+            issued_at,
+            nonce,
+        );
         verify_signature(
             &self.instruction_sysvar.to_account_info(),
-            &self.ghost_config.backend_pubkey,
+            &self.ghost_config,
+            &message,
         )?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
+
+        require_eq!(
+            reputation_level,
+            self.scoring_params
+                .reputation_level(repo_count, total_commits, account_age_days),
+            GhostErrors::ReputationLevelMismatch
+        );
+
+        // The identity is fixed at mint, it moves only through release_identity
+        require!(
+            username == &self.dev_state.hashed_username,
+            GhostErrors::IdentityMismatch
+        );
+
+        // Older attestations, even unexpired ones, can't roll the stats back
+        require!(nonce > self.dev_state.nonce, GhostErrors::NonceAlreadyUsed);
 
         // Get Current Timestamp
         let time_now = Clock::get()?.unix_timestamp;
+        require!(
+            self.ghost_config
+                .update_allowed(self.dev_state.last_updated, time_now),
+            GhostErrors::UpdateCooldown
+        );
+        let old_level = self.dev_state.reputation_level;
 
         let dev_state = &mut self.dev_state;
-        dev_state.hashed_username = username.clone();
         dev_state.repo_count = repo_count;
         dev_state.owned_repo_count = owned_repo_count;
         dev_state.total_stars = total_stars;
@@ -76,6 +142,44 @@ impl<'info> UpdateDevBadge<'info> {
         dev_state.account_age_days = account_age_days;
         dev_state.reputation_level = reputation_level;
         dev_state.last_updated = time_now;
+        dev_state.nonce = nonce;
+
+        sync_dev_badge_attributes(
+            &self.dev_badge,
+            &self.ghost_config,
+            &self.dev,
+            &self.core_program,
+            &self.system_program,
+            &self.dev_state,
+        )?;
+
+        // Collections minted before the URI was set still point at the placeholder, only those
+        // need the extra CPI
+        let uri = self
+            .ghost_config
+            .metadata_uri("dev", &self.dev_badge.key())?;
+        let current = BaseCollectionV1::from_bytes(&self.dev_badge.try_borrow_data()?)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+        if current.uri != uri {
+            let config_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[self.ghost_config.bump]]];
+            UpdateCollectionV1CpiBuilder::new(&self.core_program.to_account_info())
+                .collection(&self.dev_badge.to_account_info())
+                .payer(&self.dev.to_account_info())
+                .authority(Some(&self.ghost_config.to_account_info()))
+                .system_program(&self.system_program.to_account_info())
+                .new_uri(uri)
+                .invoke_signed(config_seeds)?;
+        }
+
+        emit!(DevBadgeUpdated {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            dev_state: self.dev_state.key(),
+            old_level,
+            new_level: reputation_level,
+            nonce,
+            timestamp: time_now,
+        });
 
         Ok(())
     }
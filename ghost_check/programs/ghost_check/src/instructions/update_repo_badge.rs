@@ -1,14 +1,17 @@
 use anchor_lang::prelude::*;
 use mpl_core::{
-    instructions::UpdatePluginV1CpiBuilder,
-    types::{Attribute, Attributes, Plugin},
+    accounts::BaseAssetV1,
+    instructions::{UpdatePluginV1CpiBuilder, UpdateV1CpiBuilder},
+    types::Plugin,
     ID as CORE_PROGRAM_ID,
 };
 
 use crate::{
     errors::GhostErrors,
+    events::RepoBadgeUpdated,
+    migration_in_progress, repo_badge_attributes, repo_badge_message, repo_badge_name,
     state::{DevState, GhostConfig, RepoState},
-    verify_signature,
+    trim_repo_name, verify_attestation_age, verify_signature,
 };
 
 #[derive(Accounts)]
@@ -20,7 +23,8 @@ pub struct UpdateRepoBadge<'info> {
     #[account(
         mut,
         seeds= [b"ghost_config"],
-        bump = ghost_config.bump
+        bump = ghost_config.bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
     )]
     pub ghost_config: Account<'info, GhostConfig>,
 
@@ -32,6 +36,14 @@ pub struct UpdateRepoBadge<'info> {
     )]
     pub dev_state: Account<'info, DevState>,
 
+    /// CHECK: Only has data once the dev asked to move to another wallet
+    #[account(
+        seeds = [b"migration", dev.key().as_ref()],
+        bump,
+        constraint = !migration_in_progress(&migration) @GhostErrors::MigrationInProgress,
+    )]
+    pub migration: UncheckedAccount<'info>,
+
     /// CHECK: This is being verified by the core program
     #[account(
         mut,
@@ -52,7 +64,7 @@ pub struct UpdateRepoBadge<'info> {
     #[account(
         mut,
         seeds = [b"repo_badge", dev_badge.key().as_ref(), &repo_name_padded],
-        bump = repo_state.bump
+        bump = repo_state.badge_bump,
     )]
     pub repo_badge: UncheckedAccount<'info>,
 
@@ -77,46 +89,47 @@ impl<'info> UpdateRepoBadge<'info> {
         open_issues: u32,
         lang1: Vec<u8>,
         lang2: Vec<u8>,
+        issued_at: i64,
+        nonce: u64,
     ) -> Result<()> {
-        // Verify the message( updated repo stats ) passed in signed by backend
-        verify_signature(&self.instruction_sysvar, &self.ghost_config.backend_pubkey)?;
+        // Verify the message( updated repo stats ) passed in signed by backend,
+        // forked repos never get a badge so the signed is_fork flag is the stored one
+        let message = repo_badge_message(
+            &self.dev.key(),
+            &username_hashed,
+            trim_repo_name(&repo_name_padded),
+            &lang1,
+            &lang2,
+            stars,
+            commits,
+            forks,
+            open_issues,
+            self.repo_state.is_fork,
+            issued_at,
+            nonce,
+        );
+        verify_signature(&self.instruction_sysvar, &self.ghost_config, &message)?;
+        verify_attestation_age(issued_at, self.ghost_config.max_attestation_age)?;
 
-        //Signer seeds for ghost config to sign the cpi
-        let signers_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[self.ghost_config.bump]]];
+        // Attested for the identity the dev badge was minted with
+        require!(
+            username_hashed == self.dev_state.hashed_username,
+            GhostErrors::IdentityMismatch
+        );
 
-        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.repo_badge.to_account_info())
-            .collection(Some(&self.dev_badge.to_account_info()))
-            .authority(Some(&self.ghost_config.to_account_info()))
-            .payer(&self.dev.to_account_info())
-            .system_program(&self.system_program.to_account_info())
-            .plugin(Plugin::Attributes(Attributes {
-                attribute_list: vec![
-                    Attribute {
-                        key: String::from("repo_name"),
-                        value: String::from_utf8(repo_name_padded.to_vec()).unwrap(),
-                    },
-                    Attribute {
-                        key: String::from("stars"),
-                        value: stars.to_string(),
-                    },
-                    Attribute {
-                        key: String::from("commits"),
-                        value: commits.to_string(),
-                    },
-                    Attribute {
-                        key: String::from("forks"),
-                        value: forks.to_string(),
-                    },
-                ],
-            }))
-            .invoke_signed(signers_seeds)?;
+        // Older attestations can't roll the stats back
+        require!(nonce > self.repo_state.nonce, GhostErrors::NonceAlreadyUsed);
 
         // Get current time
         let time_now = Clock::get()?.unix_timestamp;
+        require!(
+            self.ghost_config
+                .update_allowed(self.repo_state.last_updated, time_now),
+            GhostErrors::UpdateCooldown
+        );
 
         let repo_state = &mut self.repo_state;
-        repo_state.repo_name = repo_name_padded.to_vec();
+        repo_state.repo_name = trim_repo_name(&repo_name_padded).to_vec();
         repo_state.hashed_username = username_hashed;
         repo_state.stars = stars;
         repo_state.commits = commits;
@@ -125,6 +138,51 @@ impl<'info> UpdateRepoBadge<'info> {
         repo_state.lang1 = lang1;
         repo_state.lang2 = lang2;
         repo_state.last_updated = time_now;
+        repo_state.nonce = nonce;
+
+        //Signer seeds for ghost config to sign the cpi
+        let signers_seeds: &[&[&[u8]]] = &[&[b"ghost_config", &[self.ghost_config.bump]]];
+
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.repo_badge.to_account_info())
+            .collection(Some(&self.dev_badge.to_account_info()))
+            .authority(Some(&self.ghost_config.to_account_info()))
+            .payer(&self.dev.to_account_info())
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::Attributes(repo_badge_attributes(&self.repo_state)))
+            .invoke_signed(signers_seeds)?;
+
+        // Badges minted before the name and URI were set still carry the placeholders, only
+        // those need the extra CPI
+        let name = repo_badge_name(&self.repo_state);
+        let uri = self
+            .ghost_config
+            .metadata_uri("repo", &self.repo_badge.key())?;
+        let current = BaseAssetV1::from_bytes(&self.repo_badge.try_borrow_data()?)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+        if current.name != name || current.uri != uri {
+            UpdateV1CpiBuilder::new(&self.core_program.to_account_info())
+                .asset(&self.repo_badge.to_account_info())
+                .collection(Some(&self.dev_badge.to_account_info()))
+                .authority(Some(&self.ghost_config.to_account_info()))
+                .payer(&self.dev.to_account_info())
+                .system_program(&self.system_program.to_account_info())
+                .new_name(name)
+                .new_uri(uri)
+                .invoke_signed(signers_seeds)?;
+        }
+
+        emit!(RepoBadgeUpdated {
+            dev: self.dev.key(),
+            dev_badge: self.dev_badge.key(),
+            repo_badge: self.repo_badge.key(),
+            repo_state: self.repo_state.key(),
+            repo_name: repo_name_padded,
+            stars,
+            commits,
+            nonce,
+            timestamp: time_now,
+        });
 
         Ok(())
     }
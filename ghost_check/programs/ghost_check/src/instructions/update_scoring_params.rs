@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GhostErrors,
+    events::ScoringParamsUpdated,
+    state::{GhostConfig, ScoringParams, ScoringTier, SCORING_TIERS},
+};
+
+#[derive(Accounts)]
+pub struct UpdateScoringParams<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"ghost_config"],
+        bump = ghost_config.bump,
+        has_one = admin @GhostErrors::AdminMismatch,
+    )]
+    pub ghost_config: Account<'info, GhostConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"scoring_params"],
+        bump = scoring_params.bump,
+    )]
+    pub scoring_params: Account<'info, ScoringParams>,
+}
+
+impl<'info> UpdateScoringParams<'info> {
+    // Applies to mints and updates from now on, existing badges keep their level until updated
+    pub fn update_scoring_params(&mut self, tiers: [ScoringTier; SCORING_TIERS]) -> Result<()> {
+        require!(
+            ScoringParams::tiers_are_ordered(&tiers),
+            GhostErrors::InvalidScoringParams
+        );
+
+        let time_now = Clock::get()?.unix_timestamp;
+
+        self.scoring_params.tiers = tiers;
+        self.scoring_params.updated_at = time_now;
+
+        emit!(ScoringParamsUpdated {
+            admin: self.admin.key(),
+            tiers,
+            timestamp: time_now,
+        });
+
+        Ok(())
+    }
+}
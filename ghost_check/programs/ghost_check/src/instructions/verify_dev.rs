@@ -1,34 +1,168 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::GhostErrors;
-use crate::state::DevState;
+use crate::events::DevVerified;
+use crate::migration_in_progress;
+use crate::state::{DevState, GhostConfig, Versioned};
 
+// Returned to callers through `set_return_data`, CPI callers read it with `Return::get`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DevVerification {
+    pub dev_addr: Pubkey,
+    pub reputation_level: u8,
+    pub vouch_count: u64,
+    pub vouch_score: u64,
+    pub verified_repos: u64,
+    pub last_updated: i64,
+    pub revoked: bool,
+}
+
+impl DevVerification {
+    fn from_dev_state(dev_state: &DevState) -> Self {
+        Self {
+            dev_addr: dev_state.dev_addr,
+            reputation_level: dev_state.reputation_level,
+            vouch_count: dev_state.vouch_count,
+            vouch_score: dev_state.vouch_score,
+            verified_repos: dev_state.verified_repos,
+            last_updated: dev_state.last_updated,
+            revoked: false,
+        }
+    }
+}
+
+// Read only, no signer needed so any program can CPI into it
 #[derive(Accounts)]
 #[instruction(dev_addr: [u8;32])]
 pub struct VerifyDev<'info> {
-    #[account(mut)]
-    pub verifier: Signer<'info>,
-
+    /// CHECK: Deserialized in the handler, it is closed once the badge is revoked
     #[account(
         seeds = [b"dev_state", &dev_addr],
         bump,
     )]
-    pub target_dev_state: Account<'info, DevState>,
+    pub target_dev_state: UncheckedAccount<'info>,
+
+    /// CHECK: Only has data when the dev badge was revoked
+    #[account(
+        seeds = [b"revocation", dev_addr.as_ref()],
+        bump,
+    )]
+    pub revocation: UncheckedAccount<'info>,
+
+    /// CHECK: Only has data once the dev asked to move to another wallet
+    #[account(
+        seeds = [b"migration", dev_addr.as_ref()],
+        bump,
+    )]
+    pub migration: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized in the handler, only its max_staleness is read
+    #[account(
+        seeds = [b"ghost_config"],
+        bump,
+    )]
+    pub ghost_config: UncheckedAccount<'info>,
 }
 
 impl<'info> VerifyDev<'info> {
-    pub fn verify_dev(&self, dev_addr: [u8; 32], min_lvl: u8) -> Result<()> {
+    // `max_staleness` overrides the global one from GhostConfig, 0 accepts any age
+    pub fn verify_dev(
+        &self,
+        dev_addr: [u8; 32],
+        min_lvl: u8,
+        max_staleness: Option<i64>,
+    ) -> Result<DevVerification> {
         // validate the min_lvl input
         require!(
-            min_lvl >= 1 && min_lvl <= 5,
+            (1..=5).contains(&min_lvl),
             GhostErrors::ReputationLvlInvalid
         );
+        require!(!self.is_revoked(), GhostErrors::DevRevoked);
+        require!(!self.is_migrating(), GhostErrors::MigrationInProgress);
+
+        let time_now = Clock::get()?.unix_timestamp;
+        let target_dev_state = self.load_dev_state()?;
+
         // Validate Dev
         require!(
-            self.target_dev_state.reputation_level >= min_lvl,
+            target_dev_state.reputation_level >= min_lvl,
             GhostErrors::DevVerificationFailed
         );
+        self.require_fresh(&target_dev_state, max_staleness, time_now)?;
+
+        emit!(DevVerified {
+            dev: Pubkey::from(dev_addr),
+            dev_state: self.target_dev_state.key(),
+            min_lvl,
+            reputation_level: target_dev_state.reputation_level,
+            revoked: false,
+            timestamp: time_now,
+        });
+
+        Ok(DevVerification::from_dev_state(&target_dev_state))
+    }
+
+    // Only reads: a revoked dev is reported instead of failing and the badge age isn't checked
+    pub fn query_dev(&self, dev_addr: [u8; 32]) -> Result<DevVerification> {
+        if self.is_revoked() {
+            return Ok(DevVerification {
+                dev_addr: Pubkey::from(dev_addr),
+                revoked: true,
+                ..Default::default()
+            });
+        }
+
+        Ok(DevVerification::from_dev_state(&self.load_dev_state()?))
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        !self.revocation.data_is_empty()
+    }
 
+    // Verifications wait for the dev state to settle in the new wallet, queries still report it
+    pub fn is_migrating(&self) -> bool {
+        migration_in_progress(&self.migration)
+    }
+
+    // Fails with StaleBadge once the badge is older than the max staleness in effect
+    pub fn require_fresh(
+        &self,
+        dev_state: &DevState,
+        max_staleness: Option<i64>,
+        time_now: i64,
+    ) -> Result<()> {
+        let max_staleness = match max_staleness {
+            Some(max_staleness) => max_staleness,
+            None => self.load_ghost_config()?.max_staleness,
+        };
+
+        if max_staleness > 0 {
+            require!(
+                time_now.saturating_sub(dev_state.last_updated) <= max_staleness,
+                GhostErrors::StaleBadge
+            );
+        }
         Ok(())
     }
+
+    pub fn load_ghost_config(&self) -> Result<GhostConfig> {
+        require_keys_eq!(
+            *self.ghost_config.owner,
+            crate::ID,
+            ErrorCode::AccountNotInitialized
+        );
+        let data = self.ghost_config.try_borrow_data()?;
+        GhostConfig::try_deserialize_compat(&data)
+    }
+
+    pub fn load_dev_state(&self) -> Result<DevState> {
+        require_keys_eq!(
+            *self.target_dev_state.owner,
+            crate::ID,
+            GhostErrors::DevBadgeNotFound
+        );
+        // Accounts not yet upgraded by migrate_account still verify
+        let data = self.target_dev_state.try_borrow_data()?;
+        DevState::try_deserialize_compat(&data)
+    }
 }
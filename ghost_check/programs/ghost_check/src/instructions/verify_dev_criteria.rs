@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GhostErrors;
+use crate::events::DevCriteriaVerified;
+use crate::state::DevState;
+use crate::VerifyDev;
+
+// Bits of the criteria bitmaps, one per Criteria field
+pub const CRITERIA_LEVEL: u8 = 1 << 0;
+pub const CRITERIA_PRS_MERGED: u8 = 1 << 1;
+pub const CRITERIA_STARS: u8 = 1 << 2;
+pub const CRITERIA_VOUCH_COUNT: u8 = 1 << 3;
+pub const CRITERIA_ACCOUNT_AGE: u8 = 1 << 4;
+pub const CRITERIA_VERIFIED_REPOS: u8 = 1 << 5;
+pub const CRITERIA_STALENESS: u8 = 1 << 6;
+
+// Eligibility requirements of an integrator, `None` fields are not checked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Criteria {
+    pub min_level: Option<u8>,
+    pub min_prs_merged: Option<u32>,
+    pub min_stars: Option<u32>,
+    pub min_vouch_count: Option<u64>,
+    pub min_account_age_days: Option<u32>,
+    pub min_verified_repos: Option<u64>,
+    pub max_staleness: Option<i64>, // Max seconds since the dev badge was last updated
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CriteriaResult {
+    pub dev_addr: Pubkey,
+    pub checked: u8, // Bits of the criteria that were set
+    pub passed: u8,  // Bits of the checked criteria the dev meets
+    pub revoked: bool,
+}
+
+impl CriteriaResult {
+    // Bits of the checked criteria the dev misses
+    pub fn failed(&self) -> u8 {
+        self.checked & !self.passed
+    }
+
+    pub fn met(&self) -> bool {
+        !self.revoked && self.failed() == 0
+    }
+}
+
+impl<'info> VerifyDev<'info> {
+    // Fails with CriteriaNotMet when any checked criterion fails, the failed bitmap
+    // is part of the error message so integrators can tell the dev why
+    pub fn verify_dev_criteria(&self, criteria: Criteria) -> Result<CriteriaResult> {
+        require!(!self.is_revoked(), GhostErrors::DevRevoked);
+        require!(!self.is_migrating(), GhostErrors::MigrationInProgress);
+        let dev_state = self.load_dev_state()?;
+
+        let time_now = Clock::get()?.unix_timestamp;
+        // A max_staleness criterion replaces the global one and is reported in the bitmap
+        if criteria.max_staleness.is_none() {
+            self.require_fresh(&dev_state, None, time_now)?;
+        }
+
+        let result = evaluate_criteria(&dev_state, &criteria, time_now);
+        // The failed bitmap only goes out in the error, a failed call keeps no return data or event
+        if result.failed() != 0 {
+            return Err(criteria_not_met(result.failed()));
+        }
+
+        emit!(DevCriteriaVerified {
+            dev: dev_state.dev_addr,
+            dev_state: self.target_dev_state.key(),
+            checked: result.checked,
+            timestamp: time_now,
+        });
+
+        Ok(result)
+    }
+
+    // Only reads, like query_dev: unmet criteria come back in the bitmaps instead of failing,
+    // a revoked dev is reported and the badge age is only checked through max_staleness
+    pub fn query_dev_criteria(
+        &self,
+        dev_addr: [u8; 32],
+        criteria: Criteria,
+    ) -> Result<CriteriaResult> {
+        if self.is_revoked() {
+            return Ok(CriteriaResult {
+                dev_addr: Pubkey::from(dev_addr),
+                checked: criteria.bits(),
+                revoked: true,
+                ..Default::default()
+            });
+        }
+
+        let time_now = Clock::get()?.unix_timestamp;
+        Ok(evaluate_criteria(
+            &self.load_dev_state()?,
+            &criteria,
+            time_now,
+        ))
+    }
+}
+
+impl Criteria {
+    // Bits of the criteria that are set
+    pub fn bits(&self) -> u8 {
+        [
+            (CRITERIA_LEVEL, self.min_level.is_some()),
+            (CRITERIA_PRS_MERGED, self.min_prs_merged.is_some()),
+            (CRITERIA_STARS, self.min_stars.is_some()),
+            (CRITERIA_VOUCH_COUNT, self.min_vouch_count.is_some()),
+            (CRITERIA_ACCOUNT_AGE, self.min_account_age_days.is_some()),
+            (CRITERIA_VERIFIED_REPOS, self.min_verified_repos.is_some()),
+            (CRITERIA_STALENESS, self.max_staleness.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .fold(0, |bits, (bit, _)| bits | bit)
+    }
+}
+
+fn evaluate_criteria(dev_state: &DevState, criteria: &Criteria, time_now: i64) -> CriteriaResult {
+    let mut checked = 0;
+    let mut passed = 0;
+    let mut check = |bit: u8, pass: Option<bool>| {
+        if let Some(pass) = pass {
+            checked |= bit;
+            if pass {
+                passed |= bit;
+            }
+        }
+    };
+    check(
+        CRITERIA_LEVEL,
+        criteria
+            .min_level
+            .map(|min| dev_state.reputation_level >= min),
+    );
+    check(
+        CRITERIA_PRS_MERGED,
+        criteria
+            .min_prs_merged
+            .map(|min| dev_state.prs_merged >= min),
+    );
+    check(
+        CRITERIA_STARS,
+        criteria.min_stars.map(|min| dev_state.total_stars >= min),
+    );
+    check(
+        CRITERIA_VOUCH_COUNT,
+        criteria
+            .min_vouch_count
+            .map(|min| dev_state.vouch_count >= min),
+    );
+    check(
+        CRITERIA_ACCOUNT_AGE,
+        criteria
+            .min_account_age_days
+            .map(|min| dev_state.account_age_days >= min),
+    );
+    check(
+        CRITERIA_VERIFIED_REPOS,
+        criteria
+            .min_verified_repos
+            .map(|min| dev_state.verified_repos >= min),
+    );
+    check(
+        CRITERIA_STALENESS,
+        criteria
+            .max_staleness
+            .map(|max| time_now.saturating_sub(dev_state.last_updated) <= max),
+    );
+
+    CriteriaResult {
+        dev_addr: dev_state.dev_addr,
+        checked,
+        passed,
+        revoked: false,
+    }
+}
+
+fn criteria_not_met(failed: u8) -> Error {
+    match Error::from(GhostErrors::CriteriaNotMet) {
+        Error::AnchorError(mut error) => {
+            error.error_msg = format!("{} (failed bitmap: {:#09b})", error.error_msg, failed);
+            Error::AnchorError(error)
+        }
+        error => error,
+    }
+}
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
 
 use crate::{
     errors::GhostErrors,
-    state::{DevState, GhostConfig, VouchRecord},
+    events::DevVouched,
+    state::{DevState, GhostConfig, Versioned, VouchRecord},
+    sync_dev_badge_attributes,
 };
 
 #[derive(Accounts)]
@@ -15,14 +18,16 @@ pub struct Vouch<'info> {
         mut,
         seeds= [b"ghost_config"],
         bump,
+        constraint = !ghost_config.paused @GhostErrors::ProtocolPaused,
     )]
     pub ghost_config: Account<'info, GhostConfig>,
 
     #[account(
+        mut,
         seeds = [b"dev_state", voucher.key().as_ref()],
         bump,
         constraint = voucher_dev_state.dev_addr == voucher.key() @GhostErrors::IncorrectDevState,
-        constraint = voucher_dev_state.reputation_level >= 2 @GhostErrors::LvlNotReached,
+        constraint = voucher_dev_state.reputation_level >= ghost_config.min_voucher_level @GhostErrors::LvlNotReached,
     )]
     pub voucher_dev_state: Account<'info, DevState>,
 
@@ -33,6 +38,15 @@ pub struct Vouch<'info> {
     )]
     pub target_dev_state: Account<'info, DevState>,
 
+    /// CHECK: Target dev collection, its vouch_count attribute is refreshed by the core program
+    #[account(
+        mut,
+        seeds = [b"dev_badge", &target_addr],
+        bump = target_dev_state.collection_asset_bump,
+        address = target_dev_state.asset_address,
+    )]
+    pub target_dev_badge: UncheckedAccount<'info>,
+
     #[account(
         init,
         payer = voucher,
@@ -42,6 +56,34 @@ pub struct Vouch<'info> {
     )]
     pub vouch_record: Account<'info, VouchRecord>,
 
+    /// CHECK: Only has data when the voucher already revoked a vouch for this dev
+    #[account(
+        seeds = [b"vouch_tombstone", voucher.key().as_ref(), &target_addr],
+        bump,
+        constraint = vouch_tombstone.data_is_empty() @GhostErrors::VouchRevoked,
+    )]
+    pub vouch_tombstone: UncheckedAccount<'info>,
+
+    /// CHECK: Only has data while the voucher is moving to another wallet, its records are
+    /// being forwarded and a new one would be left behind
+    #[account(
+        seeds = [b"migration", voucher.key().as_ref()],
+        bump,
+        constraint = voucher_migration.data_is_empty() @GhostErrors::MigrationPending,
+    )]
+    pub voucher_migration: UncheckedAccount<'info>,
+
+    /// CHECK: Only has data while the target is moving to another wallet
+    #[account(
+        seeds = [b"migration", &target_addr],
+        bump,
+        constraint = target_migration.data_is_empty() @GhostErrors::MigrationPending,
+    )]
+    pub target_migration: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Core Program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -51,21 +93,55 @@ impl<'info> Vouch<'info> {
             self.voucher.key() != Pubkey::from(target_addr),
             GhostErrors::SelfVouchDenied
         );
+        // Same GitHub identity behind two wallets is still a self vouch
+        require!(
+            self.voucher_dev_state.hashed_username != self.target_dev_state.hashed_username,
+            GhostErrors::SelfVouchDenied
+        );
+
+        let voucher_level = self.voucher_dev_state.reputation_level;
+        require!(
+            self.voucher_dev_state.vouches_given
+                < self.ghost_config.vouch_budget_for(voucher_level) as u32,
+            GhostErrors::VouchBudgetExhausted
+        );
 
         let time_now = Clock::get()?.unix_timestamp;
 
         // Create vouch record (PDA prevents duplicate vouches)
         self.vouch_record.set_inner(VouchRecord {
             voucher: self.voucher.key(),
-            voucher_level: self.voucher_dev_state.reputation_level,
+            voucher_level,
             target: Pubkey::from(target_addr),
             timestamp: time_now,
             bump: bumps.vouch_record,
+            version: VouchRecord::VERSION,
         });
 
         // Update config and vouched dev state
         self.ghost_config.vouches_count += 1;
         self.target_dev_state.vouch_count += 1;
+        self.target_dev_state.vouch_score += voucher_level as u64;
+        self.voucher_dev_state.vouches_given += 1;
+
+        sync_dev_badge_attributes(
+            &self.target_dev_badge,
+            &self.ghost_config,
+            &self.voucher,
+            &self.core_program,
+            &self.system_program,
+            &self.target_dev_state,
+        )?;
+
+        emit!(DevVouched {
+            voucher: self.voucher.key(),
+            target: Pubkey::from(target_addr),
+            vouch_record: self.vouch_record.key(),
+            voucher_level,
+            target_vouch_count: self.target_dev_state.vouch_count,
+            target_vouch_score: self.target_dev_state.vouch_score,
+            timestamp: time_now,
+        });
 
         Ok(())
     }
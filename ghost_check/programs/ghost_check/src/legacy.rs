@@ -0,0 +1,169 @@
+// Account layouts deployed before the version byte existed, only read by
+// Versioned::try_deserialize_compat. Layouts in between were never deployed, a stored account
+// either uses one of these or the current layout
+use anchor_lang::prelude::*;
+
+use crate::state::{DevState, GhostConfig, RepoState, Versioned, VouchRecord};
+
+// The baseline accepted attestations of any age, upgraded configs start with this window
+// until the admin sets their own through update_config
+const UPGRADED_MAX_ATTESTATION_AGE: i64 = 5 * 60;
+
+// Level the baseline required to vouch
+const BASELINE_MIN_VOUCHER_LEVEL: u8 = 2;
+
+#[derive(AnchorDeserialize)]
+pub struct GhostConfigV0 {
+    pub admin: Pubkey,
+    pub backend_pubkey: [u8; 32],
+    pub dev_badges_minted: u64,
+    pub repo_badges_minted: u32,
+    pub vouches_count: u32,
+    pub bump: u8,
+}
+
+impl From<GhostConfigV0> for GhostConfig {
+    fn from(v0: GhostConfigV0) -> Self {
+        GhostConfig {
+            admin: v0.admin,
+            pending_admin: None,
+            paused: false,
+            backend_pubkey: v0.backend_pubkey,
+            backend_key_id: 0,
+            previous_backend_pubkey: None,
+            previous_key_expiry_slot: 0,
+            dev_badges_minted: v0.dev_badges_minted,
+            repo_badges_minted: v0.repo_badges_minted,
+            vouches_count: v0.vouches_count,
+            // No budget on active vouches, like the baseline
+            min_voucher_level: BASELINE_MIN_VOUCHER_LEVEL,
+            vouch_budget: [u16::MAX; 5],
+            max_attestation_age: UPGRADED_MAX_ATTESTATION_AGE,
+            oracles: vec![],
+            oracle_threshold: 1,
+            bump: v0.bump,
+            version: GhostConfig::VERSION,
+            metadata_base_uri: String::new(),
+            min_update_interval: 0,
+            max_staleness: 0,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct DevStateV0 {
+    pub dev_addr: Pubkey,
+    pub asset_address: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub repo_count: u32,
+    pub owned_repo_count: u32,
+    pub total_stars: u32,
+    pub total_commits: u32,
+    pub prs_merged: u32,
+    pub issues_closed: u32,
+    pub followers: u32,
+    pub account_age_days: u32,
+    pub reputation_level: u8,
+    pub verified_repos: u64,
+    pub vouch_count: u64,
+    pub last_updated: i64,
+    pub bump: u8,
+    pub collection_asset_bump: u8,
+}
+
+impl From<DevStateV0> for DevState {
+    fn from(v0: DevStateV0) -> Self {
+        DevState {
+            dev_addr: v0.dev_addr,
+            asset_address: v0.asset_address,
+            hashed_username: v0.hashed_username,
+            repo_count: v0.repo_count,
+            owned_repo_count: v0.owned_repo_count,
+            total_stars: v0.total_stars,
+            total_commits: v0.total_commits,
+            prs_merged: v0.prs_merged,
+            issues_closed: v0.issues_closed,
+            followers: v0.followers,
+            account_age_days: v0.account_age_days,
+            reputation_level: v0.reputation_level,
+            verified_repos: v0.verified_repos,
+            vouch_count: v0.vouch_count,
+            // Baseline vouches weren't weighted, each one counts with the lowest level that could
+            // vouch. The vouch records keep the real levels, removing one saturates at 0
+            vouch_score: v0
+                .vouch_count
+                .saturating_mul(BASELINE_MIN_VOUCHER_LEVEL as u64),
+            vouches_given: DevState::UNCOUNTED_VOUCHES_GIVEN,
+            last_updated: v0.last_updated,
+            nonce: 0,
+            bump: v0.bump,
+            collection_asset_bump: v0.collection_asset_bump,
+            version: DevState::VERSION,
+            vouch_tombstones: 0,
+            contribution_badges: 0,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct RepoStateV0 {
+    pub owner: Pubkey,
+    pub dev_badge: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub repo_name: Vec<u8>,
+    pub stars: u32,
+    pub commits: u32,
+    pub forks: u32,
+    pub open_issues: u32,
+    pub is_fork: u8,
+    pub lang1: Vec<u8>,
+    pub lang2: Vec<u8>,
+    pub last_updated: i64,
+    pub bump: u8,
+    pub badge_bump: u8,
+}
+
+impl From<RepoStateV0> for RepoState {
+    fn from(v0: RepoStateV0) -> Self {
+        RepoState {
+            owner: v0.owner,
+            dev_badge: v0.dev_badge,
+            hashed_username: v0.hashed_username,
+            repo_name: v0.repo_name,
+            stars: v0.stars,
+            commits: v0.commits,
+            forks: v0.forks,
+            open_issues: v0.open_issues,
+            is_fork: v0.is_fork,
+            lang1: v0.lang1,
+            lang2: v0.lang2,
+            last_updated: v0.last_updated,
+            nonce: 0,
+            bump: v0.bump,
+            badge_bump: v0.badge_bump,
+            version: RepoState::VERSION,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+pub struct VouchRecordV0 {
+    pub voucher: Pubkey,
+    pub voucher_level: u8,
+    pub target: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl From<VouchRecordV0> for VouchRecord {
+    fn from(v0: VouchRecordV0) -> Self {
+        VouchRecord {
+            voucher: v0.voucher,
+            voucher_level: v0.voucher_level,
+            target: v0.target,
+            timestamp: v0.timestamp,
+            bump: v0.bump,
+            version: VouchRecord::VERSION,
+        }
+    }
+}
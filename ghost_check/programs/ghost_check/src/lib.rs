@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 
 pub mod instructions;
@@ -5,20 +7,41 @@ pub use instructions::*;
 
 pub mod errors;
 
+pub mod events;
+
 pub mod state;
 
+pub mod legacy;
+
+#[cfg(feature = "cpi")]
+pub mod cpi_helpers;
+
 declare_id!("GQsPhnZApw9MY7khsbRLtL5mAGpmMn8wp8CFNDPTxGQr");
 
 #[program]
 pub mod ghost_check {
     use super::*;
 
-    pub fn init_config(ctx: Context<InitConfig>, backend_pubkey: [u8; 32]) -> Result<()> {
-        ctx.accounts.init_config(backend_pubkey, &ctx.bumps)
+    pub fn init_config(
+        ctx: Context<InitConfig>,
+        backend_pubkey: [u8; 32],
+        max_attestation_age: i64,
+        min_voucher_level: u8,
+        vouch_budget: [u16; 5],
+        metadata_base_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.init_config(
+            backend_pubkey,
+            max_attestation_age,
+            min_voucher_level,
+            vouch_budget,
+            metadata_base_uri,
+            &ctx.bumps,
+        )
     }
 
     pub fn mint_dev_badge(
-        ctx: Context<DevBadge>, // 73 bytes instruction data size
+        ctx: Context<DevBadge>, // 89 bytes instruction data size
         username: [u8; 32],
         repo_count: u32,
         owned_repo_count: u32,
@@ -29,6 +52,8 @@ pub mod ghost_check {
         followers: u32,
         account_age_days: u32,
         reputation_level: u8,
+        issued_at: i64,
+        nonce: u64,
     ) -> Result<()> {
         ctx.accounts.mint_collection(
             &username,
@@ -41,6 +66,8 @@ pub mod ghost_check {
             followers,
             account_age_days,
             reputation_level,
+            issued_at,
+            nonce,
             &ctx.bumps,
         )
     }
@@ -56,6 +83,8 @@ pub mod ghost_check {
         is_fork: u8,
         lang1: Vec<u8>,
         lang2: Vec<u8>,
+        issued_at: i64,
+        nonce: u64,
     ) -> Result<()> {
         ctx.accounts.mint_repo_badge(
             repo_name_padded,
@@ -67,6 +96,8 @@ pub mod ghost_check {
             is_fork,
             lang1,
             lang2,
+            issued_at,
+            nonce,
             &ctx.bumps,
         )
     }
@@ -83,6 +114,8 @@ pub mod ghost_check {
         followers: u32,
         account_age_days: u32,
         reputation_level: u8,
+        issued_at: i64,
+        nonce: u64,
     ) -> Result<()> {
         ctx.accounts.update_dev_badge(
             &username,
@@ -95,6 +128,8 @@ pub mod ghost_check {
             followers,
             account_age_days,
             reputation_level,
+            issued_at,
+            nonce,
         )
     }
 
@@ -108,6 +143,8 @@ pub mod ghost_check {
         open_issues: u32,
         lang1: Vec<u8>,
         lang2: Vec<u8>,
+        issued_at: i64,
+        nonce: u64,
     ) -> Result<()> {
         ctx.accounts.update_repo_badge(
             repo_name_padded,
@@ -118,14 +155,270 @@ pub mod ghost_check {
             open_issues,
             lang1,
             lang2,
+            issued_at,
+            nonce,
         )
     }
 
-    pub fn verify_dev(ctx: Context<VerifyDev>, dev_addr: [u8; 32], min_lvl: u8) -> Result<()> {
-        ctx.accounts.verify_dev(dev_addr, min_lvl)
+    pub fn verify_dev(
+        ctx: Context<VerifyDev>,
+        dev_addr: [u8; 32],
+        min_lvl: u8,
+        max_staleness: Option<i64>,
+    ) -> Result<DevVerification> {
+        ctx.accounts.verify_dev(dev_addr, min_lvl, max_staleness)
+    }
+
+    pub fn query_dev(ctx: Context<VerifyDev>, dev_addr: [u8; 32]) -> Result<DevVerification> {
+        ctx.accounts.query_dev(dev_addr)
+    }
+
+    // Shares verify_dev's accounts, `_dev_addr` only derives their seeds
+    pub fn verify_dev_criteria(
+        ctx: Context<VerifyDev>,
+        _dev_addr: [u8; 32],
+        criteria: Criteria,
+    ) -> Result<CriteriaResult> {
+        ctx.accounts.verify_dev_criteria(criteria)
+    }
+
+    pub fn query_dev_criteria(
+        ctx: Context<VerifyDev>,
+        dev_addr: [u8; 32],
+        criteria: Criteria,
+    ) -> Result<CriteriaResult> {
+        ctx.accounts.query_dev_criteria(dev_addr, criteria)
     }
 
     pub fn vouch_for_dev(ctx: Context<Vouch>, target_addr: [u8; 32]) -> Result<()> {
         ctx.accounts.vouch_for_dev(target_addr, &ctx.bumps)
     }
+
+    pub fn revoke_vouch(ctx: Context<RevokeVouch>, target_addr: [u8; 32]) -> Result<()> {
+        ctx.accounts.revoke_vouch(target_addr, &ctx.bumps)
+    }
+
+    pub fn rotate_backend_key(
+        ctx: Context<RotateBackendKey>,
+        new_backend_pubkey: [u8; 32],
+        new_key_id: u32,
+        grace_slots: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .rotate_backend_key(new_backend_pubkey, new_key_id, grace_slots)
+    }
+
+    pub fn set_oracles(
+        ctx: Context<SetOracles>,
+        oracles: Vec<[u8; 32]>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts.set_oracles(oracles, threshold)
+    }
+
+    pub fn release_identity(
+        ctx: Context<ReleaseIdentity>,
+        username: [u8; 32],
+        issued_at: i64,
+    ) -> Result<()> {
+        ctx.accounts.release_identity(username, issued_at)
+    }
+
+    pub fn revoke_dev_badge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevokeDevBadge<'info>>,
+        dev_addr: [u8; 32],
+        reason: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .revoke_dev_badge(dev_addr, reason, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn prune_vouch(
+        ctx: Context<PruneVouch>,
+        voucher_addr: [u8; 32],
+        target_addr: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.prune_vouch(voucher_addr, target_addr)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        max_attestation_age: Option<i64>,
+        min_voucher_level: Option<u8>,
+        vouch_budget: Option<[u16; 5]>,
+        metadata_base_uri: Option<String>,
+        min_update_interval: Option<i64>,
+        max_staleness: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts.update_config(
+            max_attestation_age,
+            min_voucher_level,
+            vouch_budget,
+            metadata_base_uri,
+            min_update_interval,
+            max_staleness,
+        )
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.set_paused(paused)
+    }
+
+    pub fn burn_repo_badge(ctx: Context<BurnRepoBadge>, repo_name_padded: [u8; 32]) -> Result<()> {
+        ctx.accounts.burn_repo_badge(repo_name_padded)
+    }
+
+    pub fn burn_dev_badge(ctx: Context<BurnDevBadge>) -> Result<()> {
+        ctx.accounts.burn_dev_badge()
+    }
+
+    pub fn request_migration(
+        ctx: Context<RequestMigration>,
+        username: [u8; 32],
+        issued_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .request_migration(username, issued_at, &ctx.bumps)
+    }
+
+    pub fn approve_migration(ctx: Context<ApproveMigration>) -> Result<()> {
+        ctx.accounts.approve_migration()
+    }
+
+    pub fn cancel_migration(ctx: Context<CancelMigration>) -> Result<()> {
+        ctx.accounts.cancel_migration()
+    }
+
+    pub fn migrate_dev_identity(ctx: Context<MigrateDevIdentity>) -> Result<()> {
+        ctx.accounts.migrate_dev_identity(&ctx.bumps)
+    }
+
+    pub fn migrate_repo_badge(
+        ctx: Context<MigrateRepoBadge>,
+        repo_name_padded: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_repo_badge(repo_name_padded, &ctx.bumps)
+    }
+
+    pub fn migrate_contribution_badge(
+        ctx: Context<MigrateContributionBadge>,
+        hashed_repo: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_contribution_badge(hashed_repo, &ctx.bumps)
+    }
+
+    pub fn migrate_vouch_record(
+        ctx: Context<MigrateVouchRecord>,
+        voucher: Pubkey,
+        target: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_vouch_record(voucher, target, &ctx.bumps)
+    }
+
+    pub fn migrate_vouch_tombstone(
+        ctx: Context<MigrateVouchTombstone>,
+        voucher: Pubkey,
+        target: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_vouch_tombstone(voucher, target, &ctx.bumps)
+    }
+
+    pub fn finish_migration(ctx: Context<FinishMigration>) -> Result<()> {
+        ctx.accounts.finish_migration()
+    }
+
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate_account()
+    }
+
+    pub fn mint_org_badge(
+        ctx: Context<OrgBadge>,
+        hashed_org: [u8; 32],
+        public_repos: u32,
+        total_stars: u32,
+        member_count: u32,
+        issued_at: i64,
+        nonce: u64,
+    ) -> Result<()> {
+        ctx.accounts.mint_org_badge(
+            hashed_org,
+            public_repos,
+            total_stars,
+            member_count,
+            issued_at,
+            nonce,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn link_org_member(ctx: Context<LinkOrgMember>) -> Result<()> {
+        ctx.accounts.link_org_member(&ctx.bumps)
+    }
+
+    pub fn mint_contribution_badge(
+        ctx: Context<ContributionBadge>,
+        hashed_repo: [u8; 32],
+        repo_full_name: Vec<u8>,
+        username_padded: [u8; 32],
+        prs_merged: u32,
+        commits: u32,
+        issued_at: i64,
+        nonce: u64,
+    ) -> Result<()> {
+        ctx.accounts.mint_contribution_badge(
+            hashed_repo,
+            repo_full_name,
+            username_padded,
+            prs_merged,
+            commits,
+            issued_at,
+            nonce,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn burn_contribution_badge(
+        ctx: Context<BurnContributionBadge>,
+        hashed_repo: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.burn_contribution_badge(hashed_repo)
+    }
+
+    pub fn init_scoring_params(
+        ctx: Context<InitScoringParams>,
+        tiers: [state::ScoringTier; state::SCORING_TIERS],
+    ) -> Result<()> {
+        ctx.accounts.init_scoring_params(tiers, &ctx.bumps)
+    }
+
+    pub fn update_scoring_params(
+        ctx: Context<UpdateScoringParams>,
+        tiers: [state::ScoringTier; state::SCORING_TIERS],
+    ) -> Result<()> {
+        ctx.accounts.update_scoring_params(tiers)
+    }
+
+    pub fn settle_vouches_given(
+        ctx: Context<SettleVouchesGiven>,
+        _dev_addr: [u8; 32],
+        vouches_given: u32,
+    ) -> Result<()> {
+        ctx.accounts.settle_vouches_given(vouches_given)
+    }
+
+    pub fn migrate_org_membership(ctx: Context<MigrateOrgMembership>) -> Result<()> {
+        ctx.accounts.migrate_org_membership(&ctx.bumps)
+    }
 }
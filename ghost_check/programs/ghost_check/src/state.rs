@@ -1,15 +1,191 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::GhostErrors, legacy};
+
+// Max independent oracles that can co-sign attestations next to the backend key
+pub const MAX_ORACLES: usize = 10;
+
+// Longest metadata base URI, leaves room for "/metadata/contribution/<asset>" in Core's 200 byte URI
+pub const MAX_METADATA_BASE_URI_LEN: usize = 128;
+
+// Longest "owner/repo" GitHub allows: 39 byte owner, '/', 100 byte repo
+pub const MAX_REPO_FULL_NAME_LEN: usize = 140;
+
+// Reputation levels above 1 (Ghost), each with its own thresholds in ScoringParams
+pub const SCORING_TIERS: usize = 4;
+
+// Seconds a wallet migration waits when the old wallet doesn't co-sign it
+pub const MIGRATION_TIMELOCK: i64 = 7 * 24 * 60 * 60;
+
+// Accounts whose layout carries a version byte. Adding a field means bumping VERSION,
+// pointing Legacy at the last deployed layout and converting it; migrate_account then upgrades
+// stored accounts in place and read-only paths use try_deserialize_compat meanwhile
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator + Space {
+    const VERSION: u8;
+
+    // Last deployed layout before VERSION
+    type Legacy: AnchorDeserialize + Into<Self>;
+
+    fn version(&self) -> u8;
+
+    // Version of the stored bytes, legacy layouts either don't parse or read 0 from the padding
+    fn stored_version(buf: &[u8]) -> u8 {
+        Self::try_deserialize(&mut &buf[..]).map_or(0, |account| account.version())
+    }
+
+    // Reads the current layout or upgrades a legacy one in memory
+    fn try_deserialize_compat(buf: &[u8]) -> Result<Self> {
+        if Self::stored_version(buf) == Self::VERSION {
+            return Self::try_deserialize(&mut &buf[..]);
+        }
+
+        require!(
+            buf.starts_with(Self::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let legacy = Self::Legacy::deserialize(&mut &buf[Self::DISCRIMINATOR.len()..])
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+
+        Ok(legacy.into())
+    }
+}
+
 // Program State
 #[derive(InitSpace)]
 #[account]
 pub struct GhostConfig {
     pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // Proposed by the admin, becomes admin once it accepts
+    pub paused: bool,                  // Blocks every mint, update and vouch instruction
     pub backend_pubkey: [u8; 32],
+    pub backend_key_id: u32, // Id of backend_pubkey, matches GhostCheck_Signer_Key_Id on the backend
+    pub previous_backend_pubkey: Option<[u8; 32]>, // Still accepted until previous_key_expiry_slot
+    pub previous_key_expiry_slot: u64,
     pub dev_badges_minted: u64,
     pub repo_badges_minted: u32, // Track total repos badges minte by the protocol for devs
     pub vouches_count: u32,      // Track total vouches in the protocol scanned
+    pub min_voucher_level: u8,   // Reputation level required to vouch for other devs
+    pub vouch_budget: [u16; 5],  // Active vouches allowed per voucher, indexed by level - 1
+    pub max_attestation_age: i64, // Seconds a backend attestation stays valid after issued_at
+    #[max_len(MAX_ORACLES)]
+    pub oracles: Vec<[u8; 32]>, // Co-signing oracle keys, the backend key is an implicit member
+    pub oracle_threshold: u8,    // Distinct signers (backend + oracles) required per attestation
+    pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+    #[max_len(MAX_METADATA_BASE_URI_LEN)]
+    pub metadata_base_uri: String, // Badge URIs are <base>/metadata/<kind>/<asset>
+    pub min_update_interval: i64, // Seconds between two updates of the same badge, 0 = no cooldown
+    pub max_staleness: i64, // Seconds since last_updated past which verify_dev fails, 0 = any age
+}
+
+impl GhostConfig {
+    // Backend keys whose attestations are accepted at `slot`
+    pub fn is_backend_key(&self, pubkey: &[u8; 32], slot: u64) -> bool {
+        if pubkey == &self.backend_pubkey {
+            return true;
+        }
+
+        self.previous_backend_pubkey.as_ref() == Some(pubkey)
+            && slot <= self.previous_key_expiry_slot
+    }
+
+    // Previous backend key still accepted, its grace window hasn't ended at `slot`
+    pub fn in_key_grace_period(&self, slot: u64) -> bool {
+        self.previous_backend_pubkey.is_some() && slot <= self.previous_key_expiry_slot
+    }
+
+    // Seat of an accepted signer in the oracle set: 0 for the backend (current or previous key,
+    // counted once), 1.. for the configured oracles
+    pub fn oracle_seat(&self, pubkey: &[u8; 32], slot: u64) -> Option<usize> {
+        if self.is_backend_key(pubkey, slot) {
+            return Some(0);
+        }
+
+        self.oracles
+            .iter()
+            .position(|oracle| oracle == pubkey)
+            .map(|i| i + 1)
+    }
+
+    // Off-chain JSON of a badge asset, `kind` is "dev", "repo", "contribution", "org" or "org_member".
+    // Configs upgraded from the baseline have no base URI until the admin sets one
+    pub fn metadata_uri(&self, kind: &str, asset: &Pubkey) -> Result<String> {
+        require!(
+            !self.metadata_base_uri.is_empty(),
+            GhostErrors::MetadataUriNotSet
+        );
+        Ok(format!(
+            "{}/metadata/{}/{}",
+            self.metadata_base_uri.trim_end_matches('/'),
+            kind,
+            asset
+        ))
+    }
+
+    // Active vouches a voucher of `level` may hold
+    pub fn vouch_budget_for(&self, level: u8) -> u16 {
+        level
+            .checked_sub(1)
+            .and_then(|i| self.vouch_budget.get(i as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    // A higher level never gets fewer vouches, level 1 may get none
+    pub fn vouch_budget_is_valid(vouch_budget: &[u16; 5]) -> bool {
+        vouch_budget.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    // Whether a badge last updated at `last_updated` may be updated again at `now`
+    pub fn update_allowed(&self, last_updated: i64, now: i64) -> bool {
+        now >= last_updated.saturating_add(self.min_update_interval)
+    }
+}
+
+// Minimums a dev must meet, all of them, to reach a reputation level
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub struct ScoringTier {
+    pub min_repos: u32,
+    pub min_commits: u32,
+    pub min_account_age_days: u32,
+}
+
+// Scoring table behind reputation_level, public so verifiers can audit how levels are earned
+#[derive(InitSpace)]
+#[account]
+pub struct ScoringParams {
+    pub tiers: [ScoringTier; SCORING_TIERS], // Levels 2 (Coder) to 5 (Legend), level 1 needs nothing
+    pub updated_at: i64,
     pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+impl ScoringParams {
+    // Highest level whose tier the metrics meet
+    pub fn reputation_level(
+        &self,
+        repo_count: u32,
+        total_commits: u32,
+        account_age_days: u32,
+    ) -> u8 {
+        self.tiers
+            .iter()
+            .rposition(|tier| {
+                repo_count >= tier.min_repos
+                    && total_commits >= tier.min_commits
+                    && account_age_days >= tier.min_account_age_days
+            })
+            .map_or(1, |i| i as u8 + 2)
+    }
+
+    // A higher level can't ask less than the one below it
+    pub fn tiers_are_ordered(tiers: &[ScoringTier; SCORING_TIERS]) -> bool {
+        tiers.windows(2).all(|pair| {
+            pair[0].min_repos <= pair[1].min_repos
+                && pair[0].min_commits <= pair[1].min_commits
+                && pair[0].min_account_age_days <= pair[1].min_account_age_days
+        })
+    }
 }
 
 // Collections state / Dev_Badge
@@ -30,9 +206,25 @@ pub struct DevState {
     pub reputation_level: u8,
     pub verified_repos: u64,
     pub vouch_count: u64,
+    pub vouch_score: u64, // Sum of the voucher levels of every active vouch received
+    pub vouches_given: u32, // Active vouches given, capped by the config vouch budget, see UNCOUNTED_VOUCHES_GIVEN
     pub last_updated: i64,
+    pub nonce: u64, // Last attestation nonce consumed for this dev
     pub bump: u8,
     pub collection_asset_bump: u8,
+    pub version: u8,              // Layout version, new fields go after it
+    pub vouch_tombstones: u32,    // Tombstones left by or for this dev, forwarded when it migrates
+    pub contribution_badges: u32, // Contribution badges in the dev collection
+}
+
+impl DevState {
+    // Baseline dev states didn't count the vouches they gave. Above every u16 budget, so the dev
+    // can't vouch again until the admin sets the real count with settle_vouches_given
+    pub const UNCOUNTED_VOUCHES_GIVEN: u32 = u32::MAX;
+
+    pub fn vouches_given_counted(&self) -> bool {
+        self.vouches_given <= u16::MAX as u32
+    }
 }
 
 // Assets state / Repo_badge
@@ -43,7 +235,7 @@ pub struct RepoState {
     pub dev_badge: Pubkey,
     pub hashed_username: [u8; 32],
     #[max_len(50)]
-    pub repo_name: Vec<u8>,
+    pub repo_name: Vec<u8>, // Trimmed, accounts minted before it was trimmed hold the padded seed
     pub stars: u32,
     pub commits: u32,
     pub forks: u32,
@@ -54,8 +246,96 @@ pub struct RepoState {
     #[max_len(10)]
     pub lang2: Vec<u8>,
     pub last_updated: i64,
+    pub nonce: u64, // Last attestation nonce consumed for this repo
+    pub bump: u8,
+    pub badge_bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+impl RepoState {
+    // The 32 byte repo name seed of the repo_state and repo_badge PDAs
+    pub fn padded_name(&self) -> [u8; 32] {
+        let mut padded = [0u8; 32];
+        let len = self.repo_name.len().min(32);
+        padded[..len].copy_from_slice(&self.repo_name[..len]);
+        padded
+    }
+}
+
+// Contribution badge, work merged into a repo owned by someone else
+#[derive(InitSpace)]
+#[account]
+pub struct ContributionState {
+    pub contributor: Pubkey,
+    pub dev_badge: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub hashed_repo: [u8; 32], // sha256 of the lowercased "owner/repo", seed of the PDAs
+    #[max_len(MAX_REPO_FULL_NAME_LEN)]
+    pub repo_full_name: Vec<u8>, // "owner/repo" as GitHub spells it
+    pub prs_merged: u32,       // PRs by the dev merged into the repo
+    pub commits: u32,          // Commits by the dev on the default branch
+    pub last_updated: i64,
+    pub nonce: u64,
     pub bump: u8,
     pub badge_bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+// One GitHub identity -> one wallet
+#[derive(InitSpace)]
+#[account]
+pub struct IdentityClaim {
+    pub hashed_username: [u8; 32],
+    pub wallet: Pubkey, // Wallet whose dev badge holds this identity
+    pub claimed_at: i64,
+    pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+// Org_Badge collection state, one per GitHub organization
+#[derive(InitSpace)]
+#[account]
+pub struct OrgState {
+    pub authority: Pubkey, // Wallet of the org admin that minted the badge, approves member links
+    pub org_badge: Pubkey,
+    pub hashed_org: [u8; 32], // sha256 of the org login
+    pub public_repos: u32,
+    pub total_stars: u32, // Stars across the org's public repos
+    pub member_count: u32,
+    pub linked_members: u32, // Dev badges linked with link_org_member
+    pub last_updated: i64,
+    pub nonce: u64,
+    pub bump: u8,
+    pub badge_bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+// A dev badge linked to an org, its member badge lives in the Org_Badge collection
+#[derive(InitSpace)]
+#[account]
+pub struct OrgMembership {
+    pub org_badge: Pubkey,
+    pub dev: Pubkey,
+    pub dev_badge: Pubkey,
+    pub member_badge: Pubkey,
+    pub linked_at: i64,
+    pub bump: u8,
+    pub member_badge_bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+// Left behind by revoke_dev_badge so verifiers can tell a revoked dev from an unknown one
+#[derive(InitSpace)]
+#[account]
+pub struct Revocation {
+    pub dev_addr: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub dev_badge: Pubkey,  // Burned collection asset
+    pub reason: u8,         // Reason code chosen by the admin (e.g. 1 = fraud, 2 = sybil)
+    pub revoked_by: Pubkey, // Admin that revoked the badge
+    pub timestamp: i64,
+    pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
 }
 
 #[derive(InitSpace)]
@@ -66,4 +346,170 @@ pub struct VouchRecord {
     pub target: Pubkey,    // Dev the voucher is vouching for
     pub timestamp: i64,    // timestamp
     pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+// Left behind by revoke_vouch, a voucher can't vouch for the same dev again
+#[derive(InitSpace)]
+#[account]
+pub struct VouchTombstone {
+    pub voucher: Pubkey,
+    pub target: Pubkey,
+    pub revoked_at: i64,
+    pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+// Moves a dev identity from old_wallet to new_wallet in steps: request (backend attestation),
+// approve by old_wallet or wait for the timelock, migrate the dev badge, each repo and
+// contribution badge, vouch record and vouch tombstone, then finish. Org memberships are moved
+// with migrate_org_membership, before or after finishing. Vouches to or from old_wallet are
+// rejected while this exists, badges of old_wallet can't be minted, updated or verified until
+// the migration is completed
+#[derive(InitSpace)]
+#[account]
+pub struct MigrationRequest {
+    pub old_wallet: Pubkey,
+    pub new_wallet: Pubkey,
+    pub hashed_username: [u8; 32],
+    pub requested_at: i64,
+    pub unlock_at: i64, // Earliest time the migration can start without approval
+    pub approved: bool, // old_wallet co-signed, the timelock no longer applies
+    pub dev_migrated: bool, // DevState and Dev_Badge exist for new_wallet
+    pub completed: bool, // Old DevState and Dev_Badge are gone
+    pub bump: u8,
+    pub version: u8, // Layout version, new fields go after it
+}
+
+impl MigrationRequest {
+    pub fn is_unlocked(&self, time_now: i64) -> bool {
+        self.approved || time_now >= self.unlock_at
+    }
+
+    // Address a record of `wallet` lives under after the migration
+    pub fn forward(&self, wallet: &Pubkey) -> Pubkey {
+        if wallet == &self.old_wallet {
+            self.new_wallet
+        } else {
+            *wallet
+        }
+    }
+
+    // Other side of a vouch between `voucher` and `target`, one of them is old_wallet
+    pub fn counterpart(&self, voucher: &Pubkey, target: &Pubkey) -> Pubkey {
+        if voucher == &self.old_wallet {
+            *target
+        } else {
+            *voucher
+        }
+    }
+}
+
+impl Versioned for GhostConfig {
+    const VERSION: u8 = 3;
+    type Legacy = legacy::GhostConfigV0;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for DevState {
+    const VERSION: u8 = 3;
+    type Legacy = legacy::DevStateV0;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for RepoState {
+    const VERSION: u8 = 1;
+    type Legacy = legacy::RepoStateV0;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for VouchRecord {
+    const VERSION: u8 = 1;
+    type Legacy = legacy::VouchRecordV0;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+// Accounts added after the version byte have no older layout, Legacy is the account itself
+impl Versioned for ScoringParams {
+    const VERSION: u8 = 1;
+    type Legacy = ScoringParams;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for ContributionState {
+    const VERSION: u8 = 1;
+    type Legacy = ContributionState;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for OrgState {
+    const VERSION: u8 = 1;
+    type Legacy = OrgState;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for OrgMembership {
+    const VERSION: u8 = 1;
+    type Legacy = OrgMembership;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for IdentityClaim {
+    const VERSION: u8 = 1;
+    type Legacy = IdentityClaim;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for Revocation {
+    const VERSION: u8 = 1;
+    type Legacy = Revocation;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for VouchTombstone {
+    const VERSION: u8 = 1;
+    type Legacy = VouchTombstone;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for MigrationRequest {
+    const VERSION: u8 = 1;
+    type Legacy = MigrationRequest;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
 }
@@ -0,0 +1,1059 @@
+// Shared setup of the Rust integration tests: the SBF build of the program runs next to the
+// Metaplex Core fixture in an in-process bank, nothing talks to a validator or the network.
+// The suites only compile with the test-sbf feature, `cargo test-sbf` enables it
+#![allow(dead_code)]
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+use anchor_lang::{
+    solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID, system_program,
+    AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use ghost_check::{
+    contribution_badge_message, hash_repo_full_name, org_badge_message,
+    state::{ScoringTier, SCORING_TIERS},
+};
+use solana_loader_v3_interface::{get_program_data_address, state::UpgradeableLoaderState};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::Clock,
+    ed25519_instruction::new_ed25519_instruction_with_signature,
+    hash::hash,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use solana_sdk_ids::{bpf_loader, bpf_loader_upgradeable};
+
+// Same fixture Anchor.toml loads into the local validator
+const MPL_CORE_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mpl_core.so");
+
+pub const MAX_ATTESTATION_AGE: i64 = 300;
+pub const MIN_VOUCHER_LEVEL: u8 = 2;
+pub const VOUCH_BUDGET: [u16; 5] = [0, 3, 5, 10, 20];
+pub const METADATA_BASE_URI: &str = "http://localhost:3000";
+pub const ORG: &str = "ghost-org";
+
+// Same table as the TypeScript suite
+pub const TIERS: [ScoringTier; SCORING_TIERS] = [
+    tier(5, 30, 0),
+    tier(20, 200, 120),
+    tier(50, 500, 365),
+    tier(100, 1500, 730),
+];
+
+const fn tier(min_repos: u32, min_commits: u32, min_account_age_days: u32) -> ScoringTier {
+    ScoringTier {
+        min_repos,
+        min_commits,
+        min_account_age_days,
+    }
+}
+
+// `cargo test-sbf` points SBF_OUT_DIR at its own build. `cargo test --features test-sbf` builds the
+// program into target/deploy once per test binary, the same place `anchor build` writes to
+fn program_so() -> PathBuf {
+    static PROGRAM_SO: OnceLock<PathBuf> = OnceLock::new();
+    PROGRAM_SO
+        .get_or_init(|| {
+            if let Ok(dir) = std::env::var("SBF_OUT_DIR").or_else(|_| std::env::var("BPF_OUT_DIR"))
+            {
+                return PathBuf::from(dir).join("ghost_check.so");
+            }
+            let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy");
+            build_sbf(&dir);
+            dir.join("ghost_check.so")
+        })
+        .clone()
+}
+
+fn build_sbf(out_dir: &Path) {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let status = Command::new(cargo)
+        .args(["build-sbf", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .arg("--sbf-out-dir")
+        .arg(out_dir)
+        .status()
+        .unwrap_or_else(|e| panic!("cargo build-sbf: {e}, install the Solana CLI tools"));
+    assert!(status.success(), "cargo build-sbf failed with {status}");
+}
+
+fn read(path: &PathBuf) -> Vec<u8> {
+    std::fs::read(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ghost_check::ID).0
+}
+
+pub fn program_data() -> Pubkey {
+    get_program_data_address(&ghost_check::ID)
+}
+
+pub fn ghost_config() -> Pubkey {
+    pda(&[b"ghost_config"])
+}
+
+pub fn scoring_params() -> Pubkey {
+    pda(&[b"scoring_params"])
+}
+
+pub fn dev_state(dev: &Pubkey) -> Pubkey {
+    pda(&[b"dev_state", dev.as_ref()])
+}
+
+pub fn dev_badge(dev: &Pubkey) -> Pubkey {
+    pda(&[b"dev_badge", dev.as_ref()])
+}
+
+pub fn identity_claim(username: &[u8; 32]) -> Pubkey {
+    pda(&[b"identity_claim", username])
+}
+
+pub fn revocation(dev: &Pubkey) -> Pubkey {
+    pda(&[b"revocation", dev.as_ref()])
+}
+
+pub fn repo_state(dev: &Pubkey, repo_name: &[u8; 32]) -> Pubkey {
+    pda(&[b"repo_state", dev_badge(dev).as_ref(), repo_name])
+}
+
+pub fn repo_badge(dev: &Pubkey, repo_name: &[u8; 32]) -> Pubkey {
+    pda(&[b"repo_badge", dev_badge(dev).as_ref(), repo_name])
+}
+
+pub fn vouch_record(voucher: &Pubkey, target: &Pubkey) -> Pubkey {
+    pda(&[b"vouch_record", voucher.as_ref(), target.as_ref()])
+}
+
+pub fn vouch_tombstone(voucher: &Pubkey, target: &Pubkey) -> Pubkey {
+    pda(&[b"vouch_tombstone", voucher.as_ref(), target.as_ref()])
+}
+
+pub fn migration(old_wallet: &Pubkey) -> Pubkey {
+    pda(&[b"migration", old_wallet.as_ref()])
+}
+
+pub fn org_state(hashed_org: &[u8; 32]) -> Pubkey {
+    pda(&[b"org_state", hashed_org])
+}
+
+pub fn org_badge(hashed_org: &[u8; 32]) -> Pubkey {
+    pda(&[b"org_badge", hashed_org])
+}
+
+pub fn org_membership(hashed_org: &[u8; 32], dev: &Pubkey) -> Pubkey {
+    pda(&[b"org_member", org_badge(hashed_org).as_ref(), dev.as_ref()])
+}
+
+pub fn org_member_badge(hashed_org: &[u8; 32], dev: &Pubkey) -> Pubkey {
+    pda(&[
+        b"org_member_badge",
+        org_badge(hashed_org).as_ref(),
+        dev.as_ref(),
+    ])
+}
+
+pub fn contribution_state(dev: &Pubkey, hashed_repo: &[u8; 32]) -> Pubkey {
+    pda(&[b"contribution_state", dev_badge(dev).as_ref(), hashed_repo])
+}
+
+pub fn contribution_badge(dev: &Pubkey, hashed_repo: &[u8; 32]) -> Pubkey {
+    pda(&[b"contribution_badge", dev_badge(dev).as_ref(), hashed_repo])
+}
+
+// GitHub logins and orgs are stored as sha256 hashes, as the backend signs them
+pub fn hashed(name: &str) -> [u8; 32] {
+    hash(name.as_bytes()).to_bytes()
+}
+
+// Repo names are zero padded to 32 bytes to serve as seeds
+pub fn padded(name: &str) -> [u8; 32] {
+    let mut padded = [0u8; 32];
+    padded[..name.len()].copy_from_slice(name.as_bytes());
+    padded
+}
+
+pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ghost_check::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// Ed25519 precompile instruction carrying `signer`'s signature of `message`
+pub fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    let signature: [u8; 64] = signer.sign_message(message).into();
+    new_ed25519_instruction_with_signature(message, &signature, &signer.pubkey().to_bytes())
+}
+
+pub fn contribution_mint_ix(
+    dev: &Pubkey,
+    username: &[u8; 32],
+    hashed_repo: [u8; 32],
+    repo_full_name: &str,
+    (prs_merged, commits): (u32, u32),
+    issued_at: i64,
+) -> Instruction {
+    ix(
+        ghost_check::accounts::ContributionBadge {
+            dev: *dev,
+            ghost_config: ghost_config(),
+            dev_state: dev_state(dev),
+            migration: migration(dev),
+            dev_badge: dev_badge(dev),
+            contribution_state: contribution_state(dev, &hashed_repo),
+            contribution_badge: contribution_badge(dev, &hashed_repo),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MintContributionBadge {
+            hashed_repo,
+            repo_full_name: repo_full_name.as_bytes().to_vec(),
+            username_padded: *username,
+            prs_merged,
+            commits,
+            issued_at,
+            nonce: 1,
+        },
+    )
+}
+
+pub fn burn_dev_badge_ix(dev: &Pubkey, username: &str) -> Instruction {
+    ix(
+        ghost_check::accounts::BurnDevBadge {
+            dev: *dev,
+            ghost_config: ghost_config(),
+            dev_state: dev_state(dev),
+            identity_claim: identity_claim(&hashed(username)),
+            dev_badge: dev_badge(dev),
+            core_program: mpl_core::ID,
+        },
+        ghost_check::instruction::BurnDevBadge {},
+    )
+}
+
+pub const REASON_SYBIL: u8 = 2;
+
+// `repos` are passed as the (repo_state, repo_badge) pairs of remaining_accounts
+pub fn revoke_ix(admin: &Pubkey, dev: &Pubkey, repos: &[&RepoMetrics]) -> Instruction {
+    let mut revoke = ix(
+        ghost_check::accounts::RevokeDevBadge {
+            admin: *admin,
+            ghost_config: ghost_config(),
+            dev_state: dev_state(dev),
+            dev_badge: dev_badge(dev),
+            revocation: revocation(dev),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::RevokeDevBadge {
+            dev_addr: dev.to_bytes(),
+            reason: REASON_SYBIL,
+        },
+    );
+    for repo in repos {
+        let name = padded(repo.name);
+        revoke
+            .accounts
+            .push(AccountMeta::new(repo_state(dev, &name), false));
+        revoke
+            .accounts
+            .push(AccountMeta::new(repo_badge(dev, &name), false));
+    }
+    revoke
+}
+
+// Signed by both the dev and the org authority
+pub fn link_org_member_ix(dev: &Pubkey, authority: &Pubkey) -> Instruction {
+    let hashed_org = hashed(ORG);
+    ix(
+        ghost_check::accounts::LinkOrgMember {
+            dev: *dev,
+            authority: *authority,
+            ghost_config: ghost_config(),
+            dev_state: dev_state(dev),
+            migration: migration(dev),
+            org_state: org_state(&hashed_org),
+            org_badge: org_badge(&hashed_org),
+            org_membership: org_membership(&hashed_org, dev),
+            member_badge: org_member_badge(&hashed_org, dev),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::LinkOrgMember {},
+    )
+}
+
+pub fn prune_vouch_ix(admin: &Pubkey, voucher: &Pubkey, target: &Pubkey) -> Instruction {
+    ix(
+        ghost_check::accounts::PruneVouch {
+            admin: *admin,
+            ghost_config: ghost_config(),
+            revocation: revocation(voucher),
+            vouch_record: vouch_record(voucher, target),
+            target_dev_state: dev_state(target),
+            target_dev_badge: dev_badge(target),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::PruneVouch {
+            voucher_addr: voucher.to_bytes(),
+            target_addr: target.to_bytes(),
+        },
+    )
+}
+
+// GitHub metrics of a dev badge attestation
+#[derive(Clone, Copy, Debug)]
+pub struct DevMetrics {
+    pub repo_count: u32,
+    pub owned_repo_count: u32,
+    pub total_stars: u32,
+    pub total_commits: u32,
+    pub prs_merged: u32,
+    pub issues_closed: u32,
+    pub followers: u32,
+    pub account_age_days: u32,
+    pub reputation_level: u8,
+}
+
+impl DevMetrics {
+    // Metrics that score `level` under TIERS
+    pub fn level(level: u8) -> Self {
+        let (repo_count, total_commits, account_age_days) = match level {
+            1 => (3, 12, 40),
+            2 => (8, 60, 30),
+            3 => (25, 250, 200),
+            4 => (60, 800, 400),
+            _ => (120, 2000, 1000),
+        };
+
+        Self {
+            repo_count,
+            owned_repo_count: repo_count / 2,
+            total_stars: 40 * level as u32,
+            total_commits,
+            prs_merged: level as u32,
+            issues_closed: 2,
+            followers: 10,
+            account_age_days,
+            reputation_level: level,
+        }
+    }
+
+    pub fn message(
+        &self,
+        dev: &Pubkey,
+        username: &[u8; 32],
+        issued_at: i64,
+        nonce: u64,
+    ) -> [u8; 32] {
+        ghost_check::dev_badge_message(
+            dev,
+            username,
+            self.repo_count,
+            self.total_commits,
+            self.owned_repo_count,
+            self.total_stars,
+            self.prs_merged,
+            self.issues_closed,
+            self.followers,
+            self.account_age_days,
+            self.reputation_level,
+            issued_at,
+            nonce,
+        )
+    }
+
+    pub fn mint_ix(
+        &self,
+        dev: &Pubkey,
+        username: &[u8; 32],
+        issued_at: i64,
+        nonce: u64,
+    ) -> Instruction {
+        ix(
+            ghost_check::accounts::DevBadge {
+                dev: *dev,
+                ghost_config: ghost_config(),
+                dev_state: dev_state(dev),
+                identity_claim: identity_claim(username),
+                scoring_params: scoring_params(),
+                revocation: revocation(dev),
+                asset: dev_badge(dev),
+                system_program: system_program::ID,
+                instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+                core_program: mpl_core::ID,
+            },
+            ghost_check::instruction::MintDevBadge {
+                username: *username,
+                repo_count: self.repo_count,
+                owned_repo_count: self.owned_repo_count,
+                total_stars: self.total_stars,
+                total_commits: self.total_commits,
+                prs_merged: self.prs_merged,
+                issues_closed: self.issues_closed,
+                followers: self.followers,
+                account_age_days: self.account_age_days,
+                reputation_level: self.reputation_level,
+                issued_at,
+                nonce,
+            },
+        )
+    }
+
+    pub fn update_ix(
+        &self,
+        dev: &Pubkey,
+        username: &[u8; 32],
+        issued_at: i64,
+        nonce: u64,
+    ) -> Instruction {
+        ix(
+            ghost_check::accounts::UpdateDevBadge {
+                dev: *dev,
+                ghost_config: ghost_config(),
+                dev_state: dev_state(dev),
+                migration: migration(dev),
+                scoring_params: scoring_params(),
+                dev_badge: dev_badge(dev),
+                instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::UpdateDevBadge {
+                username: *username,
+                repo_count: self.repo_count,
+                owned_repo_count: self.owned_repo_count,
+                total_stars: self.total_stars,
+                total_commits: self.total_commits,
+                prs_merged: self.prs_merged,
+                issues_closed: self.issues_closed,
+                followers: self.followers,
+                account_age_days: self.account_age_days,
+                reputation_level: self.reputation_level,
+                issued_at,
+                nonce,
+            },
+        )
+    }
+}
+
+// Stats of a repo badge attestation
+#[derive(Clone, Debug)]
+pub struct RepoMetrics {
+    pub name: &'static str,
+    pub stars: u32,
+    pub commits: u32,
+    pub forks: u32,
+    pub open_issues: u32,
+    pub is_fork: u8,
+    pub lang1: Vec<u8>,
+    pub lang2: Vec<u8>,
+}
+
+impl RepoMetrics {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            stars: 42,
+            commits: 120,
+            forks: 3,
+            open_issues: 1,
+            is_fork: 0,
+            lang1: b"Rust".to_vec(),
+            lang2: b"TypeScript".to_vec(),
+        }
+    }
+
+    pub fn message(
+        &self,
+        dev: &Pubkey,
+        username: &[u8; 32],
+        issued_at: i64,
+        nonce: u64,
+    ) -> [u8; 32] {
+        ghost_check::repo_badge_message(
+            dev,
+            username,
+            self.name.as_bytes(),
+            &self.lang1,
+            &self.lang2,
+            self.stars,
+            self.commits,
+            self.forks,
+            self.open_issues,
+            self.is_fork,
+            issued_at,
+            nonce,
+        )
+    }
+
+    pub fn mint_ix(
+        &self,
+        dev: &Pubkey,
+        username: &[u8; 32],
+        issued_at: i64,
+        nonce: u64,
+    ) -> Instruction {
+        let repo_name = padded(self.name);
+        ix(
+            ghost_check::accounts::RepoBadge {
+                dev: *dev,
+                ghost_config: ghost_config(),
+                dev_state: dev_state(dev),
+                migration: migration(dev),
+                dev_badge: dev_badge(dev),
+                repo_state: repo_state(dev, &repo_name),
+                repo_badge: repo_badge(dev, &repo_name),
+                instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::MintRepoBadge {
+                repo_name_padded: repo_name,
+                username_hashed: *username,
+                stars: self.stars,
+                commits: self.commits,
+                forks: self.forks,
+                open_issues: self.open_issues,
+                is_fork: self.is_fork,
+                lang1: self.lang1.clone(),
+                lang2: self.lang2.clone(),
+                issued_at,
+                nonce,
+            },
+        )
+    }
+    pub fn update_ix(
+        &self,
+        dev: &Pubkey,
+        username: &[u8; 32],
+        issued_at: i64,
+        nonce: u64,
+    ) -> Instruction {
+        let repo_name = padded(self.name);
+        ix(
+            ghost_check::accounts::UpdateRepoBadge {
+                dev: *dev,
+                ghost_config: ghost_config(),
+                dev_state: dev_state(dev),
+                migration: migration(dev),
+                dev_badge: dev_badge(dev),
+                repo_state: repo_state(dev, &repo_name),
+                repo_badge: repo_badge(dev, &repo_name),
+                instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::UpdateRepoBadge {
+                repo_name_padded: repo_name,
+                username_hashed: *username,
+                stars: self.stars,
+                commits: self.commits,
+                forks: self.forks,
+                open_issues: self.open_issues,
+                lang1: self.lang1.clone(),
+                lang2: self.lang2.clone(),
+                issued_at,
+                nonce,
+            },
+        )
+    }
+
+    pub fn burn_ix(&self, dev: &Pubkey) -> Instruction {
+        let repo_name = padded(self.name);
+        ix(
+            ghost_check::accounts::BurnRepoBadge {
+                dev: *dev,
+                ghost_config: ghost_config(),
+                dev_state: dev_state(dev),
+                dev_badge: dev_badge(dev),
+                repo_state: repo_state(dev, &repo_name),
+                repo_badge: repo_badge(dev, &repo_name),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::BurnRepoBadge {
+                repo_name_padded: repo_name,
+            },
+        )
+    }
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    // Upgrade authority of the program, so the only wallet init_config accepts
+    pub admin: Keypair,
+    pub backend: Keypair,
+    sent: HashSet<Signature>,
+}
+
+impl TestEnv {
+    // Program deployed, config and scoring table initialized
+    pub async fn new() -> Self {
+        let mut env = Self::deployed().await;
+
+        let admin = env.admin.insecure_clone();
+        env.send(&[env.init_config_ix(&admin.pubkey())], &[&admin])
+            .await
+            .unwrap();
+        env.send(
+            &[ix(
+                ghost_check::accounts::InitScoringParams {
+                    admin: admin.pubkey(),
+                    ghost_config: ghost_config(),
+                    scoring_params: scoring_params(),
+                    system_program: system_program::ID,
+                },
+                ghost_check::instruction::InitScoringParams { tiers: TIERS },
+            )],
+            &[&admin],
+        )
+        .await
+        .unwrap();
+
+        env
+    }
+
+    // Program deployed as upgradeable with `admin` as upgrade authority, nothing initialized
+    pub async fn deployed() -> Self {
+        let admin = Keypair::new();
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+
+        let elf = read(&program_so());
+        program_test.add_account(
+            ghost_check::ID,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: bincode::serialize(&UpgradeableLoaderState::Program {
+                    programdata_address: program_data(),
+                })
+                .unwrap(),
+                owner: bpf_loader_upgradeable::ID,
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+        let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(admin.pubkey()),
+        })
+        .unwrap();
+        data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+        data.extend_from_slice(&elf);
+        program_test.add_account(
+            program_data(),
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data,
+                owner: bpf_loader_upgradeable::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        program_test.add_account(
+            mpl_core::ID,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: read(&PathBuf::from(MPL_CORE_FIXTURE)),
+                owner: bpf_loader::ID,
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+        program_test.add_account(admin.pubkey(), funded());
+
+        Self {
+            ctx: program_test.start_with_context().await,
+            admin,
+            backend: Keypair::new(),
+            sent: HashSet::new(),
+        }
+    }
+
+    pub fn init_config_ix(&self, admin: &Pubkey) -> Instruction {
+        ix(
+            ghost_check::accounts::InitConfig {
+                admin: *admin,
+                ghost_config: ghost_config(),
+                system_program: system_program::ID,
+                this_program: ghost_check::ID,
+                program_data: program_data(),
+            },
+            ghost_check::instruction::InitConfig {
+                backend_pubkey: self.backend.pubkey().to_bytes(),
+                max_attestation_age: MAX_ATTESTATION_AGE,
+                min_voucher_level: MIN_VOUCHER_LEVEL,
+                vouch_budget: VOUCH_BUDGET,
+                metadata_base_uri: METADATA_BASE_URI.to_string(),
+            },
+        )
+    }
+
+    // Fresh wallet holding 100 SOL
+    pub async fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.ctx
+            .set_account(&wallet.pubkey(), &AccountSharedData::from(funded()));
+        wallet
+    }
+
+    // The first signer pays the fees
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.ctx.last_blockhash,
+        );
+        // The bank drops a transaction it already processed, a retry needs a new blockhash
+        if self.sent.contains(&tx.signatures[0]) {
+            self.ctx.last_blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+            tx.sign(signers, self.ctx.last_blockhash);
+        }
+        self.sent.insert(tx.signatures[0]);
+
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    // Runs the instructions without committing them and decodes the last one's return data
+    pub async fn view<T: AnchorDeserialize>(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<T, TransactionError> {
+        let payer = self.ctx.payer.insecure_clone();
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[&payer],
+            self.ctx.last_blockhash,
+        );
+        let simulation = self
+            .ctx
+            .banks_client
+            .simulate_transaction(tx)
+            .await
+            .unwrap();
+        simulation.result.unwrap()?;
+
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        Ok(T::deserialize(&mut &return_data.data[..]).unwrap())
+    }
+
+    // Backend signed dev badge mint, issued now with nonce 1
+    pub async fn mint_dev_badge(
+        &mut self,
+        dev: &Keypair,
+        username: &str,
+        metrics: DevMetrics,
+    ) -> Result<(), BanksClientError> {
+        let username = hashed(username);
+        let issued_at = self.now().await;
+        let message = metrics.message(&dev.pubkey(), &username, issued_at, 1);
+        let instructions = [
+            ed25519_ix(&self.backend, &message),
+            metrics.mint_ix(&dev.pubkey(), &username, issued_at, 1),
+        ];
+        self.send(&instructions, &[dev]).await
+    }
+
+    // Backend signed dev badge update, issued now
+    pub async fn update_dev_badge(
+        &mut self,
+        dev: &Keypair,
+        username: &str,
+        metrics: DevMetrics,
+        nonce: u64,
+    ) -> Result<(), BanksClientError> {
+        let username = hashed(username);
+        let issued_at = self.now().await;
+        let message = metrics.message(&dev.pubkey(), &username, issued_at, nonce);
+        let instructions = [
+            ed25519_ix(&self.backend, &message),
+            metrics.update_ix(&dev.pubkey(), &username, issued_at, nonce),
+        ];
+        self.send(&instructions, &[dev]).await
+    }
+
+    // Backend signed repo badge mint, issued now with nonce 1
+    pub async fn mint_repo_badge(
+        &mut self,
+        dev: &Keypair,
+        username: &str,
+        repo: &RepoMetrics,
+    ) -> Result<(), BanksClientError> {
+        let username = hashed(username);
+        let issued_at = self.now().await;
+        let message = repo.message(&dev.pubkey(), &username, issued_at, 1);
+        let instructions = [
+            ed25519_ix(&self.backend, &message),
+            repo.mint_ix(&dev.pubkey(), &username, issued_at, 1),
+        ];
+        self.send(&instructions, &[dev]).await
+    }
+
+    // Backend signed contribution badge mint for `repo_full_name`, issued now with nonce 1
+    pub async fn mint_contribution_badge(
+        &mut self,
+        dev: &Keypair,
+        username: &str,
+        repo_full_name: &str,
+        contributions: (u32, u32),
+    ) -> Result<(), BanksClientError> {
+        let username = hashed(username);
+        let issued_at = self.now().await;
+        let message = contribution_badge_message(
+            &dev.pubkey(),
+            &username,
+            repo_full_name.as_bytes(),
+            contributions.0,
+            contributions.1,
+            issued_at,
+            1,
+        );
+        let hashed_repo = hash_repo_full_name(repo_full_name.as_bytes());
+        let instructions = [
+            ed25519_ix(&self.backend, &message),
+            contribution_mint_ix(
+                &dev.pubkey(),
+                &username,
+                hashed_repo,
+                repo_full_name,
+                contributions,
+                issued_at,
+            ),
+        ];
+        self.send(&instructions, &[dev]).await
+    }
+
+    // Backend signed org badge mint for ORG, issued now with nonce 1
+    pub async fn mint_org_badge(&mut self, authority: &Keypair) -> Result<(), BanksClientError> {
+        let hashed_org = hashed(ORG);
+        let issued_at = self.now().await;
+        let message = org_badge_message(&authority.pubkey(), &hashed_org, 12, 340, 8, issued_at, 1);
+        let mint = ix(
+            ghost_check::accounts::OrgBadge {
+                authority: authority.pubkey(),
+                ghost_config: ghost_config(),
+                org_state: org_state(&hashed_org),
+                org_badge: org_badge(&hashed_org),
+                instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::MintOrgBadge {
+                hashed_org,
+                public_repos: 12,
+                total_stars: 340,
+                member_count: 8,
+                issued_at,
+                nonce: 1,
+            },
+        );
+        let instructions = [ed25519_ix(&self.backend, &message), mint];
+        self.send(&instructions, &[authority]).await
+    }
+
+    pub fn vouch_ix(&self, voucher: &Pubkey, target: &Pubkey) -> Instruction {
+        ix(
+            ghost_check::accounts::Vouch {
+                voucher: *voucher,
+                ghost_config: ghost_config(),
+                voucher_dev_state: dev_state(voucher),
+                target_dev_state: dev_state(target),
+                target_dev_badge: dev_badge(target),
+                vouch_record: vouch_record(voucher, target),
+                vouch_tombstone: vouch_tombstone(voucher, target),
+                voucher_migration: migration(voucher),
+                target_migration: migration(target),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::VouchForDev {
+                target_addr: target.to_bytes(),
+            },
+        )
+    }
+
+    pub fn revoke_vouch_ix(&self, voucher: &Pubkey, target: &Pubkey) -> Instruction {
+        ix(
+            ghost_check::accounts::RevokeVouch {
+                voucher: *voucher,
+                ghost_config: ghost_config(),
+                voucher_dev_state: dev_state(voucher),
+                target_dev_state: dev_state(target),
+                target_dev_badge: dev_badge(target),
+                vouch_record: vouch_record(voucher, target),
+                vouch_tombstone: vouch_tombstone(voucher, target),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            ghost_check::instruction::RevokeVouch {
+                target_addr: target.to_bytes(),
+            },
+        )
+    }
+
+    pub fn verify_dev_ix(
+        &self,
+        dev: &Pubkey,
+        min_lvl: u8,
+        max_staleness: Option<i64>,
+    ) -> Instruction {
+        ix(
+            ghost_check::accounts::VerifyDev {
+                target_dev_state: dev_state(dev),
+                revocation: revocation(dev),
+                migration: migration(dev),
+                ghost_config: ghost_config(),
+            },
+            ghost_check::instruction::VerifyDev {
+                dev_addr: dev.to_bytes(),
+                min_lvl,
+                max_staleness,
+            },
+        )
+    }
+
+    pub fn query_dev_ix(&self, dev: &Pubkey) -> Instruction {
+        ix(
+            ghost_check::accounts::VerifyDev {
+                target_dev_state: dev_state(dev),
+                revocation: revocation(dev),
+                migration: migration(dev),
+                ghost_config: ghost_config(),
+            },
+            ghost_check::instruction::QueryDev {
+                dev_addr: dev.to_bytes(),
+            },
+        )
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    // Moves the cluster clock forward, the slot stays where it is
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    // Moves the clock sysvar past `slot`, the bank itself stays where it is
+    pub async fn advance_past_slot(&mut self, slot: u64) {
+        let mut clock = self.clock().await;
+        clock.slot = clock.slot.max(slot + 1);
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn try_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        self.ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .map(|account| T::try_deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        self.try_account(address)
+            .await
+            .unwrap_or_else(|| panic!("account {address} does not exist"))
+    }
+
+    pub async fn exists(&mut self, address: &Pubkey) -> bool {
+        self.ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    // Name and URI Core stores on an asset or a collection
+    pub async fn core_metadata(&mut self, address: &Pubkey) -> (String, String) {
+        let data = self
+            .ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"))
+            .data;
+        if data[0] == mpl_core::types::Key::CollectionV1 as u8 {
+            let collection = mpl_core::accounts::BaseCollectionV1::from_bytes(&data).unwrap();
+            (collection.name, collection.uri)
+        } else {
+            let asset = mpl_core::accounts::BaseAssetV1::from_bytes(&data).unwrap();
+            (asset.name, asset.uri)
+        }
+    }
+
+    // Core burns leave a 1-byte account behind instead of closing it
+    pub async fn burned(&mut self, address: &Pubkey) -> bool {
+        self.ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .is_none_or(|account| account.data.len() <= 1)
+    }
+}
+
+fn funded() -> Account {
+    Account {
+        lamports: 100 * LAMPORTS_PER_SOL,
+        data: vec![],
+        owner: system_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+// Error code a failed transaction returned, from the program, Anchor or the system program
+pub fn error_code(result: Result<(), BanksClientError>) -> u32 {
+    custom_code(result.expect_err("transaction should have failed").unwrap())
+}
+
+fn custom_code(err: TransactionError) -> u32 {
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        err => panic!("expected a custom program error, got {err:?}"),
+    }
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, expected: impl Into<u32>) {
+    assert_eq!(error_code(result), expected.into());
+}
+
+pub fn assert_view_error<T: std::fmt::Debug>(
+    result: Result<T, TransactionError>,
+    expected: impl Into<u32>,
+) {
+    let err = result.expect_err("simulation should have failed");
+    assert_eq!(custom_code(err), expected.into());
+}
@@ -0,0 +1,402 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::{system_program, InstructionData};
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    state::{GhostConfig, ScoringParams, ScoringTier},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+fn update_config_ix(admin: &Pubkey, args: ghost_check::instruction::UpdateConfig) -> Instruction {
+    ix(
+        ghost_check::accounts::UpdateConfig {
+            admin: *admin,
+            ghost_config: ghost_config(),
+        },
+        args,
+    )
+}
+
+fn no_config_change() -> ghost_check::instruction::UpdateConfig {
+    ghost_check::instruction::UpdateConfig {
+        max_attestation_age: None,
+        min_voucher_level: None,
+        vouch_budget: None,
+        metadata_base_uri: None,
+        min_update_interval: None,
+        max_staleness: None,
+    }
+}
+
+// Admin instructions that only touch ghost_config share the same two accounts
+fn admin_ix(admin: &Pubkey, data: impl InstructionData) -> Instruction {
+    ix(
+        ghost_check::accounts::SetPaused {
+            admin: *admin,
+            ghost_config: ghost_config(),
+        },
+        data,
+    )
+}
+
+fn set_oracles_ix(admin: &Pubkey, oracles: &[&Keypair], threshold: u8) -> Instruction {
+    admin_ix(
+        admin,
+        ghost_check::instruction::SetOracles {
+            oracles: oracles.iter().map(|o| o.pubkey().to_bytes()).collect(),
+            threshold,
+        },
+    )
+}
+
+#[tokio::test]
+async fn init_config_by_upgrade_authority() {
+    let mut env = TestEnv::deployed().await;
+    let admin = env.admin.insecure_clone();
+
+    env.send(&[env.init_config_ix(&admin.pubkey())], &[&admin])
+        .await
+        .unwrap();
+
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.backend_pubkey, env.backend.pubkey().to_bytes());
+    assert_eq!(config.max_attestation_age, MAX_ATTESTATION_AGE);
+    assert_eq!(config.min_voucher_level, MIN_VOUCHER_LEVEL);
+    assert_eq!(config.vouch_budget, VOUCH_BUDGET);
+    assert_eq!(config.metadata_base_uri, METADATA_BASE_URI);
+    assert_eq!(config.oracle_threshold, 1);
+    assert_eq!(config.min_update_interval, 0);
+    assert_eq!(config.max_staleness, 0);
+    assert_eq!(config.version, 3);
+    assert!(!config.paused);
+}
+
+#[tokio::test]
+async fn init_config_rejects_other_wallets() {
+    let mut env = TestEnv::deployed().await;
+    let intruder = env.wallet().await;
+
+    let result = env
+        .send(&[env.init_config_ix(&intruder.pubkey())], &[&intruder])
+        .await;
+    assert_error(result, GhostErrors::UpgradeAuthorityMismatch);
+    assert!(!env.exists(&ghost_config()).await);
+}
+
+#[tokio::test]
+async fn update_config() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+
+    let ix = update_config_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::UpdateConfig {
+            max_attestation_age: Some(600),
+            min_voucher_level: Some(3),
+            metadata_base_uri: Some("https://api.ghostcheck.dev/".to_string()),
+            min_update_interval: Some(60),
+            max_staleness: Some(86_400),
+            ..no_config_change()
+        },
+    );
+    env.send(&[ix], &[&admin]).await.unwrap();
+
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.max_attestation_age, 600);
+    assert_eq!(config.min_voucher_level, 3);
+    assert_eq!(config.vouch_budget, VOUCH_BUDGET);
+    assert_eq!(config.metadata_base_uri, "https://api.ghostcheck.dev/");
+    assert_eq!(config.min_update_interval, 60);
+    assert_eq!(config.max_staleness, 86_400);
+}
+
+#[tokio::test]
+async fn update_config_rejects_invalid_values() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+
+    let negative_interval = update_config_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::UpdateConfig {
+            min_update_interval: Some(-1),
+            ..no_config_change()
+        },
+    );
+    let result = env.send(&[negative_interval], &[&admin]).await;
+    assert_error(result, GhostErrors::InvalidFreshnessConfig);
+
+    let long_uri = update_config_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::UpdateConfig {
+            metadata_base_uri: Some("x".repeat(200)),
+            ..no_config_change()
+        },
+    );
+    let result = env.send(&[long_uri], &[&admin]).await;
+    assert_error(result, GhostErrors::MetadataUriTooLong);
+
+    let decreasing_budget = update_config_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::UpdateConfig {
+            vouch_budget: Some([0, 3, 5, 4, 20]),
+            ..no_config_change()
+        },
+    );
+    let result = env.send(&[decreasing_budget], &[&admin]).await;
+    assert_error(result, GhostErrors::InvalidVouchBudget);
+
+    let intruder = env.wallet().await;
+    let result = env
+        .send(
+            &[update_config_ix(&intruder.pubkey(), no_config_change())],
+            &[&intruder],
+        )
+        .await;
+    assert_error(result, GhostErrors::AdminMismatch);
+}
+
+#[tokio::test]
+async fn admin_handover() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let new_admin = env.wallet().await;
+    let intruder = env.wallet().await;
+
+    let propose = admin_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::ProposeAdmin {
+            new_admin: new_admin.pubkey(),
+        },
+    );
+    env.send(&[propose], &[&admin]).await.unwrap();
+
+    let accept = |new_admin: &Pubkey| {
+        ix(
+            ghost_check::accounts::AcceptAdmin {
+                new_admin: *new_admin,
+                ghost_config: ghost_config(),
+            },
+            ghost_check::instruction::AcceptAdmin {},
+        )
+    };
+    let result = env.send(&[accept(&intruder.pubkey())], &[&intruder]).await;
+    assert_error(result, GhostErrors::PendingAdminMismatch);
+
+    env.send(&[accept(&new_admin.pubkey())], &[&new_admin])
+        .await
+        .unwrap();
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    // The previous admin lost its rights
+    let pause = admin_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::SetPaused { paused: true },
+    );
+    assert_error(
+        env.send(&[pause], &[&admin]).await,
+        GhostErrors::AdminMismatch,
+    );
+}
+
+#[tokio::test]
+async fn paused_protocol_blocks_mints() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let dev = env.wallet().await;
+
+    let pause = |paused| {
+        admin_ix(
+            &admin.pubkey(),
+            ghost_check::instruction::SetPaused { paused },
+        )
+    };
+    env.send(&[pause(true)], &[&admin]).await.unwrap();
+
+    let result = env
+        .mint_dev_badge(&dev, "paused-dev", DevMetrics::level(2))
+        .await;
+    assert_error(result, GhostErrors::ProtocolPaused);
+
+    env.send(&[pause(false)], &[&admin]).await.unwrap();
+    env.mint_dev_badge(&dev, "paused-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rotated_backend_key_stays_valid_for_grace_slots() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let old_backend = env.backend.insecure_clone();
+    let new_backend = Keypair::new();
+
+    let rotate = |key: &Keypair, grace_slots| {
+        admin_ix(
+            &admin.pubkey(),
+            ghost_check::instruction::RotateBackendKey {
+                new_backend_pubkey: key.pubkey().to_bytes(),
+                new_key_id: 2,
+                grace_slots,
+            },
+        )
+    };
+    env.send(&[rotate(&new_backend, 10)], &[&admin])
+        .await
+        .unwrap();
+    let result = env.send(&[rotate(&new_backend, 10)], &[&admin]).await;
+    assert_error(result, GhostErrors::BackendKeyUnchanged);
+
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.backend_pubkey, new_backend.pubkey().to_bytes());
+    assert_eq!(config.backend_key_id, 2);
+    assert_eq!(
+        config.previous_backend_pubkey,
+        Some(old_backend.pubkey().to_bytes())
+    );
+
+    // Inside the grace window the old key still signs
+    let early = env.wallet().await;
+    env.mint_dev_badge(&early, "early-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    // Rotating again inside the window would drop the old key early
+    let newest_backend = Keypair::new();
+    let rotate_again = admin_ix(
+        &admin.pubkey(),
+        ghost_check::instruction::RotateBackendKey {
+            new_backend_pubkey: newest_backend.pubkey().to_bytes(),
+            new_key_id: 3,
+            grace_slots: 0,
+        },
+    );
+    let result = env
+        .send(std::slice::from_ref(&rotate_again), &[&admin])
+        .await;
+    assert_error(result, GhostErrors::KeyGracePeriodActive);
+
+    // Once it ended, a rotation without grace expires the second key with the slot
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    env.advance_past_slot(config.previous_key_expiry_slot).await;
+    env.send(&[rotate_again], &[&admin]).await.unwrap();
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    env.advance_past_slot(config.previous_key_expiry_slot).await;
+
+    let late = env.wallet().await;
+    for expired in [old_backend, new_backend] {
+        env.backend = expired;
+        let result = env
+            .mint_dev_badge(&late, "late-dev", DevMetrics::level(2))
+            .await;
+        assert_error(result, GhostErrors::BackendPubkeyMismatch);
+    }
+
+    env.backend = newest_backend;
+    env.mint_dev_badge(&late, "late-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn oracle_threshold_requires_cosigners() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let oracle = Keypair::new();
+
+    // The backend key already holds a seat
+    let backend = env.backend.insecure_clone();
+    let result = env
+        .send(
+            &[set_oracles_ix(&admin.pubkey(), &[&backend], 1)],
+            &[&admin],
+        )
+        .await;
+    assert_error(result, GhostErrors::InvalidOracleSet);
+
+    let result = env
+        .send(&[set_oracles_ix(&admin.pubkey(), &[&oracle], 3)], &[&admin])
+        .await;
+    assert_error(result, GhostErrors::InvalidOracleThreshold);
+
+    env.send(&[set_oracles_ix(&admin.pubkey(), &[&oracle], 2)], &[&admin])
+        .await
+        .unwrap();
+
+    let dev = env.wallet().await;
+    let result = env
+        .mint_dev_badge(&dev, "oracle-dev", DevMetrics::level(2))
+        .await;
+    assert_error(result, GhostErrors::OracleThresholdNotMet);
+
+    // Backend and oracle signatures of the same attestation meet the threshold
+    let username = hashed("oracle-dev");
+    let metrics = DevMetrics::level(2);
+    let issued_at = env.now().await;
+    let message = metrics.message(&dev.pubkey(), &username, issued_at, 1);
+    let instructions = [
+        ed25519_ix(&env.backend, &message),
+        ed25519_ix(&oracle, &message),
+        metrics.mint_ix(&dev.pubkey(), &username, issued_at, 1),
+    ];
+    env.send(&instructions, &[&dev]).await.unwrap();
+}
+
+#[tokio::test]
+async fn scoring_table_updates() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+
+    let params: ScoringParams = env.account(&scoring_params()).await;
+    assert_eq!(params.tiers, TIERS);
+
+    let update = |tiers| {
+        ix(
+            ghost_check::accounts::UpdateScoringParams {
+                admin: admin.pubkey(),
+                ghost_config: ghost_config(),
+                scoring_params: scoring_params(),
+            },
+            ghost_check::instruction::UpdateScoringParams { tiers },
+        )
+    };
+
+    let mut decreasing = TIERS;
+    decreasing[3].min_repos = 1;
+    let result = env.send(&[update(decreasing)], &[&admin]).await;
+    assert_error(result, GhostErrors::InvalidScoringParams);
+
+    let mut raised = TIERS;
+    raised[0] = ScoringTier {
+        min_repos: 10,
+        min_commits: 100,
+        min_account_age_days: 30,
+    };
+    env.send(&[update(raised)], &[&admin]).await.unwrap();
+    let params: ScoringParams = env.account(&scoring_params()).await;
+    assert_eq!(params.tiers, raised);
+
+    // Metrics that scored level 2 under the old table now score level 1
+    let dev = env.wallet().await;
+    let result = env
+        .mint_dev_badge(&dev, "scored-dev", DevMetrics::level(2))
+        .await;
+    assert_error(result, GhostErrors::ReputationLevelMismatch);
+
+    // The table exists once
+    let init_again = ix(
+        ghost_check::accounts::InitScoringParams {
+            admin: admin.pubkey(),
+            ghost_config: ghost_config(),
+            scoring_params: scoring_params(),
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::InitScoringParams { tiers: TIERS },
+    );
+    assert_error(env.send(&[init_again], &[&admin]).await, 0u32);
+}
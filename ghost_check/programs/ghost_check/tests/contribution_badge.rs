@@ -0,0 +1,208 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::{error::ErrorCode, system_program};
+use common::*;
+use ghost_check::{
+    contribution_badge_message,
+    errors::GhostErrors,
+    hash_repo_full_name,
+    state::{ContributionState, DevState},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::Signer,
+};
+
+const USERNAME: &str = "contributor";
+const REPO: &str = "solana-labs/solana";
+
+fn burn_ix(dev: &Pubkey, hashed_repo: [u8; 32]) -> Instruction {
+    ix(
+        ghost_check::accounts::BurnContributionBadge {
+            dev: *dev,
+            ghost_config: ghost_config(),
+            dev_state: dev_state(dev),
+            dev_badge: dev_badge(dev),
+            contribution_state: contribution_state(dev, &hashed_repo),
+            contribution_badge: contribution_badge(dev, &hashed_repo),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::BurnContributionBadge { hashed_repo },
+    )
+}
+
+#[tokio::test]
+async fn mint_contribution_badge_once_per_repo() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    env.mint_contribution_badge(&dev, USERNAME, REPO, (4, 37))
+        .await
+        .unwrap();
+
+    let hashed_repo = hash_repo_full_name(REPO.as_bytes());
+    let state: ContributionState = env
+        .account(&contribution_state(&dev.pubkey(), &hashed_repo))
+        .await;
+    assert_eq!(state.contributor, dev.pubkey());
+    assert_eq!(state.dev_badge, dev_badge(&dev.pubkey()));
+    assert_eq!(state.hashed_username, hashed(USERNAME));
+    assert_eq!(state.repo_full_name, REPO.as_bytes());
+    assert_eq!(state.prs_merged, 4);
+    assert_eq!(state.commits, 37);
+    let counts: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(counts.contribution_badges, 1);
+    assert!(
+        env.exists(&contribution_badge(&dev.pubkey(), &hashed_repo))
+            .await
+    );
+
+    // GitHub names are case-insensitive, another casing is the same repo
+    let result = env
+        .mint_contribution_badge(&dev, USERNAME, "Solana-Labs/Solana", (5, 40))
+        .await;
+    assert_error(result, 0u32);
+}
+
+#[tokio::test]
+async fn contribution_requirements() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    // Seeded by another repo than the attested one
+    let issued_at = env.now().await;
+    let message = contribution_badge_message(
+        &dev.pubkey(),
+        &hashed(USERNAME),
+        REPO.as_bytes(),
+        4,
+        37,
+        issued_at,
+        1,
+    );
+    let hashed_repo = hash_repo_full_name(b"coral-xyz/anchor");
+    let instructions = [
+        ed25519_ix(&env.backend, &message),
+        contribution_mint_ix(
+            &dev.pubkey(),
+            &hashed(USERNAME),
+            hashed_repo,
+            REPO,
+            (4, 37),
+            issued_at,
+        ),
+    ];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, GhostErrors::RepoHashMismatch);
+
+    let result = env
+        .mint_contribution_badge(&dev, USERNAME, REPO, (0, 0))
+        .await;
+    assert_error(result, GhostErrors::NoContributions);
+
+    // Attested for another GitHub account than the dev badge's
+    let result = env
+        .mint_contribution_badge(&dev, "someone-else", REPO, (4, 37))
+        .await;
+    assert_error(result, GhostErrors::IdentityMismatch);
+
+    // Only merged PRs or only commits are both enough
+    env.mint_contribution_badge(&dev, USERNAME, REPO, (1, 0))
+        .await
+        .unwrap();
+    env.mint_contribution_badge(&dev, USERNAME, "coral-xyz/anchor", (0, 1))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn burn_contribution_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let other = env.wallet().await;
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_dev_badge(&other, "other-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_contribution_badge(&dev, USERNAME, REPO, (4, 37))
+        .await
+        .unwrap();
+    let hashed_repo = hash_repo_full_name(REPO.as_bytes());
+
+    // The badge is derived from the dev's own collection
+    let mut burn = burn_ix(&other.pubkey(), hashed_repo);
+    burn.accounts[4].pubkey = contribution_state(&dev.pubkey(), &hashed_repo);
+    let result = env.send(&[burn], &[&other]).await;
+    assert_error(result, ErrorCode::ConstraintSeeds);
+
+    // The dev collection can't go while a contribution badge is left in it
+    let result = env
+        .send(&[burn_dev_badge_ix(&dev.pubkey(), USERNAME)], &[&dev])
+        .await;
+    assert_error(result, GhostErrors::ContributionBadgesRemaining);
+
+    env.send(&[burn_ix(&dev.pubkey(), hashed_repo)], &[&dev])
+        .await
+        .unwrap();
+    assert!(
+        !env.exists(&contribution_state(&dev.pubkey(), &hashed_repo))
+            .await
+    );
+    let state: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(state.contribution_badges, 0);
+    env.send(&[burn_dev_badge_ix(&dev.pubkey(), USERNAME)], &[&dev])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn revoke_burns_contribution_badges() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_contribution_badge(&dev, USERNAME, REPO, (4, 37))
+        .await
+        .unwrap();
+    let hashed_repo = hash_repo_full_name(REPO.as_bytes());
+
+    // Contribution badges are counted like repo badges, none may be left in the collection
+    let result = env
+        .send(&[revoke_ix(&admin.pubkey(), &dev.pubkey(), &[])], &[&admin])
+        .await;
+    assert_error(result, GhostErrors::ContributionBadgesRemaining);
+
+    let mut revoke = revoke_ix(&admin.pubkey(), &dev.pubkey(), &[]);
+    revoke.accounts.push(AccountMeta::new(
+        contribution_state(&dev.pubkey(), &hashed_repo),
+        false,
+    ));
+    let result = env.send(std::slice::from_ref(&revoke), &[&admin]).await;
+    assert_error(result, GhostErrors::InvalidRemainingAccounts);
+
+    revoke.accounts.push(AccountMeta::new(
+        contribution_badge(&dev.pubkey(), &hashed_repo),
+        false,
+    ));
+    env.send(&[revoke], &[&admin]).await.unwrap();
+
+    assert!(
+        !env.exists(&contribution_state(&dev.pubkey(), &hashed_repo))
+            .await
+    );
+    assert!(!env.exists(&dev_state(&dev.pubkey())).await);
+}
@@ -0,0 +1,491 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::{
+    error::ErrorCode, solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID,
+};
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    identity_release_message,
+    state::{DevState, GhostConfig, IdentityClaim},
+    Criteria, CriteriaResult, DevVerification, CRITERIA_LEVEL, CRITERIA_PRS_MERGED, CRITERIA_STARS,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+fn verify_criteria_ix(dev: &Pubkey, criteria: Criteria) -> Instruction {
+    ix(
+        ghost_check::accounts::VerifyDev {
+            target_dev_state: dev_state(dev),
+            revocation: revocation(dev),
+            migration: migration(dev),
+            ghost_config: ghost_config(),
+        },
+        ghost_check::instruction::VerifyDevCriteria {
+            _dev_addr: dev.to_bytes(),
+            criteria,
+        },
+    )
+}
+
+fn query_criteria_ix(dev: &Pubkey, criteria: Criteria) -> Instruction {
+    ix(
+        ghost_check::accounts::VerifyDev {
+            target_dev_state: dev_state(dev),
+            revocation: revocation(dev),
+            migration: migration(dev),
+            ghost_config: ghost_config(),
+        },
+        ghost_check::instruction::QueryDevCriteria {
+            dev_addr: dev.to_bytes(),
+            criteria,
+        },
+    )
+}
+
+fn release_identity_ix(
+    new_wallet: &Pubkey,
+    old_wallet: &Pubkey,
+    username: &str,
+    issued_at: i64,
+) -> Instruction {
+    let username = hashed(username);
+    ix(
+        ghost_check::accounts::ReleaseIdentity {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            identity_claim: identity_claim(&username),
+            old_dev_state: dev_state(old_wallet),
+            old_dev_badge: dev_badge(old_wallet),
+            old_wallet: *old_wallet,
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            core_program: mpl_core::ID,
+        },
+        ghost_check::instruction::ReleaseIdentity {
+            username,
+            issued_at,
+        },
+    )
+}
+
+// Mint signed by `signer` instead of the backend, issued `age` seconds ago
+async fn mint_signed_by(
+    env: &mut TestEnv,
+    dev: &Keypair,
+    signer: &Keypair,
+    metrics: DevMetrics,
+    age: i64,
+) -> Result<(), solana_program_test::BanksClientError> {
+    let username = hashed("ghost-dev");
+    let issued_at = env.now().await - age;
+    let message = metrics.message(&dev.pubkey(), &username, issued_at, 1);
+    let instructions = [
+        ed25519_ix(signer, &message),
+        metrics.mint_ix(&dev.pubkey(), &username, issued_at, 1),
+    ];
+    env.send(&instructions, &[dev]).await
+}
+
+#[tokio::test]
+async fn mint_dev_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let metrics = DevMetrics::level(3);
+
+    env.mint_dev_badge(&dev, "ghost-dev", metrics)
+        .await
+        .unwrap();
+
+    let state: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(state.dev_addr, dev.pubkey());
+    assert_eq!(state.asset_address, dev_badge(&dev.pubkey()));
+    assert_eq!(state.hashed_username, hashed("ghost-dev"));
+    assert_eq!(state.repo_count, metrics.repo_count);
+    assert_eq!(state.total_commits, metrics.total_commits);
+    assert_eq!(state.reputation_level, 3);
+    assert_eq!(state.nonce, 1);
+    assert_eq!(state.verified_repos, 0);
+
+    let claim: IdentityClaim = env.account(&identity_claim(&hashed("ghost-dev"))).await;
+    assert_eq!(claim.wallet, dev.pubkey());
+    assert!(env.exists(&dev_badge(&dev.pubkey())).await);
+
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.dev_badges_minted, 1);
+}
+
+#[tokio::test]
+async fn mint_rejects_invalid_attestations() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let backend = env.backend.insecure_clone();
+    let metrics = DevMetrics::level(2);
+
+    let result = mint_signed_by(&mut env, &dev, &Keypair::new(), metrics, 0).await;
+    assert_error(result, GhostErrors::BackendPubkeyMismatch);
+
+    let result = mint_signed_by(&mut env, &dev, &backend, metrics, MAX_ATTESTATION_AGE + 1).await;
+    assert_error(result, GhostErrors::AttestationExpired);
+
+    let result = mint_signed_by(&mut env, &dev, &backend, metrics, -3600).await;
+    assert_error(result, GhostErrors::AttestationFromFuture);
+
+    // Level 5 is claimed but the metrics only reach level 2 under the scoring table
+    let inflated = DevMetrics {
+        reputation_level: 5,
+        ..metrics
+    };
+    let result = env.mint_dev_badge(&dev, "ghost-dev", inflated).await;
+    assert_error(result, GhostErrors::ReputationLevelMismatch);
+
+    // Signed stats differ from the instruction arguments
+    let username = hashed("ghost-dev");
+    let issued_at = env.now().await;
+    let message = metrics.message(&dev.pubkey(), &username, issued_at, 1);
+    let boosted = DevMetrics {
+        total_stars: 10_000,
+        ..metrics
+    };
+    let instructions = [
+        ed25519_ix(&backend, &message),
+        boosted.mint_ix(&dev.pubkey(), &username, issued_at, 1),
+    ];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, GhostErrors::SignedMessageMismatch);
+
+    // Without an Ed25519 instruction there is nothing to verify
+    let result = env
+        .send(
+            &[metrics.mint_ix(&dev.pubkey(), &username, issued_at, 1)],
+            &[&dev],
+        )
+        .await;
+    assert_error(result, GhostErrors::InvalidSignature);
+
+    assert!(!env.exists(&dev_state(&dev.pubkey())).await);
+}
+
+#[tokio::test]
+async fn mint_rejects_offsets_outside_the_ed25519_instruction() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let metrics = DevMetrics::level(2);
+
+    let username = hashed("ghost-dev");
+    let issued_at = env.now().await;
+    let message = metrics.message(&dev.pubkey(), &username, issued_at, 1);
+    let mut ed25519 = ed25519_ix(&env.backend, &message);
+    // Instruction 0 is the Ed25519 instruction itself, the precompile still passes
+    // but the message could live in any other instruction
+    ed25519.data[14..16].copy_from_slice(&0u16.to_le_bytes());
+
+    let instructions = [
+        ed25519,
+        metrics.mint_ix(&dev.pubkey(), &username, issued_at, 1),
+    ];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, GhostErrors::Ed25519OffsetsInvalid);
+}
+
+#[tokio::test]
+async fn one_dev_badge_per_wallet_and_identity() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    // The system program refuses to create the dev_state a second time
+    let result = env
+        .mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await;
+    assert_error(result, 0u32);
+
+    // The identity_claim already exists for this GitHub account
+    let other = env.wallet().await;
+    let result = env
+        .mint_dev_badge(&other, "ghost-dev", DevMetrics::level(2))
+        .await;
+    assert_error(result, 0u32);
+    assert!(!env.exists(&dev_state(&other.pubkey())).await);
+}
+
+#[tokio::test]
+async fn update_dev_badge() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    env.update_dev_badge(&dev, "ghost-dev", DevMetrics::level(4), 2)
+        .await
+        .unwrap();
+    let state: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(state.reputation_level, 4);
+    assert_eq!(state.nonce, 2);
+
+    // An older attestation can't roll the stats back
+    let result = env
+        .update_dev_badge(&dev, "ghost-dev", DevMetrics::level(2), 2)
+        .await;
+    assert_error(result, GhostErrors::NonceAlreadyUsed);
+
+    // The identity is fixed at mint
+    let result = env
+        .update_dev_badge(&dev, "someone-else", DevMetrics::level(4), 3)
+        .await;
+    assert_error(result, GhostErrors::IdentityMismatch);
+
+    // A cooldown spaces updates out
+    let ix = ix(
+        ghost_check::accounts::UpdateConfig {
+            admin: admin.pubkey(),
+            ghost_config: ghost_config(),
+        },
+        ghost_check::instruction::UpdateConfig {
+            max_attestation_age: None,
+            min_voucher_level: None,
+            vouch_budget: None,
+            metadata_base_uri: Some("https://api.ghostcheck.dev".to_string()),
+            min_update_interval: Some(3600),
+            max_staleness: None,
+        },
+    );
+    env.send(&[ix], &[&admin]).await.unwrap();
+    let result = env
+        .update_dev_badge(&dev, "ghost-dev", DevMetrics::level(5), 3)
+        .await;
+    assert_error(result, GhostErrors::UpdateCooldown);
+
+    env.advance_clock(3600).await;
+    env.update_dev_badge(&dev, "ghost-dev", DevMetrics::level(5), 3)
+        .await
+        .unwrap();
+    let state: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(state.reputation_level, 5);
+
+    // The collection URI follows the new metadata base URI on its next update
+    let (_, uri) = env.core_metadata(&dev_badge(&dev.pubkey())).await;
+    assert_eq!(
+        uri,
+        format!(
+            "https://api.ghostcheck.dev/metadata/dev/{}",
+            dev_badge(&dev.pubkey())
+        )
+    );
+}
+
+#[tokio::test]
+async fn update_rejects_another_devs_state() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let other = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_dev_badge(&other, "other-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    let username = hashed("ghost-dev");
+    let metrics = DevMetrics::level(3);
+    let issued_at = env.now().await;
+    let message = metrics.message(&dev.pubkey(), &username, issued_at, 2);
+    let mut update = metrics.update_ix(&dev.pubkey(), &username, issued_at, 2);
+    update.accounts[2].pubkey = dev_state(&other.pubkey());
+
+    let instructions = [ed25519_ix(&env.backend, &message), update];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn verify_dev() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(3))
+        .await
+        .unwrap();
+
+    let verification: DevVerification = env
+        .view(&[env.verify_dev_ix(&dev.pubkey(), 3, None)])
+        .await
+        .unwrap();
+    assert_eq!(verification.dev_addr, dev.pubkey());
+    assert_eq!(verification.reputation_level, 3);
+    assert!(!verification.revoked);
+
+    let result = env
+        .view::<DevVerification>(&[env.verify_dev_ix(&dev.pubkey(), 4, None)])
+        .await;
+    assert_view_error(result, GhostErrors::DevVerificationFailed);
+
+    for min_lvl in [0, 6] {
+        let result = env
+            .view::<DevVerification>(&[env.verify_dev_ix(&dev.pubkey(), min_lvl, None)])
+            .await;
+        assert_view_error(result, GhostErrors::ReputationLvlInvalid);
+    }
+
+    let nobody = Keypair::new();
+    let result = env
+        .view::<DevVerification>(&[env.verify_dev_ix(&nobody.pubkey(), 1, None)])
+        .await;
+    assert_view_error(result, GhostErrors::DevBadgeNotFound);
+}
+
+#[tokio::test]
+async fn verify_dev_rejects_stale_badges() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(3))
+        .await
+        .unwrap();
+    env.advance_clock(120).await;
+
+    let result = env
+        .view::<DevVerification>(&[env.verify_dev_ix(&dev.pubkey(), 1, Some(60))])
+        .await;
+    assert_view_error(result, GhostErrors::StaleBadge);
+
+    // 0 accepts any age, and query_dev never checks it
+    let fresh_enough: DevVerification = env
+        .view(&[env.verify_dev_ix(&dev.pubkey(), 1, Some(0))])
+        .await
+        .unwrap();
+    assert_eq!(fresh_enough.reputation_level, 3);
+    let query: DevVerification = env.view(&[env.query_dev_ix(&dev.pubkey())]).await.unwrap();
+    assert_eq!(query.reputation_level, 3);
+}
+
+#[tokio::test]
+async fn verify_dev_criteria() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(3))
+        .await
+        .unwrap();
+
+    let result: CriteriaResult = env
+        .view(&[verify_criteria_ix(
+            &dev.pubkey(),
+            Criteria {
+                min_level: Some(2),
+                min_prs_merged: Some(3),
+                ..Default::default()
+            },
+        )])
+        .await
+        .unwrap();
+    assert_eq!(result.dev_addr, dev.pubkey());
+    assert_eq!(result.checked, CRITERIA_LEVEL | CRITERIA_PRS_MERGED);
+    assert_eq!(result.passed, result.checked);
+
+    let unmet = Criteria {
+        min_level: Some(2),
+        min_stars: Some(10_000),
+        ..Default::default()
+    };
+    let result = env
+        .view::<CriteriaResult>(&[verify_criteria_ix(&dev.pubkey(), unmet.clone())])
+        .await;
+    assert_view_error(result, GhostErrors::CriteriaNotMet);
+
+    // The query reports the same criteria per bit instead of failing
+    let result: CriteriaResult = env
+        .view(&[query_criteria_ix(&dev.pubkey(), unmet)])
+        .await
+        .unwrap();
+    assert_eq!(result.checked, CRITERIA_LEVEL | CRITERIA_STARS);
+    assert_eq!(result.passed, CRITERIA_LEVEL);
+    assert_eq!(result.failed(), CRITERIA_STARS);
+    assert!(!result.met());
+}
+
+#[tokio::test]
+async fn burn_dev_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    env.send(&[burn_dev_badge_ix(&dev.pubkey(), "ghost-dev")], &[&dev])
+        .await
+        .unwrap();
+    assert!(!env.exists(&dev_state(&dev.pubkey())).await);
+    assert!(!env.exists(&identity_claim(&hashed("ghost-dev"))).await);
+
+    // The GitHub identity is free again for another wallet
+    let next = env.wallet().await;
+    env.mint_dev_badge(&next, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn release_identity() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    env.mint_dev_badge(&old_wallet, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_repo_badge(&old_wallet, "ghost-dev", &repo)
+        .await
+        .unwrap();
+
+    let release = |env: &TestEnv, new_wallet: &Pubkey, issued_at| {
+        let message = identity_release_message(
+            &hashed("ghost-dev"),
+            &old_wallet.pubkey(),
+            new_wallet,
+            issued_at,
+        );
+        [
+            ed25519_ix(&env.backend, &message),
+            release_identity_ix(new_wallet, &old_wallet.pubkey(), "ghost-dev", issued_at),
+        ]
+    };
+
+    // Repo badges would be orphaned on the old wallet
+    let issued_at = env.now().await;
+    let result = env
+        .send(
+            &release(&env, &new_wallet.pubkey(), issued_at),
+            &[&new_wallet],
+        )
+        .await;
+    assert_error(result, GhostErrors::RepoBadgesRemaining);
+
+    env.send(&[repo.burn_ix(&old_wallet.pubkey())], &[&old_wallet])
+        .await
+        .unwrap();
+    let result = env
+        .send(
+            &release(&env, &old_wallet.pubkey(), issued_at),
+            &[&old_wallet],
+        )
+        .await;
+    assert_error(result, GhostErrors::SameWalletRelease);
+
+    env.send(
+        &release(&env, &new_wallet.pubkey(), issued_at),
+        &[&new_wallet],
+    )
+    .await
+    .unwrap();
+    assert!(!env.exists(&dev_state(&old_wallet.pubkey())).await);
+    assert!(env.burned(&dev_badge(&old_wallet.pubkey())).await);
+    assert!(!env.exists(&identity_claim(&hashed("ghost-dev"))).await);
+
+    env.mint_dev_badge(&new_wallet, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    let claim: IdentityClaim = env.account(&identity_claim(&hashed("ghost-dev"))).await;
+    assert_eq!(claim.wallet, new_wallet.pubkey());
+}
@@ -0,0 +1,978 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::{
+    solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID, system_program,
+    AnchorSerialize, Discriminator,
+};
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    hash_repo_full_name, identity_migration_message,
+    state::{
+        ContributionState, DevState, GhostConfig, IdentityClaim, MigrationRequest, OrgMembership,
+        OrgState, RepoState, Versioned, VouchRecord, MIGRATION_TIMELOCK,
+    },
+    DevVerification,
+};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USERNAME: &str = "moving-dev";
+
+// Backend signed request from `new_wallet` to take over the old wallet's identity
+async fn request_migration(env: &mut TestEnv, old_wallet: &Pubkey, new_wallet: &Keypair) {
+    let username = hashed(USERNAME);
+    let issued_at = env.now().await;
+    let message =
+        identity_migration_message(&username, old_wallet, &new_wallet.pubkey(), issued_at);
+    let request = ix(
+        ghost_check::accounts::RequestMigration {
+            new_wallet: new_wallet.pubkey(),
+            ghost_config: ghost_config(),
+            identity_claim: identity_claim(&username),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            migration: migration(old_wallet),
+            instruction_sysvar: INSTRUCTIONS_SYSVAR_ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::RequestMigration {
+            username,
+            issued_at,
+        },
+    );
+    let instructions = [ed25519_ix(&env.backend, &message), request];
+    env.send(&instructions, &[new_wallet]).await.unwrap();
+}
+
+fn approve_ix(old_wallet: &Pubkey) -> Instruction {
+    ix(
+        ghost_check::accounts::ApproveMigration {
+            old_wallet: *old_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+        },
+        ghost_check::instruction::ApproveMigration {},
+    )
+}
+
+fn cancel_ix(old_wallet: &Pubkey, new_wallet: &Pubkey) -> Instruction {
+    ix(
+        ghost_check::accounts::CancelMigration {
+            old_wallet: *old_wallet,
+            migration: migration(old_wallet),
+            new_wallet: *new_wallet,
+        },
+        ghost_check::instruction::CancelMigration {},
+    )
+}
+
+fn migrate_dev_ix(old_wallet: &Pubkey, new_wallet: &Pubkey) -> Instruction {
+    ix(
+        ghost_check::accounts::MigrateDevIdentity {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            new_dev_state: dev_state(new_wallet),
+            revocation: revocation(new_wallet),
+            new_dev_badge: dev_badge(new_wallet),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateDevIdentity {},
+    )
+}
+
+fn migrate_repo_ix(old_wallet: &Pubkey, new_wallet: &Pubkey, repo: &RepoMetrics) -> Instruction {
+    let name = padded(repo.name);
+    ix(
+        ghost_check::accounts::MigrateRepoBadge {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            old_dev_badge: dev_badge(old_wallet),
+            old_repo_state: repo_state(old_wallet, &name),
+            old_repo_badge: repo_badge(old_wallet, &name),
+            new_dev_state: dev_state(new_wallet),
+            new_dev_badge: dev_badge(new_wallet),
+            new_repo_state: repo_state(new_wallet, &name),
+            new_repo_badge: repo_badge(new_wallet, &name),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateRepoBadge {
+            repo_name_padded: name,
+        },
+    )
+}
+
+fn migrate_contribution_ix(old_wallet: &Pubkey, new_wallet: &Pubkey, repo: &str) -> Instruction {
+    let hashed_repo = hash_repo_full_name(repo.as_bytes());
+    ix(
+        ghost_check::accounts::MigrateContributionBadge {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            old_dev_badge: dev_badge(old_wallet),
+            old_contribution_state: contribution_state(old_wallet, &hashed_repo),
+            old_contribution_badge: contribution_badge(old_wallet, &hashed_repo),
+            new_dev_state: dev_state(new_wallet),
+            new_dev_badge: dev_badge(new_wallet),
+            new_contribution_state: contribution_state(new_wallet, &hashed_repo),
+            new_contribution_badge: contribution_badge(new_wallet, &hashed_repo),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateContributionBadge { hashed_repo },
+    )
+}
+
+// Address a record of `wallet` lives under once old_wallet moved to new_wallet
+fn forward(old_wallet: &Pubkey, new_wallet: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    if wallet == old_wallet {
+        *new_wallet
+    } else {
+        *wallet
+    }
+}
+
+fn migrate_vouch_ix(
+    old_wallet: &Pubkey,
+    new_wallet: &Pubkey,
+    voucher: &Pubkey,
+    target: &Pubkey,
+) -> Instruction {
+    let counterpart = if voucher == old_wallet {
+        target
+    } else {
+        voucher
+    };
+    ix(
+        ghost_check::accounts::MigrateVouchRecord {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            new_dev_state: dev_state(new_wallet),
+            counterpart_dev_state: dev_state(counterpart),
+            counterpart_dev_badge: dev_badge(counterpart),
+            old_vouch_record: vouch_record(voucher, target),
+            new_vouch_record: vouch_record(
+                &forward(old_wallet, new_wallet, voucher),
+                &forward(old_wallet, new_wallet, target),
+            ),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateVouchRecord {
+            voucher: *voucher,
+            target: *target,
+        },
+    )
+}
+
+fn migrate_tombstone_ix(
+    old_wallet: &Pubkey,
+    new_wallet: &Pubkey,
+    voucher: &Pubkey,
+    target: &Pubkey,
+) -> Instruction {
+    let counterpart = if voucher == old_wallet {
+        target
+    } else {
+        voucher
+    };
+    ix(
+        ghost_check::accounts::MigrateVouchTombstone {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            new_dev_state: dev_state(new_wallet),
+            counterpart_dev_state: dev_state(counterpart),
+            old_vouch_tombstone: vouch_tombstone(voucher, target),
+            new_vouch_tombstone: vouch_tombstone(
+                &forward(old_wallet, new_wallet, voucher),
+                &forward(old_wallet, new_wallet, target),
+            ),
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateVouchTombstone {
+            voucher: *voucher,
+            target: *target,
+        },
+    )
+}
+
+fn migrate_org_ix(old_wallet: &Pubkey, new_wallet: &Pubkey) -> Instruction {
+    let hashed_org = hashed(ORG);
+    ix(
+        ghost_check::accounts::MigrateOrgMembership {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            new_dev_state: dev_state(new_wallet),
+            org_state: org_state(&hashed_org),
+            org_badge: org_badge(&hashed_org),
+            old_org_membership: org_membership(&hashed_org, old_wallet),
+            old_member_badge: org_member_badge(&hashed_org, old_wallet),
+            new_org_membership: org_membership(&hashed_org, new_wallet),
+            new_member_badge: org_member_badge(&hashed_org, new_wallet),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateOrgMembership {},
+    )
+}
+
+fn finish_ix(old_wallet: &Pubkey, new_wallet: &Pubkey) -> Instruction {
+    ix(
+        ghost_check::accounts::FinishMigration {
+            new_wallet: *new_wallet,
+            ghost_config: ghost_config(),
+            migration: migration(old_wallet),
+            old_wallet: *old_wallet,
+            old_dev_state: dev_state(old_wallet),
+            old_dev_badge: dev_badge(old_wallet),
+            new_dev_state: dev_state(new_wallet),
+            new_dev_badge: dev_badge(new_wallet),
+            identity_claim: identity_claim(&hashed(USERNAME)),
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::FinishMigration {},
+    )
+}
+
+#[tokio::test]
+async fn migrate_wallet() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    let friend = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(3))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&old_wallet, USERNAME, &repo)
+        .await
+        .unwrap();
+    env.mint_dev_badge(&friend, "friend", DevMetrics::level(4))
+        .await
+        .unwrap();
+    let (old, new, friend_key) = (old_wallet.pubkey(), new_wallet.pubkey(), friend.pubkey());
+    env.send(&[env.vouch_ix(&old, &friend_key)], &[&old_wallet])
+        .await
+        .unwrap();
+    env.send(&[env.vouch_ix(&friend_key, &old)], &[&friend])
+        .await
+        .unwrap();
+
+    request_migration(&mut env, &old, &new_wallet).await;
+    let request: MigrationRequest = env.account(&migration(&old)).await;
+    assert_eq!(request.new_wallet, new);
+    assert!(!request.approved);
+
+    // The old wallet co-signs, no need to wait for the timelock
+    env.send(&[approve_ix(&old)], &[&old_wallet]).await.unwrap();
+    env.send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    // Repo badges must move before the old collection can go
+    let result = env.send(&[finish_ix(&old, &new)], &[&new_wallet]).await;
+    assert_error(result, GhostErrors::RepoBadgesRemaining);
+
+    env.send(&[migrate_repo_ix(&old, &new, &repo)], &[&new_wallet])
+        .await
+        .unwrap();
+    env.send(
+        &[
+            migrate_vouch_ix(&old, &new, &old, &friend_key),
+            migrate_vouch_ix(&old, &new, &friend_key, &old),
+        ],
+        &[&new_wallet],
+    )
+    .await
+    .unwrap();
+    env.send(&[finish_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    let state: DevState = env.account(&dev_state(&new)).await;
+    assert_eq!(state.hashed_username, hashed(USERNAME));
+    assert_eq!(state.reputation_level, 3);
+    assert_eq!(state.verified_repos, 1);
+    assert_eq!(state.vouch_count, 1);
+    assert_eq!(state.vouch_score, 4);
+    assert_eq!(state.vouches_given, 1);
+    assert!(!env.exists(&dev_state(&old)).await);
+    assert!(env.exists(&repo_state(&new, &padded(repo.name))).await);
+
+    let given: VouchRecord = env.account(&vouch_record(&new, &friend_key)).await;
+    assert_eq!(given.voucher, new);
+    let received: VouchRecord = env.account(&vouch_record(&friend_key, &new)).await;
+    assert_eq!(received.target, new);
+
+    let claim: IdentityClaim = env.account(&identity_claim(&hashed(USERNAME))).await;
+    assert_eq!(claim.wallet, new);
+    let request: MigrationRequest = env.account(&migration(&old)).await;
+    assert!(request.completed);
+}
+
+#[tokio::test]
+async fn migrate_wallet_merges_duplicate_vouches() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    let (friend, other, leaver) = (env.wallet().await, env.wallet().await, env.wallet().await);
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(3))
+        .await
+        .unwrap();
+    for (wallet, username) in [(&friend, "friend"), (&other, "other"), (&leaver, "leaver")] {
+        env.mint_dev_badge(wallet, username, DevMetrics::level(4))
+            .await
+            .unwrap();
+    }
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+    let (friend_key, other_key, leaver_key) = (friend.pubkey(), other.pubkey(), leaver.pubkey());
+    env.send(&[env.vouch_ix(&friend_key, &old)], &[&friend])
+        .await
+        .unwrap();
+    env.send(
+        &[
+            env.vouch_ix(&old, &other_key),
+            env.vouch_ix(&old, &leaver_key),
+            env.revoke_vouch_ix(&old, &leaver_key),
+        ],
+        &[&old_wallet],
+    )
+    .await
+    .unwrap();
+
+    // Vouches to or from the old wallet would be left behind once its records moved
+    request_migration(&mut env, &old, &new_wallet).await;
+    let result = env
+        .send(&[env.vouch_ix(&leaver_key, &old)], &[&leaver])
+        .await;
+    assert_error(result, GhostErrors::MigrationPending);
+    let result = env
+        .send(&[env.vouch_ix(&old, &friend_key)], &[&old_wallet])
+        .await;
+    assert_error(result, GhostErrors::MigrationPending);
+
+    env.send(&[approve_ix(&old)], &[&old_wallet]).await.unwrap();
+    env.send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    // The same vouches land on the new wallet before the old records are forwarded
+    env.send(&[env.vouch_ix(&friend_key, &new)], &[&friend])
+        .await
+        .unwrap();
+    env.send(
+        &[
+            env.vouch_ix(&new, &other_key),
+            env.vouch_ix(&new, &leaver_key),
+            env.revoke_vouch_ix(&new, &leaver_key),
+        ],
+        &[&new_wallet],
+    )
+    .await
+    .unwrap();
+
+    let result = env.send(&[finish_ix(&old, &new)], &[&new_wallet]).await;
+    assert_error(result, GhostErrors::VouchesNotMigrated);
+
+    env.send(
+        &[
+            migrate_vouch_ix(&old, &new, &friend_key, &old),
+            migrate_vouch_ix(&old, &new, &old, &other_key),
+        ],
+        &[&new_wallet],
+    )
+    .await
+    .unwrap();
+    assert!(!env.exists(&vouch_record(&friend_key, &old)).await);
+    assert!(!env.exists(&vouch_record(&old, &other_key)).await);
+    let friend_state: DevState = env.account(&dev_state(&friend_key)).await;
+    assert_eq!(friend_state.vouches_given, 1);
+    let other_state: DevState = env.account(&dev_state(&other_key)).await;
+    assert_eq!(other_state.vouch_count, 1);
+    assert_eq!(other_state.vouch_score, 3);
+
+    // Tombstones block finishing too, they carry the anti flip-flop rule over
+    let result = env.send(&[finish_ix(&old, &new)], &[&new_wallet]).await;
+    assert_error(result, GhostErrors::VouchesNotMigrated);
+    env.send(
+        &[migrate_tombstone_ix(&old, &new, &old, &leaver_key)],
+        &[&new_wallet],
+    )
+    .await
+    .unwrap();
+    assert!(!env.exists(&vouch_tombstone(&old, &leaver_key)).await);
+    let leaver_state: DevState = env.account(&dev_state(&leaver_key)).await;
+    assert_eq!(leaver_state.vouch_tombstones, 1);
+
+    env.send(&[finish_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+    let state: DevState = env.account(&dev_state(&new)).await;
+    assert_eq!(state.vouch_count, 1);
+    assert_eq!(state.vouch_score, 4);
+    assert_eq!(state.vouches_given, 1);
+    assert_eq!(state.vouch_tombstones, 1);
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.vouches_count, 2);
+}
+
+#[tokio::test]
+async fn migrate_wallet_moves_contribution_badges() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    let repo = "solana-labs/solana";
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_contribution_badge(&old_wallet, USERNAME, repo, (4, 37))
+        .await
+        .unwrap();
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+
+    request_migration(&mut env, &old, &new_wallet).await;
+    env.send(&[approve_ix(&old)], &[&old_wallet]).await.unwrap();
+    env.send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    // Contribution badges live in the old collection too
+    let result = env.send(&[finish_ix(&old, &new)], &[&new_wallet]).await;
+    assert_error(result, GhostErrors::ContributionBadgesRemaining);
+
+    env.send(&[migrate_contribution_ix(&old, &new, repo)], &[&new_wallet])
+        .await
+        .unwrap();
+    env.send(&[finish_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    let hashed_repo = hash_repo_full_name(repo.as_bytes());
+    let state: ContributionState = env.account(&contribution_state(&new, &hashed_repo)).await;
+    assert_eq!(state.contributor, new);
+    assert_eq!(state.dev_badge, dev_badge(&new));
+    assert_eq!(state.repo_full_name, repo.as_bytes());
+    assert_eq!((state.prs_merged, state.commits), (4, 37));
+    assert!(env.exists(&contribution_badge(&new, &hashed_repo)).await);
+    assert!(!env.exists(&contribution_state(&old, &hashed_repo)).await);
+    let counts: DevState = env.account(&dev_state(&new)).await;
+    assert_eq!(counts.contribution_badges, 1);
+}
+
+#[tokio::test]
+async fn migration_timelock() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+
+    request_migration(&mut env, &old, &new_wallet).await;
+    let result = env
+        .send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await;
+    assert_error(result, GhostErrors::MigrationLocked);
+
+    env.advance_clock(MIGRATION_TIMELOCK).await;
+    env.send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    // Too late to cancel once the dev badge moved
+    let result = env.send(&[cancel_ix(&old, &new)], &[&old_wallet]).await;
+    assert_error(result, GhostErrors::MigrationInProgress);
+}
+
+#[tokio::test]
+async fn cancel_migration() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+
+    request_migration(&mut env, &old, &new_wallet).await;
+
+    // A paused protocol blocks approving, the old wallet can still cancel
+    let admin = env.admin.insecure_clone();
+    let pause = ix(
+        ghost_check::accounts::SetPaused {
+            admin: admin.pubkey(),
+            ghost_config: ghost_config(),
+        },
+        ghost_check::instruction::SetPaused { paused: true },
+    );
+    env.send(&[pause], &[&admin]).await.unwrap();
+    let result = env.send(&[approve_ix(&old)], &[&old_wallet]).await;
+    assert_error(result, GhostErrors::ProtocolPaused);
+
+    env.send(&[cancel_ix(&old, &new)], &[&old_wallet])
+        .await
+        .unwrap();
+    assert!(!env.exists(&migration(&old)).await);
+
+    let result = env
+        .send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await;
+    assert_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn migrating_wallet_is_frozen() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    let authority = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&old_wallet, USERNAME, &repo)
+        .await
+        .unwrap();
+    env.mint_org_badge(&authority).await.unwrap();
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+
+    request_migration(&mut env, &old, &new_wallet).await;
+
+    // Nothing changes on the old dev state while it is being moved
+    let result = env
+        .update_dev_badge(&old_wallet, USERNAME, DevMetrics::level(3), 2)
+        .await;
+    assert_error(result, GhostErrors::MigrationInProgress);
+    let result = env
+        .mint_repo_badge(&old_wallet, USERNAME, &RepoMetrics::new("anchor"))
+        .await;
+    assert_error(result, GhostErrors::MigrationInProgress);
+    let username = hashed(USERNAME);
+    let issued_at = env.now().await;
+    let message = repo.message(&old, &username, issued_at, 2);
+    let instructions = [
+        ed25519_ix(&env.backend, &message),
+        repo.update_ix(&old, &username, issued_at, 2),
+    ];
+    let result = env.send(&instructions, &[&old_wallet]).await;
+    assert_error(result, GhostErrors::MigrationInProgress);
+    let result = env
+        .mint_contribution_badge(&old_wallet, USERNAME, "solana-labs/solana", (4, 37))
+        .await;
+    assert_error(result, GhostErrors::MigrationInProgress);
+    let result = env
+        .send(
+            &[link_org_member_ix(&old, &authority.pubkey())],
+            &[&old_wallet, &authority],
+        )
+        .await;
+    assert_error(result, GhostErrors::MigrationInProgress);
+
+    // Verifiers wait for the new wallet, a query still reports the old one
+    let result = env
+        .view::<DevVerification>(&[env.verify_dev_ix(&old, 1, None)])
+        .await;
+    assert_view_error(result, GhostErrors::MigrationInProgress);
+    let queried: DevVerification = env.view(&[env.query_dev_ix(&old)]).await.unwrap();
+    assert_eq!(queried.reputation_level, 2);
+
+    env.send(&[cancel_ix(&old, &new)], &[&old_wallet])
+        .await
+        .unwrap();
+    let verified: DevVerification = env.view(&[env.verify_dev_ix(&old, 1, None)]).await.unwrap();
+    assert_eq!(verified.dev_addr, old);
+    env.update_dev_badge(&old_wallet, USERNAME, DevMetrics::level(3), 2)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn migrate_wallet_moves_org_memberships() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    let authority = env.wallet().await;
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_org_badge(&authority).await.unwrap();
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+    env.send(
+        &[link_org_member_ix(&old, &authority.pubkey())],
+        &[&old_wallet, &authority],
+    )
+    .await
+    .unwrap();
+    let linked: OrgMembership = env.account(&org_membership(&hashed(ORG), &old)).await;
+
+    request_migration(&mut env, &old, &new_wallet).await;
+    env.send(&[approve_ix(&old)], &[&old_wallet]).await.unwrap();
+    env.send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+    env.send(&[finish_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+
+    // Memberships aren't in the dev collection, they still move once the migration is completed
+    env.send(&[migrate_org_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+    let membership: OrgMembership = env.account(&org_membership(&hashed(ORG), &new)).await;
+    assert_eq!(membership.dev, new);
+    assert_eq!(membership.dev_badge, dev_badge(&new));
+    assert_eq!(
+        membership.member_badge,
+        org_member_badge(&hashed(ORG), &new)
+    );
+    assert_eq!(membership.linked_at, linked.linked_at);
+    assert!(env.exists(&membership.member_badge).await);
+    assert!(!env.exists(&org_membership(&hashed(ORG), &old)).await);
+    assert!(env.burned(&linked.member_badge).await);
+    let state: OrgState = env.account(&org_state(&hashed(ORG))).await;
+    assert_eq!(state.linked_members, 1);
+}
+
+#[tokio::test]
+async fn migrate_org_membership_merges_duplicate_links() {
+    let mut env = TestEnv::new().await;
+    let old_wallet = env.wallet().await;
+    let new_wallet = env.wallet().await;
+    let authority = env.wallet().await;
+    env.mint_dev_badge(&old_wallet, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_org_badge(&authority).await.unwrap();
+    let (old, new) = (old_wallet.pubkey(), new_wallet.pubkey());
+    env.send(
+        &[link_org_member_ix(&old, &authority.pubkey())],
+        &[&old_wallet, &authority],
+    )
+    .await
+    .unwrap();
+
+    request_migration(&mut env, &old, &new_wallet).await;
+    env.send(&[approve_ix(&old)], &[&old_wallet]).await.unwrap();
+    env.send(&[migrate_dev_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+    // The org linked the new wallet on its own
+    env.send(
+        &[link_org_member_ix(&new, &authority.pubkey())],
+        &[&new_wallet, &authority],
+    )
+    .await
+    .unwrap();
+    let linked: OrgMembership = env.account(&org_membership(&hashed(ORG), &new)).await;
+
+    env.send(&[migrate_org_ix(&old, &new)], &[&new_wallet])
+        .await
+        .unwrap();
+    let membership: OrgMembership = env.account(&org_membership(&hashed(ORG), &new)).await;
+    assert_eq!(membership.linked_at, linked.linked_at);
+    assert!(!env.exists(&org_membership(&hashed(ORG), &old)).await);
+    assert!(env.burned(&org_member_badge(&hashed(ORG), &old)).await);
+    let state: OrgState = env.account(&org_state(&hashed(ORG))).await;
+    assert_eq!(state.linked_members, 1);
+}
+
+fn migrate_account_ix(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    ix(
+        ghost_check::accounts::MigrateAccount {
+            payer: *payer,
+            account: *account,
+            system_program: system_program::ID,
+        },
+        ghost_check::instruction::MigrateAccount {},
+    )
+}
+
+// Replaces `address` with an account holding `data`, padded to `len` like an Anchor init
+fn write_legacy(env: &mut TestEnv, address: &Pubkey, mut data: Vec<u8>, len: usize) {
+    data.resize(len, 0);
+    env.ctx.set_account(
+        address,
+        &AccountSharedData::from(Account {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: ghost_check::ID,
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+}
+
+#[tokio::test]
+async fn migrate_account_upgrades_legacy_layouts() {
+    let mut env = TestEnv::new().await;
+    let payer = env.wallet().await;
+    let (voucher, target) = (Keypair::new().pubkey(), Keypair::new().pubkey());
+    let address = vouch_record(&voucher, &target);
+
+    // A vouch record written before the version byte existed
+    let mut data = VouchRecord::DISCRIMINATOR.to_vec();
+    (voucher, 3u8, target, 1_700_000_000i64, 255u8)
+        .serialize(&mut data)
+        .unwrap();
+    let len = data.len();
+    write_legacy(&mut env, &address, data, len);
+
+    let upgrade = migrate_account_ix(&payer.pubkey(), &address);
+    env.send(std::slice::from_ref(&upgrade), &[&payer])
+        .await
+        .unwrap();
+
+    let record: VouchRecord = env.account(&address).await;
+    assert_eq!(record.version, VouchRecord::VERSION);
+    assert_eq!(record.voucher, voucher);
+    assert_eq!(record.voucher_level, 3);
+    assert_eq!(record.target, target);
+    assert_eq!(record.timestamp, 1_700_000_000);
+
+    let result = env.send(&[upgrade], &[&payer]).await;
+    assert_error(result, GhostErrors::AccountUpToDate);
+
+    // Accounts versioned from the start have nothing to upgrade yet
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+    let claim: IdentityClaim = env.account(&identity_claim(&hashed(USERNAME))).await;
+    assert_eq!(claim.version, IdentityClaim::VERSION);
+    let upgrade = migrate_account_ix(&payer.pubkey(), &identity_claim(&hashed(USERNAME)));
+    let result = env.send(&[upgrade], &[&payer]).await;
+    assert_error(result, GhostErrors::AccountUpToDate);
+}
+
+#[tokio::test]
+async fn migrate_account_upgrades_baseline_config() {
+    let mut env = TestEnv::new().await;
+    let payer = env.wallet().await;
+    let current: GhostConfig = env.account(&ghost_config()).await;
+
+    // admin, backend_pubkey, dev_badges_minted, repo_badges_minted, vouches_count, bump
+    let mut data = GhostConfig::DISCRIMINATOR.to_vec();
+    (
+        current.admin,
+        current.backend_pubkey,
+        7u64,
+        3u32,
+        2u32,
+        current.bump,
+    )
+        .serialize(&mut data)
+        .unwrap();
+    write_legacy(&mut env, &ghost_config(), data, 8 + 32 + 32 + 8 + 4 + 4 + 1);
+
+    env.send(
+        &[migrate_account_ix(&payer.pubkey(), &ghost_config())],
+        &[&payer],
+    )
+    .await
+    .unwrap();
+
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.version, GhostConfig::VERSION);
+    assert_eq!(config.admin, current.admin);
+    assert_eq!(config.backend_pubkey, current.backend_pubkey);
+    assert_eq!(config.dev_badges_minted, 7);
+    assert_eq!(config.repo_badges_minted, 3);
+    assert_eq!(config.vouches_count, 2);
+    assert_eq!(config.pending_admin, None);
+    assert!(!config.paused);
+    assert_eq!(config.min_voucher_level, 2);
+    assert_eq!(config.oracle_threshold, 1);
+    assert!(config.max_attestation_age > 0);
+
+    assert_eq!(config.metadata_base_uri, "");
+
+    // Badges can't point at an empty base URI, the rest of the defaults keep the protocol usable
+    let dev = env.wallet().await;
+    let result = env
+        .mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await;
+    assert_error(result, GhostErrors::MetadataUriNotSet);
+
+    let admin = env.admin.insecure_clone();
+    let set_uri = ix(
+        ghost_check::accounts::UpdateConfig {
+            admin: admin.pubkey(),
+            ghost_config: ghost_config(),
+        },
+        ghost_check::instruction::UpdateConfig {
+            max_attestation_age: None,
+            min_voucher_level: None,
+            vouch_budget: None,
+            metadata_base_uri: Some(METADATA_BASE_URI.to_string()),
+            min_update_interval: None,
+            max_staleness: None,
+        },
+    );
+    env.send(&[set_uri], &[&admin]).await.unwrap();
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(2))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn migrate_account_upgrades_baseline_badges() {
+    let mut env = TestEnv::new().await;
+    let payer = env.wallet().await;
+    let dev = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&dev, USERNAME, DevMetrics::level(3))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&dev, USERNAME, &repo).await.unwrap();
+    let dev_address = dev_state(&dev.pubkey());
+    let repo_address = repo_state(&dev.pubkey(), &padded(repo.name));
+    let dev_before: DevState = env.account(&dev_address).await;
+    let repo_before: RepoState = env.account(&repo_address).await;
+
+    // Baseline DevState: no vouch_score, vouches_given or nonce
+    let s = &dev_before;
+    let mut data = DevState::DISCRIMINATOR.to_vec();
+    (
+        s.dev_addr,
+        s.asset_address,
+        s.hashed_username,
+        s.repo_count,
+        s.owned_repo_count,
+        s.total_stars,
+        s.total_commits,
+        s.prs_merged,
+    )
+        .serialize(&mut data)
+        .unwrap();
+    (
+        s.issues_closed,
+        s.followers,
+        s.account_age_days,
+        s.reputation_level,
+        s.verified_repos,
+        3u64, // vouch_count
+        s.last_updated,
+        s.bump,
+        s.collection_asset_bump,
+    )
+        .serialize(&mut data)
+        .unwrap();
+    let len = data.len();
+    write_legacy(&mut env, &dev_address, data, len);
+
+    // Baseline RepoState: no nonce, Vec fields padded to their max_len
+    let r = &repo_before;
+    let mut data = RepoState::DISCRIMINATOR.to_vec();
+    (
+        r.owner,
+        r.dev_badge,
+        r.hashed_username,
+        r.repo_name.clone(),
+        r.stars,
+        r.commits,
+        r.forks,
+        r.open_issues,
+        r.is_fork,
+        r.lang1.clone(),
+        r.lang2.clone(),
+    )
+        .serialize(&mut data)
+        .unwrap();
+    (r.last_updated, r.bump, r.badge_bump)
+        .serialize(&mut data)
+        .unwrap();
+    let baseline_space = 32 * 3 + (4 + 50) + 4 * 4 + 1 + (4 + 10) * 2 + 8 + 2;
+    write_legacy(&mut env, &repo_address, data, 8 + baseline_space);
+
+    env.send(
+        &[
+            migrate_account_ix(&payer.pubkey(), &dev_address),
+            migrate_account_ix(&payer.pubkey(), &repo_address),
+        ],
+        &[&payer],
+    )
+    .await
+    .unwrap();
+
+    let dev_after: DevState = env.account(&dev_address).await;
+    assert_eq!(dev_after.version, DevState::VERSION);
+    assert_eq!(dev_after.hashed_username, dev_before.hashed_username);
+    assert_eq!(dev_after.reputation_level, 3);
+    assert_eq!(dev_after.verified_repos, 1);
+    assert_eq!(dev_after.last_updated, dev_before.last_updated);
+    assert_eq!(
+        dev_after.collection_asset_bump,
+        dev_before.collection_asset_bump
+    );
+    assert_eq!(dev_after.vouch_count, 3);
+    // Each baseline vouch weighs the lowest level that could vouch
+    assert_eq!(dev_after.vouch_score, 6);
+    assert_eq!(dev_after.vouches_given, DevState::UNCOUNTED_VOUCHES_GIVEN);
+    assert_eq!(dev_after.nonce, 0);
+
+    // Over any budget until the admin sets the real count, once
+    let target = env.wallet().await;
+    env.mint_dev_badge(&target, "vouched-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    let vouch = env.vouch_ix(&dev.pubkey(), &target.pubkey());
+    let result = env.send(std::slice::from_ref(&vouch), &[&dev]).await;
+    assert_error(result, GhostErrors::VouchBudgetExhausted);
+
+    let admin = env.admin.insecure_clone();
+    let settle = |vouches_given| {
+        ix(
+            ghost_check::accounts::SettleVouchesGiven {
+                admin: admin.pubkey(),
+                ghost_config: ghost_config(),
+                dev_state: dev_address,
+            },
+            ghost_check::instruction::SettleVouchesGiven {
+                _dev_addr: dev.pubkey().to_bytes(),
+                vouches_given,
+            },
+        )
+    };
+    env.send(&[settle(0)], &[&admin]).await.unwrap();
+    let settled: DevState = env.account(&dev_address).await;
+    assert_eq!(settled.vouches_given, 0);
+    env.send(&[vouch], &[&dev]).await.unwrap();
+    let result = env.send(&[settle(5)], &[&admin]).await;
+    assert_error(result, GhostErrors::VouchesGivenCounted);
+
+    let repo_after: RepoState = env.account(&repo_address).await;
+    assert_eq!(repo_after.version, RepoState::VERSION);
+    assert_eq!(repo_after.repo_name, repo_before.repo_name);
+    assert_eq!(repo_after.stars, repo.stars);
+    assert_eq!(repo_after.lang2, repo_before.lang2);
+    assert_eq!(repo_after.badge_bump, repo_before.badge_bump);
+    assert_eq!(repo_after.nonce, 0);
+}
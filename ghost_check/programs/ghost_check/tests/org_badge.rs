@@ -0,0 +1,100 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    state::{OrgMembership, OrgState},
+};
+use solana_sdk::signer::Signer;
+
+#[tokio::test]
+async fn mint_org_badge_once_per_org() {
+    let mut env = TestEnv::new().await;
+    let authority = env.wallet().await;
+    env.mint_org_badge(&authority).await.unwrap();
+
+    let state: OrgState = env.account(&org_state(&hashed(ORG))).await;
+    assert_eq!(state.authority, authority.pubkey());
+    assert_eq!(state.org_badge, org_badge(&hashed(ORG)));
+    assert_eq!(state.public_repos, 12);
+    assert_eq!(state.member_count, 8);
+    assert_eq!(state.linked_members, 0);
+    assert!(env.exists(&org_badge(&hashed(ORG))).await);
+
+    // The org_state PDA already exists
+    let other = env.wallet().await;
+    let result = env.mint_org_badge(&other).await;
+    assert_error(result, 0u32);
+}
+
+#[tokio::test]
+async fn link_org_member() {
+    let mut env = TestEnv::new().await;
+    let authority = env.wallet().await;
+    let dev = env.wallet().await;
+    env.mint_org_badge(&authority).await.unwrap();
+    env.mint_dev_badge(&dev, "member", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    env.send(
+        &[link_org_member_ix(&dev.pubkey(), &authority.pubkey())],
+        &[&dev, &authority],
+    )
+    .await
+    .unwrap();
+
+    let membership: OrgMembership = env
+        .account(&org_membership(&hashed(ORG), &dev.pubkey()))
+        .await;
+    assert_eq!(membership.dev, dev.pubkey());
+    assert_eq!(membership.dev_badge, dev_badge(&dev.pubkey()));
+    assert_eq!(
+        membership.member_badge,
+        org_member_badge(&hashed(ORG), &dev.pubkey())
+    );
+    let state: OrgState = env.account(&org_state(&hashed(ORG))).await;
+    assert_eq!(state.linked_members, 1);
+
+    // Member badges have their own metadata, not the org's
+    let (_, uri) = env.core_metadata(&membership.member_badge).await;
+    assert_eq!(
+        uri,
+        format!(
+            "{METADATA_BASE_URI}/metadata/org_member/{}",
+            membership.member_badge
+        )
+    );
+
+    // One membership per dev and org
+    let result = env
+        .send(
+            &[link_org_member_ix(&dev.pubkey(), &authority.pubkey())],
+            &[&dev, &authority],
+        )
+        .await;
+    assert_error(result, 0u32);
+}
+
+#[tokio::test]
+async fn only_the_org_authority_links_members() {
+    let mut env = TestEnv::new().await;
+    let authority = env.wallet().await;
+    let dev = env.wallet().await;
+    env.mint_org_badge(&authority).await.unwrap();
+    env.mint_dev_badge(&dev, "member", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    // The dev can't approve its own membership
+    let result = env
+        .send(&[link_org_member_ix(&dev.pubkey(), &dev.pubkey())], &[&dev])
+        .await;
+    assert_error(result, GhostErrors::OrgAuthorityMismatch);
+    assert!(
+        !env.exists(&org_membership(&hashed(ORG), &dev.pubkey()))
+            .await
+    );
+}
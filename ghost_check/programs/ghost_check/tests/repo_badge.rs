@@ -0,0 +1,225 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    state::{DevState, GhostConfig, RepoState},
+};
+use solana_sdk::signer::Signer;
+
+#[tokio::test]
+async fn mint_repo_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    env.mint_repo_badge(&dev, "ghost-dev", &repo).await.unwrap();
+
+    let state: RepoState = env
+        .account(&repo_state(&dev.pubkey(), &padded(repo.name)))
+        .await;
+    assert_eq!(state.owner, dev.pubkey());
+    assert_eq!(state.dev_badge, dev_badge(&dev.pubkey()));
+    assert_eq!(state.hashed_username, hashed("ghost-dev"));
+    // Stored trimmed, the padded name only serves as seed
+    assert_eq!(state.repo_name, b"ghost-check");
+    assert_eq!(state.stars, repo.stars);
+    assert_eq!(state.lang1, repo.lang1);
+    assert_eq!(state.nonce, 1);
+    assert!(
+        env.exists(&repo_badge(&dev.pubkey(), &padded(repo.name)))
+            .await
+    );
+
+    let dev_state: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(dev_state.verified_repos, 1);
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.repo_badges_minted, 1);
+}
+
+#[tokio::test]
+async fn forked_repos_get_no_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    let fork = RepoMetrics {
+        is_fork: 1,
+        ..RepoMetrics::new("anchor")
+    };
+    let result = env.mint_repo_badge(&dev, "ghost-dev", &fork).await;
+    assert_error(result, GhostErrors::ForkedRepo);
+    assert!(
+        !env.exists(&repo_state(&dev.pubkey(), &padded(fork.name)))
+            .await
+    );
+}
+
+#[tokio::test]
+async fn repo_badge_fields_cant_be_shifted() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    let signed = RepoMetrics {
+        lang1: b"foo".to_vec(),
+        lang2: b"Rust".to_vec(),
+        ..RepoMetrics::new("ghost-check")
+    };
+    // Same bytes once concatenated, only the boundary between the languages moved
+    let shifted = RepoMetrics {
+        lang1: b"fooR".to_vec(),
+        lang2: b"ust".to_vec(),
+        ..signed.clone()
+    };
+    let username = hashed("ghost-dev");
+    let issued_at = env.now().await;
+    let message = signed.message(&dev.pubkey(), &username, issued_at, 1);
+    let mint = shifted.mint_ix(&dev.pubkey(), &username, issued_at, 1);
+
+    let instructions = [ed25519_ix(&env.backend, &message), mint];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, GhostErrors::SignedMessageMismatch);
+}
+
+#[tokio::test]
+async fn repo_badge_requires_the_devs_own_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let other = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+
+    // No dev badge minted yet
+    let result = env.mint_repo_badge(&dev, "ghost-dev", &repo).await;
+    assert_error(result, ErrorCode::AccountNotInitialized);
+
+    env.mint_dev_badge(&other, "other-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    let username = hashed("ghost-dev");
+    let issued_at = env.now().await;
+    let message = repo.message(&dev.pubkey(), &username, issued_at, 1);
+    let mut mint = repo.mint_ix(&dev.pubkey(), &username, issued_at, 1);
+    mint.accounts[2].pubkey = dev_state(&other.pubkey());
+
+    let instructions = [ed25519_ix(&env.backend, &message), mint];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn update_repo_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&dev, "ghost-dev", &repo).await.unwrap();
+
+    let username = hashed("ghost-dev");
+    let grown = RepoMetrics {
+        stars: 420,
+        commits: 300,
+        ..repo.clone()
+    };
+    let issued_at = env.now().await;
+    let message = grown.message(&dev.pubkey(), &username, issued_at, 2);
+    let instructions = [
+        ed25519_ix(&env.backend, &message),
+        grown.update_ix(&dev.pubkey(), &username, issued_at, 2),
+    ];
+    env.send(&instructions, &[&dev]).await.unwrap();
+
+    let state: RepoState = env
+        .account(&repo_state(&dev.pubkey(), &padded(repo.name)))
+        .await;
+    assert_eq!(state.stars, 420);
+    assert_eq!(state.commits, 300);
+    assert_eq!(state.nonce, 2);
+    let badge = repo_badge(&dev.pubkey(), &padded(repo.name));
+    let (name, uri) = env.core_metadata(&badge).await;
+    assert_eq!(name, repo.name);
+    assert_eq!(uri, format!("{METADATA_BASE_URI}/metadata/repo/{badge}"));
+
+    // Replaying the same nonce with older stats is refused
+    let message = repo.message(&dev.pubkey(), &username, issued_at, 2);
+    let instructions = [
+        ed25519_ix(&env.backend, &message),
+        repo.update_ix(&dev.pubkey(), &username, issued_at, 2),
+    ];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, GhostErrors::NonceAlreadyUsed);
+}
+
+#[tokio::test]
+async fn repo_badges_keep_the_dev_badge_identity() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+
+    // Attested for another GitHub account than the dev badge's
+    let result = env.mint_repo_badge(&dev, "someone-else", &repo).await;
+    assert_error(result, GhostErrors::IdentityMismatch);
+
+    env.mint_repo_badge(&dev, "ghost-dev", &repo).await.unwrap();
+    let other = hashed("someone-else");
+    let issued_at = env.now().await;
+    let message = repo.message(&dev.pubkey(), &other, issued_at, 2);
+    let instructions = [
+        ed25519_ix(&env.backend, &message),
+        repo.update_ix(&dev.pubkey(), &other, issued_at, 2),
+    ];
+    let result = env.send(&instructions, &[&dev]).await;
+    assert_error(result, GhostErrors::IdentityMismatch);
+
+    let state: RepoState = env
+        .account(&repo_state(&dev.pubkey(), &padded(repo.name)))
+        .await;
+    assert_eq!(state.hashed_username, hashed("ghost-dev"));
+}
+
+#[tokio::test]
+async fn burn_repo_badge() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(2))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&dev, "ghost-dev", &repo).await.unwrap();
+
+    // Core only burns an empty collection
+    let result = env
+        .send(&[burn_dev_badge_ix(&dev.pubkey(), "ghost-dev")], &[&dev])
+        .await;
+    assert_error(result, GhostErrors::RepoBadgesRemaining);
+
+    env.send(&[repo.burn_ix(&dev.pubkey())], &[&dev])
+        .await
+        .unwrap();
+    assert!(
+        !env.exists(&repo_state(&dev.pubkey(), &padded(repo.name)))
+            .await
+    );
+    let state: DevState = env.account(&dev_state(&dev.pubkey())).await;
+    assert_eq!(state.verified_repos, 0);
+
+    env.send(&[burn_dev_badge_ix(&dev.pubkey(), "ghost-dev")], &[&dev])
+        .await
+        .unwrap();
+    assert!(!env.exists(&dev_state(&dev.pubkey())).await);
+}
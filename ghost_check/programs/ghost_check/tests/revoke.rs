@@ -0,0 +1,178 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    state::{IdentityClaim, Revocation},
+    DevVerification,
+};
+use solana_sdk::{account::AccountSharedData, pubkey::Pubkey, signer::Signer};
+
+// BaseCollectionV1 without any plugin, as dev collections were minted before the burn delegate.
+// `current_size` badges are still in it
+fn collection_without_plugins(update_authority: &Pubkey, current_size: u32) -> Vec<u8> {
+    let name = b"Dev_Badge";
+    let mut data = vec![5]; // Key::CollectionV1
+    data.extend_from_slice(update_authority.as_ref());
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name);
+    data.extend_from_slice(&0u32.to_le_bytes()); // Empty uri
+    data.extend_from_slice(&current_size.to_le_bytes()); // num_minted
+    data.extend_from_slice(&current_size.to_le_bytes()); // current_size
+    data
+}
+
+#[tokio::test]
+async fn revoke_dev_badge() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let dev = env.wallet().await;
+    let first = RepoMetrics::new("ghost-check");
+    let second = RepoMetrics::new("grant-gate");
+    env.mint_dev_badge(&dev, "sybil", DevMetrics::level(3))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&dev, "sybil", &first).await.unwrap();
+    env.mint_repo_badge(&dev, "sybil", &second).await.unwrap();
+
+    // Every repo badge must go, otherwise Core refuses to burn the collection
+    let result = env
+        .send(
+            &[revoke_ix(&admin.pubkey(), &dev.pubkey(), &[&first])],
+            &[&admin],
+        )
+        .await;
+    assert_error(result, GhostErrors::RepoBadgesRemaining);
+
+    env.send(
+        &[revoke_ix(
+            &admin.pubkey(),
+            &dev.pubkey(),
+            &[&first, &second],
+        )],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let revoked: Revocation = env.account(&revocation(&dev.pubkey())).await;
+    assert_eq!(revoked.dev_addr, dev.pubkey());
+    assert_eq!(revoked.hashed_username, hashed("sybil"));
+    assert_eq!(revoked.reason, REASON_SYBIL);
+    assert_eq!(revoked.revoked_by, admin.pubkey());
+    assert!(!env.exists(&dev_state(&dev.pubkey())).await);
+    for repo in [&first, &second] {
+        assert!(
+            !env.exists(&repo_state(&dev.pubkey(), &padded(repo.name)))
+                .await
+        );
+    }
+    // The claim stays, the identity can't start over on another wallet
+    let claim: IdentityClaim = env.account(&identity_claim(&hashed("sybil"))).await;
+    assert_eq!(claim.wallet, dev.pubkey());
+}
+
+#[tokio::test]
+async fn revoked_devs_fail_verification_and_minting() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let dev = env.wallet().await;
+    env.mint_dev_badge(&dev, "sybil", DevMetrics::level(3))
+        .await
+        .unwrap();
+    env.send(&[revoke_ix(&admin.pubkey(), &dev.pubkey(), &[])], &[&admin])
+        .await
+        .unwrap();
+
+    // A query reports the revocation instead of failing
+    let query: DevVerification = env.view(&[env.query_dev_ix(&dev.pubkey())]).await.unwrap();
+    assert!(query.revoked);
+    assert_eq!(query.reputation_level, 0);
+
+    let result = env
+        .view::<DevVerification>(&[env.verify_dev_ix(&dev.pubkey(), 1, None)])
+        .await;
+    assert_view_error(result, GhostErrors::DevRevoked);
+
+    let result = env
+        .mint_dev_badge(&dev, "sybil-again", DevMetrics::level(3))
+        .await;
+    assert_error(result, GhostErrors::DevRevoked);
+
+    let other = env.wallet().await;
+    let result = env
+        .mint_dev_badge(&other, "sybil", DevMetrics::level(3))
+        .await;
+    assert_error(result, 0u32);
+}
+
+#[tokio::test]
+async fn only_the_admin_revokes() {
+    let mut env = TestEnv::new().await;
+    let dev = env.wallet().await;
+    let intruder = env.wallet().await;
+    env.mint_dev_badge(&dev, "ghost-dev", DevMetrics::level(3))
+        .await
+        .unwrap();
+
+    let result = env
+        .send(
+            &[revoke_ix(&intruder.pubkey(), &dev.pubkey(), &[])],
+            &[&intruder],
+        )
+        .await;
+    assert_error(result, GhostErrors::AdminMismatch);
+    assert!(env.exists(&dev_state(&dev.pubkey())).await);
+}
+
+#[tokio::test]
+async fn collections_without_burn_delegate_still_burn() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let dev = env.wallet().await;
+    let repo = RepoMetrics::new("ghost-check");
+    env.mint_dev_badge(&dev, "sybil", DevMetrics::level(3))
+        .await
+        .unwrap();
+    env.mint_repo_badge(&dev, "sybil", &repo).await.unwrap();
+
+    // Core only adds permanent plugins at creation, older collections never get the delegate
+    let address = dev_badge(&dev.pubkey());
+    let mut collection = env
+        .ctx
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    collection.data = collection_without_plugins(&ghost_config(), 1);
+    env.ctx
+        .set_account(&address, &AccountSharedData::from(collection));
+
+    // The frozen repo badge needs its owner to burn, the admin alone can't
+    let result = env
+        .send(
+            &[revoke_ix(&admin.pubkey(), &dev.pubkey(), &[&repo])],
+            &[&admin],
+        )
+        .await;
+    assert_error(result, GhostErrors::BurnDelegateMissing);
+
+    // ghost_config thaws it and the dev burns it
+    env.send(&[repo.burn_ix(&dev.pubkey())], &[&dev])
+        .await
+        .unwrap();
+    assert!(
+        env.burned(&repo_badge(&dev.pubkey(), &padded(repo.name)))
+            .await
+    );
+
+    // An empty collection burns through its update authority
+    env.send(&[revoke_ix(&admin.pubkey(), &dev.pubkey(), &[])], &[&admin])
+        .await
+        .unwrap();
+    assert!(env.burned(&address).await);
+    assert!(!env.exists(&dev_state(&dev.pubkey())).await);
+}
@@ -0,0 +1,237 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use ghost_check::{
+    errors::GhostErrors,
+    state::{DevState, GhostConfig, VouchRecord},
+};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+// Wallet holding a dev badge of `level`
+async fn dev(env: &mut TestEnv, username: &str, level: u8) -> Keypair {
+    let wallet = env.wallet().await;
+    env.mint_dev_badge(&wallet, username, DevMetrics::level(level))
+        .await
+        .unwrap();
+    wallet
+}
+
+#[tokio::test]
+async fn vouch_for_dev() {
+    let mut env = TestEnv::new().await;
+    let voucher = dev(&mut env, "voucher", 3).await;
+    let target = dev(&mut env, "newcomer", 1).await;
+
+    let vouch = env.vouch_ix(&voucher.pubkey(), &target.pubkey());
+    env.send(std::slice::from_ref(&vouch), &[&voucher])
+        .await
+        .unwrap();
+
+    let record: VouchRecord = env
+        .account(&vouch_record(&voucher.pubkey(), &target.pubkey()))
+        .await;
+    assert_eq!(record.voucher, voucher.pubkey());
+    assert_eq!(record.target, target.pubkey());
+    assert_eq!(record.voucher_level, 3);
+
+    let target_state: DevState = env.account(&dev_state(&target.pubkey())).await;
+    assert_eq!(target_state.vouch_count, 1);
+    assert_eq!(target_state.vouch_score, 3);
+    let voucher_state: DevState = env.account(&dev_state(&voucher.pubkey())).await;
+    assert_eq!(voucher_state.vouches_given, 1);
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.vouches_count, 1);
+
+    // The vouch_record PDA only exists once per pair
+    let result = env.send(&[vouch], &[&voucher]).await;
+    assert_error(result, 0u32);
+}
+
+#[tokio::test]
+async fn vouch_requirements() {
+    let mut env = TestEnv::new().await;
+    let voucher = dev(&mut env, "voucher", 2).await;
+    let newcomer = dev(&mut env, "newcomer", 1).await;
+
+    let result = env
+        .send(
+            &[env.vouch_ix(&voucher.pubkey(), &voucher.pubkey())],
+            &[&voucher],
+        )
+        .await;
+    assert_error(result, GhostErrors::SelfVouchDenied);
+
+    // Below min_voucher_level
+    let result = env
+        .send(
+            &[env.vouch_ix(&newcomer.pubkey(), &voucher.pubkey())],
+            &[&newcomer],
+        )
+        .await;
+    assert_error(result, GhostErrors::LvlNotReached);
+
+    // Level 2 may hold VOUCH_BUDGET[1] active vouches
+    let budget = VOUCH_BUDGET[1] as usize;
+    let mut targets = vec![newcomer];
+    for i in 1..=budget {
+        targets.push(dev(&mut env, &format!("newcomer-{i}"), 1).await);
+    }
+    for target in &targets[..budget] {
+        env.send(
+            &[env.vouch_ix(&voucher.pubkey(), &target.pubkey())],
+            &[&voucher],
+        )
+        .await
+        .unwrap();
+    }
+    let extra = targets[budget].pubkey();
+    let result = env
+        .send(&[env.vouch_ix(&voucher.pubkey(), &extra)], &[&voucher])
+        .await;
+    assert_error(result, GhostErrors::VouchBudgetExhausted);
+
+    // Revoking one frees the budget again
+    let first = targets[0].pubkey();
+    env.send(
+        &[env.revoke_vouch_ix(&voucher.pubkey(), &first)],
+        &[&voucher],
+    )
+    .await
+    .unwrap();
+    env.send(&[env.vouch_ix(&voucher.pubkey(), &extra)], &[&voucher])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn revoke_vouch() {
+    let mut env = TestEnv::new().await;
+    let voucher = dev(&mut env, "voucher", 3).await;
+    let target = dev(&mut env, "newcomer", 1).await;
+    env.send(
+        &[env.vouch_ix(&voucher.pubkey(), &target.pubkey())],
+        &[&voucher],
+    )
+    .await
+    .unwrap();
+
+    env.send(
+        &[env.revoke_vouch_ix(&voucher.pubkey(), &target.pubkey())],
+        &[&voucher],
+    )
+    .await
+    .unwrap();
+    assert!(
+        !env.exists(&vouch_record(&voucher.pubkey(), &target.pubkey()))
+            .await
+    );
+    assert!(
+        env.exists(&vouch_tombstone(&voucher.pubkey(), &target.pubkey()))
+            .await
+    );
+
+    let target_state: DevState = env.account(&dev_state(&target.pubkey())).await;
+    assert_eq!(target_state.vouch_count, 0);
+    assert_eq!(target_state.vouch_score, 0);
+    let voucher_state: DevState = env.account(&dev_state(&voucher.pubkey())).await;
+    assert_eq!(voucher_state.vouches_given, 0);
+
+    // The tombstone blocks vouching for the same dev again
+    let result = env
+        .send(
+            &[env.vouch_ix(&voucher.pubkey(), &target.pubkey())],
+            &[&voucher],
+        )
+        .await;
+    assert_error(result, GhostErrors::VouchRevoked);
+}
+
+#[tokio::test]
+async fn revoke_vouch_once_the_target_is_gone() {
+    let mut env = TestEnv::new().await;
+    let voucher = dev(&mut env, "voucher", 3).await;
+    let target = dev(&mut env, "leaver", 1).await;
+    env.send(
+        &[env.vouch_ix(&voucher.pubkey(), &target.pubkey())],
+        &[&voucher],
+    )
+    .await
+    .unwrap();
+    env.send(&[burn_dev_badge_ix(&target.pubkey(), "leaver")], &[&target])
+        .await
+        .unwrap();
+
+    // The budget is freed even though there's no target state to update anymore
+    env.send(
+        &[env.revoke_vouch_ix(&voucher.pubkey(), &target.pubkey())],
+        &[&voucher],
+    )
+    .await
+    .unwrap();
+    assert!(
+        !env.exists(&vouch_record(&voucher.pubkey(), &target.pubkey()))
+            .await
+    );
+    let voucher_state: DevState = env.account(&dev_state(&voucher.pubkey())).await;
+    assert_eq!(voucher_state.vouches_given, 0);
+}
+
+#[tokio::test]
+async fn prune_vouches_of_revoked_devs() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let voucher = dev(&mut env, "sybil", 3).await;
+    let target = dev(&mut env, "newcomer", 1).await;
+    let gone = dev(&mut env, "leaver", 1).await;
+    for target in [&target, &gone] {
+        env.send(
+            &[env.vouch_ix(&voucher.pubkey(), &target.pubkey())],
+            &[&voucher],
+        )
+        .await
+        .unwrap();
+    }
+
+    let prune = prune_vouch_ix(&admin.pubkey(), &voucher.pubkey(), &target.pubkey());
+    let result = env.send(std::slice::from_ref(&prune), &[&admin]).await;
+    assert_error(result, ErrorCode::AccountNotInitialized);
+
+    env.send(
+        &[revoke_ix(&admin.pubkey(), &voucher.pubkey(), &[])],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    env.send(&[prune], &[&admin]).await.unwrap();
+    assert!(
+        !env.exists(&vouch_record(&voucher.pubkey(), &target.pubkey()))
+            .await
+    );
+    let target_state: DevState = env.account(&dev_state(&target.pubkey())).await;
+    assert_eq!(target_state.vouch_count, 0);
+    assert_eq!(target_state.vouch_score, 0);
+
+    // A target that burned its badge has nothing left to update, the record is still closed
+    env.send(&[burn_dev_badge_ix(&gone.pubkey(), "leaver")], &[&gone])
+        .await
+        .unwrap();
+    env.send(
+        &[prune_vouch_ix(
+            &admin.pubkey(),
+            &voucher.pubkey(),
+            &gone.pubkey(),
+        )],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    assert!(
+        !env.exists(&vouch_record(&voucher.pubkey(), &gone.pubkey()))
+            .await
+    );
+    let config: GhostConfig = env.account(&ghost_config()).await;
+    assert_eq!(config.vouches_count, 0);
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use ghost_check::{cpi::accounts::VerifyDev, cpi_helpers::require_dev_level, program::GhostCheck};
+
+declare_id!("3HWMD4zbptq8Kmwsq9Gk81VUAdiVSPcTnoHsxEi2Sxy2");
+
+// Example consumer of ghost_check: devs above the pool's reputation level can claim a grant once
+#[program]
+pub mod grant_gate {
+    use super::*;
+
+    pub fn init_pool(ctx: Context<InitPool>, min_lvl: u8, max_badge_age: i64) -> Result<()> {
+        ctx.accounts.pool.set_inner(GrantPool {
+            authority: ctx.accounts.authority.key(),
+            min_lvl,
+            max_badge_age,
+            claims: 0,
+            bump: ctx.bumps.pool,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_grant(ctx: Context<ClaimGrant>) -> Result<()> {
+        let accounts = &ctx.accounts;
+
+        let cpi_ctx = CpiContext::new(
+            accounts.ghost_check_program.to_account_info(),
+            VerifyDev {
+                target_dev_state: accounts.dev_state.to_account_info(),
+                revocation: accounts.revocation.to_account_info(),
+                migration: accounts.migration.to_account_info(),
+                ghost_config: accounts.ghost_config.to_account_info(),
+            },
+        );
+        let verification = require_dev_level(
+            cpi_ctx,
+            accounts.dev.key(),
+            accounts.pool.min_lvl,
+            Some(accounts.pool.max_badge_age),
+        )?;
+
+        ctx.accounts.claim.set_inner(GrantClaim {
+            dev: ctx.accounts.dev.key(),
+            reputation_level: verification.reputation_level,
+            vouch_score: verification.vouch_score,
+            claimed_at: Clock::get()?.unix_timestamp,
+            bump: ctx.bumps.claim,
+        });
+        ctx.accounts.pool.claims += 1;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = GrantPool::DISCRIMINATOR.len() + GrantPool::INIT_SPACE,
+        seeds = [b"grant_pool"],
+        bump,
+    )]
+    pub pool: Account<'info, GrantPool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimGrant<'info> {
+    #[account(mut)]
+    pub dev: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"grant_pool"],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, GrantPool>,
+
+    /// CHECK: Seeds and data are checked by ghost_check's verify_dev
+    pub dev_state: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds and data are checked by ghost_check's verify_dev
+    pub revocation: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds and data are checked by ghost_check's verify_dev
+    pub migration: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds and data are checked by ghost_check's verify_dev
+    pub ghost_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = dev,
+        space = GrantClaim::DISCRIMINATOR.len() + GrantClaim::INIT_SPACE,
+        seeds = [b"grant_claim", dev.key().as_ref()],
+        bump,
+    )]
+    pub claim: Account<'info, GrantClaim>,
+
+    pub ghost_check_program: Program<'info, GhostCheck>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct GrantPool {
+    pub authority: Pubkey,
+    pub min_lvl: u8,
+    pub max_badge_age: i64, // Seconds since the dev badge was last updated, 0 = any age
+    pub claims: u64,
+    pub bump: u8,
+}
+
+#[derive(InitSpace)]
+#[account]
+pub struct GrantClaim {
+    pub dev: Pubkey,
+    pub reputation_level: u8,
+    pub vouch_score: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,309 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GhostCheck } from "../target/types/ghost_check";
+import {
+  PublicKey,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+import crypto from "crypto";
+import nacl from "tweetnacl";
+import { expect } from "chai";
+
+// ── Helpers ──
+
+function sha256(data: Buffer | Uint8Array): Buffer {
+  return crypto.createHash("sha256").update(data).digest();
+}
+
+function u32BE(n: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32BE(n);
+  return buf;
+}
+
+function i64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64BE(BigInt(n));
+  return buf;
+}
+
+function u64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64BE(BigInt(n));
+  return buf;
+}
+
+// Default scoring table: levels 2 (Coder) to 5 (Legend)
+const SCORING_TIERS = [
+  { minRepos: 5, minCommits: 30, minAccountAgeDays: 0 },
+  { minRepos: 20, minCommits: 200, minAccountAgeDays: 120 },
+  { minRepos: 50, minCommits: 500, minAccountAgeDays: 365 },
+  { minRepos: 100, minCommits: 1500, minAccountAgeDays: 730 },
+];
+
+// ── Tests ──
+
+describe("contribution_badge", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GhostCheck as Program<GhostCheck>;
+  const payer = provider.wallet.payer;
+  const connection = provider.connection;
+
+  // Oracle registered by these tests, its signature alone meets a threshold of 1
+  const oracle = Keypair.generate();
+
+  const BPF_LOADER = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  const repoFullName = "solana-foundation/anchor";
+  const hashedRepo = sha256(Buffer.from(repoFullName.toLowerCase()));
+
+  // Oracles found on the config, put back once the suite is done
+  let previousOracles: number[][] | null = null;
+  let previousThreshold = 1;
+
+  const pda = (seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const contributionStatePda = (dev: Keypair) =>
+    pda([
+      Buffer.from("contribution_state"),
+      pda([Buffer.from("dev_badge"), dev.publicKey.toBuffer()]).toBuffer(),
+      hashedRepo,
+    ]);
+
+  function oracleIx(hashedMessage: Buffer) {
+    return Ed25519Program.createInstructionWithPublicKey({
+      publicKey: oracle.publicKey.toBytes(),
+      message: hashedMessage,
+      signature: nacl.sign.detached(hashedMessage, oracle.secretKey),
+    });
+  }
+
+  // Funded wallet holding a level 2 dev badge, every test starts from its own
+  async function freshDev(): Promise<Keypair> {
+    const dev = Keypair.generate();
+    const sig = await connection.requestAirdrop(dev.publicKey, 2e9);
+    await connection.confirmTransaction(sig);
+    await mintDevBadge(dev);
+    return dev;
+  }
+
+  async function mintDevBadge(dev: Keypair) {
+    const issuedAt = Math.floor(Date.now() / 1000);
+    const nonce = 1;
+    const reputationLevel = 2;
+    const hashedUsername = sha256(Buffer.from(dev.publicKey.toBase58()));
+    const metrics = [18, 107, 10, 50, 5, 3, 20, 365];
+    const [
+      repoCount,
+      totalCommits,
+      ownedRepoCount,
+      totalStars,
+      prsMerged,
+      issuesClosed,
+      followers,
+      accountAgeDays,
+    ] = metrics;
+
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("dev_badge"),
+        hashedUsername,
+        dev.publicKey.toBuffer(),
+        ...metrics.map(u32BE),
+        Buffer.from([reputationLevel]),
+        i64BE(issuedAt),
+        u64BE(nonce),
+      ])
+    );
+
+    const mintIx = await program.methods
+      .mintDevBadge(
+        Array.from(hashedUsername),
+        repoCount,
+        ownedRepoCount,
+        totalStars,
+        totalCommits,
+        prsMerged,
+        issuesClosed,
+        followers,
+        accountAgeDays,
+        reputationLevel,
+        new anchor.BN(issuedAt),
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        dev: dev.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(oracleIx(hashedMessage)).add(mintIx);
+    await sendAndConfirmTransaction(connection, tx, [dev]);
+  }
+
+  async function mintContributionBadge(
+    dev: Keypair,
+    repoName: string,
+    prsMerged: number,
+    commits: number
+  ) {
+    const issuedAt = Math.floor(Date.now() / 1000);
+    const nonce = 1;
+    const hashedUsername = sha256(Buffer.from(dev.publicKey.toBase58()));
+
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("contribution"),
+        hashedUsername,
+        dev.publicKey.toBuffer(),
+        Buffer.from(repoName),
+        u32BE(prsMerged),
+        u32BE(commits),
+        i64BE(issuedAt),
+        u64BE(nonce),
+      ])
+    );
+
+    const mintIx = await program.methods
+      .mintContributionBadge(
+        Array.from(hashedRepo),
+        Buffer.from(repoName),
+        Array.from(hashedUsername),
+        prsMerged,
+        commits,
+        new anchor.BN(issuedAt),
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        dev: dev.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(oracleIx(hashedMessage)).add(mintIx);
+    await sendAndConfirmTransaction(connection, tx, [dev]);
+  }
+
+  before(async () => {
+    const config = await program.account.ghostConfig.fetchNullable(
+      pda([Buffer.from("ghost_config")])
+    );
+
+    if (config === null) {
+      await program.methods
+        .initConfig(
+          Array.from(oracle.publicKey.toBytes()),
+          new anchor.BN(300),
+          2,
+          [0, 3, 5, 10, 20],
+          "http://localhost:3000"
+        )
+        .accounts({
+          admin: payer.publicKey,
+          programData: PublicKey.findProgramAddressSync(
+            [program.programId.toBuffer()],
+            BPF_LOADER
+          )[0],
+        })
+        .rpc();
+    } else {
+      previousOracles = config.oracles.map((key) => Array.from(key));
+      previousThreshold = config.oracleThreshold;
+      await program.methods
+        .setOracles([Array.from(oracle.publicKey.toBytes())], 1)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    const scoringParams = await program.account.scoringParams.fetchNullable(
+      pda([Buffer.from("scoring_params")])
+    );
+    if (scoringParams === null) {
+      await program.methods
+        .initScoringParams(SCORING_TIERS)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+  });
+
+  after(async () => {
+    if (previousOracles !== null) {
+      await program.methods
+        .setOracles(previousOracles, previousThreshold)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+  });
+
+  it("Should fail — repo name doesn't hash to the seed", async () => {
+    const dev = await freshDev();
+    try {
+      await mintContributionBadge(dev, "solana-foundation/other", 3, 12);
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("RepoHashMismatch");
+    }
+  });
+
+  it("Should fail — no merged PRs or commits", async () => {
+    const dev = await freshDev();
+    try {
+      await mintContributionBadge(dev, repoFullName, 0, 0);
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("NoContributions");
+    }
+  });
+
+  it("Mints a contribution badge in the dev collection", async () => {
+    const dev = await freshDev();
+    await mintContributionBadge(dev, repoFullName, 3, 12);
+
+    const contributionState = await program.account.contributionState.fetch(
+      contributionStatePda(dev)
+    );
+    expect(contributionState.contributor.toBase58()).to.equal(
+      dev.publicKey.toBase58()
+    );
+    expect(Buffer.from(contributionState.repoFullName).toString()).to.equal(
+      repoFullName
+    );
+    expect(contributionState.prsMerged).to.equal(3);
+    expect(contributionState.commits).to.equal(12);
+    const devState = await program.account.devState.fetch(
+      pda([Buffer.from("dev_state"), dev.publicKey.toBuffer()])
+    );
+    expect(devState.contributionBadges).to.equal(1);
+  });
+
+  it("Dev burns the contribution badge", async () => {
+    const dev = await freshDev();
+    await mintContributionBadge(dev, repoFullName, 3, 12);
+
+    await program.methods
+      .burnContributionBadge(Array.from(hashedRepo))
+      .accounts({
+        dev: dev.publicKey,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .signers([dev])
+      .rpc();
+
+    expect(
+      await program.account.contributionState.fetchNullable(
+        contributionStatePda(dev)
+      )
+    ).to.be.null;
+  });
+});
@@ -20,15 +20,34 @@ function sha256(data: Buffer | Uint8Array): Buffer {
   return crypto.createHash("sha256").update(data).digest();
 }
 
+function u16BE(n: number): Buffer {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16BE(n);
+  return buf;
+}
+
 function u32BE(n: number): Buffer {
   const buf = Buffer.alloc(4);
   buf.writeUInt32BE(n);
   return buf;
 }
 
+function i64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64BE(BigInt(n));
+  return buf;
+}
+
+function u64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64BE(BigInt(n));
+  return buf;
+}
+
 // sign dev metrics
 function signDevMetrics(
   secretKey: Uint8Array,
+  wallet: PublicKey,
   username: string,
   repoCount: number,
   totalCommits: number,
@@ -38,11 +57,15 @@ function signDevMetrics(
   issuesClosed: number,
   followers: number,
   accountAgeDays: number,
-  reputationLevel: number
+  reputationLevel: number,
+  issuedAt: number,
+  nonce: number
 ) {
   const hashedUsername = sha256(Buffer.from(username));
   const message = Buffer.concat([
+    Buffer.from("dev_badge"),
     hashedUsername,
+    wallet.toBuffer(),
     u32BE(repoCount),
     u32BE(totalCommits),
     u32BE(ownedRepoCount),
@@ -52,6 +75,8 @@ function signDevMetrics(
     u32BE(followers),
     u32BE(accountAgeDays),
     Buffer.from([reputationLevel]),
+    i64BE(issuedAt),
+    u64BE(nonce),
   ]);
   const hashedMessage = sha256(message);
   const signature = nacl.sign.detached(hashedMessage, secretKey);
@@ -61,6 +86,7 @@ function signDevMetrics(
 // Sign repo metrics
 function signRepoMetrics(
   secretKey: Uint8Array,
+  wallet: PublicKey,
   username: string,
   repoName: string,
   lang1: Buffer,
@@ -69,25 +95,40 @@ function signRepoMetrics(
   commits: number,
   forks: number,
   openIssues: number,
-  isFork: number
+  isFork: number,
+  issuedAt: number,
+  nonce: number
 ) {
   const hashedUsername = sha256(Buffer.from(username));
   const message = Buffer.concat([
+    Buffer.from("repo_badge"),
     hashedUsername,
-    Buffer.from(repoName),
-    lang1,
-    lang2,
+    wallet.toBuffer(),
+    ...[Buffer.from(repoName), lang1, lang2].flatMap((field) => [
+      u16BE(field.length),
+      field,
+    ]),
     u32BE(stars),
     u32BE(commits),
     u32BE(forks),
     u32BE(openIssues),
     Buffer.from([isFork]),
+    i64BE(issuedAt),
+    u64BE(nonce),
   ]);
   const hashedMessage = sha256(message);
   const signature = nacl.sign.detached(hashedMessage, secretKey);
   return { hashedUsername, hashedMessage, signature };
 }
 
+// Default scoring table: levels 2 (Coder) to 5 (Legend)
+const SCORING_TIERS = [
+  { minRepos: 5, minCommits: 30, minAccountAgeDays: 0 },
+  { minRepos: 20, minCommits: 200, minAccountAgeDays: 120 },
+  { minRepos: 50, minCommits: 500, minAccountAgeDays: 365 },
+  { minRepos: 100, minCommits: 1500, minAccountAgeDays: 730 },
+];
+
 // ── Tests ──
 
 describe("ghost_check", () => {
@@ -112,6 +153,11 @@ describe("ghost_check", () => {
     "BPFLoaderUpgradeab1e11111111111111111111111"
   );
   const repoNamePadded = Buffer.from("Raydium-Indexer".padEnd(32, "\0"));
+  const maxAttestationAge = new anchor.BN(300);
+  const minVoucherLevel = 2;
+  // Active vouches per voucher level 1..5
+  const vouchBudget = [0, 3, 5, 10, 20];
+  const metadataBaseUri = "http://localhost:3000";
 
   let ghostConfigPda: PublicKey;
   let devStatePda: PublicKey;
@@ -166,7 +212,13 @@ describe("ghost_check", () => {
 
   it("Initialize Config", async () => {
     const tx = await program.methods
-      .initConfig(backendPubkeyArray)
+      .initConfig(
+        backendPubkeyArray,
+        maxAttestationAge,
+        minVoucherLevel,
+        vouchBudget,
+        metadataBaseUri
+      )
       .accounts({
         admin: payer.publicKey,
         programData: programDataPda,
@@ -175,6 +227,132 @@ describe("ghost_check", () => {
     console.log("Config initialized:", tx);
   });
 
+  it("Update config", async () => {
+    await program.methods
+      .updateConfig(
+        null,
+        3,
+        null,
+        "https://api.ghostcheck.dev/",
+        new anchor.BN(60),
+        new anchor.BN(90 * 24 * 60 * 60)
+      )
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+
+    const config = await program.account.ghostConfig.fetch(ghostConfigPda);
+    expect(config.minVoucherLevel).to.equal(3);
+    expect(config.maxAttestationAge.toNumber()).to.equal(300);
+    expect(config.metadataBaseUri).to.equal("https://api.ghostcheck.dev/");
+    expect(config.minUpdateInterval.toNumber()).to.equal(60);
+    expect(config.maxStaleness.toNumber()).to.equal(90 * 24 * 60 * 60);
+
+    try {
+      await program.methods
+        .updateConfig(null, null, null, null, new anchor.BN(-1), null)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("InvalidFreshnessConfig");
+    }
+
+    await program.methods
+      .updateConfig(
+        null,
+        minVoucherLevel,
+        null,
+        metadataBaseUri,
+        new anchor.BN(0),
+        new anchor.BN(0)
+      )
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+  });
+
+  it("Initialize scoring params", async () => {
+    await program.methods
+      .initScoringParams(SCORING_TIERS)
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+
+    const scoringParams = await program.account.scoringParams.fetch(
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("scoring_params")],
+        program.programId
+      )[0]
+    );
+    expect(scoringParams.tiers[3].minCommits).to.equal(1500);
+  });
+
+  it("Should fail — scoring tiers that decrease", async () => {
+    const tiers = SCORING_TIERS.map((tier) => ({ ...tier }));
+    tiers[2].minRepos = 10;
+
+    try {
+      await program.methods
+        .updateScoringParams(tiers)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("InvalidScoringParams");
+    }
+  });
+
+  it("Pause and unpause the protocol", async () => {
+    await program.methods
+      .setPaused(true)
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+    expect((await program.account.ghostConfig.fetch(ghostConfigPda)).paused).to
+      .be.true;
+
+    await program.methods
+      .setPaused(false)
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+    expect((await program.account.ghostConfig.fetch(ghostConfigPda)).paused).to
+      .be.false;
+  });
+
+  it("Two-step admin transfer", async () => {
+    await program.methods
+      .proposeAdmin(user2.publicKey)
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: payer.publicKey })
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("PendingAdminMismatch");
+    }
+
+    await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: user2.publicKey })
+      .signers([user2])
+      .rpc();
+    expect(
+      (await program.account.ghostConfig.fetch(ghostConfigPda)).admin.toBase58()
+    ).to.equal(user2.publicKey.toBase58());
+
+    // Hand the admin back for the rest of the suite
+    await program.methods
+      .proposeAdmin(payer.publicKey)
+      .accounts({ admin: user2.publicKey })
+      .signers([user2])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ newAdmin: payer.publicKey })
+      .rpc();
+  });
+
   // it("Should fail — unauthorized init_config", async () => {
   //   const fake = Keypair.generate();
   //   const sig = await connection.requestAirdrop(fake.publicKey, 1e9);
@@ -0,0 +1,425 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GhostCheck } from "../target/types/ghost_check";
+import { GrantGate } from "../target/types/grant_gate";
+import {
+  PublicKey,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+import crypto from "crypto";
+import nacl from "tweetnacl";
+import { expect } from "chai";
+
+// ── Helpers ──
+
+function sha256(data: Buffer | Uint8Array): Buffer {
+  return crypto.createHash("sha256").update(data).digest();
+}
+
+function u32BE(n: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32BE(n);
+  return buf;
+}
+
+function i64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64BE(BigInt(n));
+  return buf;
+}
+
+function u64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64BE(BigInt(n));
+  return buf;
+}
+
+// Default scoring table: levels 2 (Coder) to 5 (Legend)
+const SCORING_TIERS = [
+  { minRepos: 5, minCommits: 30, minAccountAgeDays: 0 },
+  { minRepos: 20, minCommits: 200, minAccountAgeDays: 120 },
+  { minRepos: 50, minCommits: 500, minAccountAgeDays: 365 },
+  { minRepos: 100, minCommits: 1500, minAccountAgeDays: 730 },
+];
+
+// Merged PRs attested for every dev, the criteria test checks its threshold around it
+const PRS_MERGED = 5;
+
+// Attested metrics scoring exactly `level` under SCORING_TIERS: the minimums of its tier,
+// or one short of the level 2 tier for level 1
+function metricsForLevel(level: number): number[] {
+  const tier = SCORING_TIERS[Math.max(level, 2) - 2];
+  const [repos, commits, accountAgeDays] =
+    level === 1
+      ? [tier.minRepos - 1, tier.minCommits - 1, tier.minAccountAgeDays]
+      : [tier.minRepos, tier.minCommits, tier.minAccountAgeDays];
+  // repo_count, total_commits, owned_repo_count, total_stars, prs_merged,
+  // issues_closed, followers, account_age_days
+  return [repos, commits, repos, 0, PRS_MERGED, 0, 0, accountAgeDays];
+}
+
+const METRICS_BY_LEVEL: Record<number, number[]> = {
+  1: metricsForLevel(1),
+  5: metricsForLevel(5),
+};
+
+// ── Tests ──
+
+describe("grant_gate", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const ghostCheck = anchor.workspace.GhostCheck as Program<GhostCheck>;
+  const grantGate = anchor.workspace.GrantGate as Program<GrantGate>;
+  const payer = provider.wallet.payer;
+  const connection = provider.connection;
+
+  // Oracle registered by these tests, its signature alone meets a threshold of 1
+  const oracle = Keypair.generate();
+  const legend = Keypair.generate();
+  const newbie = Keypair.generate();
+
+  const BPF_LOADER = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  const MIN_LVL = 3;
+  const MAX_BADGE_AGE = new anchor.BN(30 * 24 * 60 * 60);
+
+  const pda = (seeds: Buffer[], programId: PublicKey) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  const verifyDevAccounts = (dev: PublicKey) => ({
+    targetDevState: pda(
+      [Buffer.from("dev_state"), dev.toBuffer()],
+      ghostCheck.programId
+    ),
+    revocation: pda(
+      [Buffer.from("revocation"), dev.toBuffer()],
+      ghostCheck.programId
+    ),
+    migration: pda(
+      [Buffer.from("migration"), dev.toBuffer()],
+      ghostCheck.programId
+    ),
+  });
+
+  // Mints the dev badge, or updates it with a later nonce when `update` is set
+  async function attestDevBadge(
+    dev: Keypair,
+    username: string,
+    reputationLevel: number,
+    metrics = METRICS_BY_LEVEL[reputationLevel],
+    nonce = 1,
+    update = false
+  ) {
+    const issuedAt = Math.floor(Date.now() / 1000);
+    const [
+      repoCount,
+      totalCommits,
+      ownedRepoCount,
+      totalStars,
+      prsMerged,
+      issuesClosed,
+      followers,
+      accountAgeDays,
+    ] = metrics;
+
+    const hashedUsername = sha256(Buffer.from(username));
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("dev_badge"),
+        hashedUsername,
+        dev.publicKey.toBuffer(),
+        ...metrics.map(u32BE),
+        Buffer.from([reputationLevel]),
+        i64BE(issuedAt),
+        u64BE(nonce),
+      ])
+    );
+
+    const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
+      publicKey: oracle.publicKey.toBytes(),
+      message: hashedMessage,
+      signature: nacl.sign.detached(hashedMessage, oracle.secretKey),
+    });
+
+    const attestIx = await ghostCheck.methods[
+      update ? "updateDevBadge" : "mintDevBadge"
+    ](
+      Array.from(hashedUsername),
+      repoCount,
+      ownedRepoCount,
+      totalStars,
+      totalCommits,
+      prsMerged,
+      issuesClosed,
+      followers,
+      accountAgeDays,
+      reputationLevel,
+      new anchor.BN(issuedAt),
+      new anchor.BN(nonce)
+    )
+      .accounts({
+        dev: dev.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(ed25519Ix).add(attestIx);
+    await sendAndConfirmTransaction(connection, tx, [dev]);
+  }
+
+  function claimGrant(dev: Keypair) {
+    const { targetDevState, revocation, migration } = verifyDevAccounts(
+      dev.publicKey
+    );
+
+    return grantGate.methods
+      .claimGrant()
+      .accounts({
+        dev: dev.publicKey,
+        devState: targetDevState,
+        revocation,
+        migration,
+        ghostConfig: pda([Buffer.from("ghost_config")], ghostCheck.programId),
+      })
+      .signers([dev])
+      .rpc();
+  }
+
+  before(async () => {
+    const ghostConfigPda = pda(
+      [Buffer.from("ghost_config")],
+      ghostCheck.programId
+    );
+    const config = await ghostCheck.account.ghostConfig.fetchNullable(
+      ghostConfigPda
+    );
+
+    if (config === null) {
+      await ghostCheck.methods
+        .initConfig(
+          Array.from(oracle.publicKey.toBytes()),
+          new anchor.BN(300),
+          2,
+          [0, 3, 5, 10, 20],
+          "http://localhost:3000"
+        )
+        .accounts({
+          admin: payer.publicKey,
+          programData: pda([ghostCheck.programId.toBuffer()], BPF_LOADER),
+        })
+        .rpc();
+    } else {
+      await ghostCheck.methods
+        .setOracles([Array.from(oracle.publicKey.toBytes())], 1)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    const scoringParams = await ghostCheck.account.scoringParams.fetchNullable(
+      pda([Buffer.from("scoring_params")], ghostCheck.programId)
+    );
+    if (scoringParams === null) {
+      await ghostCheck.methods
+        .initScoringParams(SCORING_TIERS)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    for (const dev of [legend, newbie]) {
+      const sig = await connection.requestAirdrop(dev.publicKey, 2e9);
+      await connection.confirmTransaction(sig);
+    }
+
+    await attestDevBadge(legend, "grant-gate-legend", 5);
+    await attestDevBadge(newbie, "grant-gate-newbie", 1);
+
+    await grantGate.methods
+      .initPool(MIN_LVL, MAX_BADGE_AGE)
+      .accounts({ authority: payer.publicKey })
+      .rpc();
+  });
+
+  it("Should fail — attested level not backed by the metrics", async () => {
+    const pretender = Keypair.generate();
+    const sig = await connection.requestAirdrop(pretender.publicKey, 2e9);
+    await connection.confirmTransaction(sig);
+
+    try {
+      await attestDevBadge(
+        pretender,
+        "grant-gate-pretender",
+        5,
+        METRICS_BY_LEVEL[1]
+      );
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("ReputationLevelMismatch");
+    }
+  });
+
+  it("verify_dev returns the dev reputation", async () => {
+    const verification = await ghostCheck.methods
+      .verifyDev(Array.from(legend.publicKey.toBytes()), MIN_LVL, null)
+      .accounts(verifyDevAccounts(legend.publicKey))
+      .view();
+
+    expect(verification.devAddr.toBase58()).to.equal(
+      legend.publicKey.toBase58()
+    );
+    expect(verification.reputationLevel).to.equal(5);
+    expect(verification.revoked).to.equal(false);
+  });
+
+  it("verify_dev_criteria reports the failed criteria", async () => {
+    const criteria = {
+      minLevel: 5,
+      minPrsMerged: PRS_MERGED,
+      minStars: null,
+      minVouchCount: null,
+      minAccountAgeDays: 365,
+      minVerifiedRepos: null,
+      maxStaleness: new anchor.BN(3600),
+    };
+
+    const result = await ghostCheck.methods
+      .verifyDevCriteria(Array.from(legend.publicKey.toBytes()), criteria)
+      .accounts(verifyDevAccounts(legend.publicKey))
+      .view();
+    expect(result.checked).to.equal(0b1010011);
+    expect(result.passed).to.equal(0b1010011);
+
+    const unmet = {
+      ...criteria,
+      minPrsMerged: PRS_MERGED + 1,
+      minVouchCount: new anchor.BN(1),
+    };
+    const query = await ghostCheck.methods
+      .queryDevCriteria(Array.from(legend.publicKey.toBytes()), unmet)
+      .accounts(verifyDevAccounts(legend.publicKey))
+      .view();
+    expect(query.checked).to.equal(0b1011011);
+    expect(query.passed).to.equal(0b1010001);
+
+    try {
+      await ghostCheck.methods
+        .verifyDevCriteria(Array.from(legend.publicKey.toBytes()), unmet)
+        .accounts(verifyDevAccounts(legend.publicKey))
+        .view();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.not.equal("Should have failed");
+      expect(e.logs.join("\n")).to.include("failed bitmap: 0b0001010");
+    }
+  });
+
+  it("Claims a grant through the CPI gate", async () => {
+    await claimGrant(legend);
+
+    const claim = await grantGate.account.grantClaim.fetch(
+      pda(
+        [Buffer.from("grant_claim"), legend.publicKey.toBuffer()],
+        grantGate.programId
+      )
+    );
+    expect(claim.dev.toBase58()).to.equal(legend.publicKey.toBase58());
+    expect(claim.reputationLevel).to.equal(5);
+  });
+
+  it("Should fail — dev below the pool level", async () => {
+    try {
+      await claimGrant(newbie);
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("DevVerificationFailed");
+    }
+  });
+
+  it("Should fail — dev badge updated again inside the cooldown", async () => {
+    await ghostCheck.methods
+      .updateConfig(null, null, null, null, new anchor.BN(3600), null)
+      .accounts({ admin: payer.publicKey })
+      .rpc();
+
+    try {
+      await attestDevBadge(
+        newbie,
+        "grant-gate-newbie",
+        1,
+        METRICS_BY_LEVEL[1],
+        2,
+        true
+      );
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("UpdateCooldown");
+    } finally {
+      await ghostCheck.methods
+        .updateConfig(null, null, null, null, new anchor.BN(0), null)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+  });
+
+  it("Should fail — badge older than the requested max staleness", async () => {
+    // Let the clock move past the 1 second override
+    await new Promise((resolve) => setTimeout(resolve, 2500));
+
+    try {
+      await ghostCheck.methods
+        .verifyDev(
+          Array.from(legend.publicKey.toBytes()),
+          MIN_LVL,
+          new anchor.BN(1)
+        )
+        .accounts(verifyDevAccounts(legend.publicKey))
+        .view();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.not.equal("Should have failed");
+      expect(e.logs.join("\n")).to.include("StaleBadge");
+    }
+
+    // 0 accepts any age, whatever the global setting
+    const verification = await ghostCheck.methods
+      .verifyDev(
+        Array.from(legend.publicKey.toBytes()),
+        MIN_LVL,
+        new anchor.BN(0)
+      )
+      .accounts(verifyDevAccounts(legend.publicKey))
+      .view();
+    expect(verification.reputationLevel).to.equal(5);
+  });
+
+  it("Should fail — revoked dev", async () => {
+    await ghostCheck.methods
+      .revokeDevBadge(Array.from(newbie.publicKey.toBytes()), 1)
+      .accounts({
+        admin: payer.publicKey,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .rpc();
+
+    // query_dev reports the revocation instead of failing
+    const verification = await ghostCheck.methods
+      .queryDev(Array.from(newbie.publicKey.toBytes()))
+      .accounts(verifyDevAccounts(newbie.publicKey))
+      .view();
+    expect(verification.revoked).to.equal(true);
+
+    try {
+      await claimGrant(newbie);
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("DevRevoked");
+    }
+  });
+});
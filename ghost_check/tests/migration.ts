@@ -0,0 +1,311 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GhostCheck } from "../target/types/ghost_check";
+import {
+  PublicKey,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+import crypto from "crypto";
+import nacl from "tweetnacl";
+import { expect } from "chai";
+
+// ── Helpers ──
+
+function sha256(data: Buffer | Uint8Array): Buffer {
+  return crypto.createHash("sha256").update(data).digest();
+}
+
+function u32BE(n: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32BE(n);
+  return buf;
+}
+
+function i64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64BE(BigInt(n));
+  return buf;
+}
+
+function u64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64BE(BigInt(n));
+  return buf;
+}
+
+// Default scoring table: levels 2 (Coder) to 5 (Legend)
+const SCORING_TIERS = [
+  { minRepos: 5, minCommits: 30, minAccountAgeDays: 0 },
+  { minRepos: 20, minCommits: 200, minAccountAgeDays: 120 },
+  { minRepos: 50, minCommits: 500, minAccountAgeDays: 365 },
+  { minRepos: 100, minCommits: 1500, minAccountAgeDays: 730 },
+];
+
+// ── Tests ──
+
+describe("migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GhostCheck as Program<GhostCheck>;
+  const payer = provider.wallet.payer;
+  const connection = provider.connection;
+
+  // Oracle registered by these tests, its signature alone meets a threshold of 1
+  const oracle = Keypair.generate();
+  const oldWallet = Keypair.generate();
+  const newWallet = Keypair.generate();
+
+  const BPF_LOADER = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  const username = "migrating-dev";
+  const hashedUsername = sha256(Buffer.from(username));
+
+  const pda = (seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  function oracleIx(hashedMessage: Buffer) {
+    return Ed25519Program.createInstructionWithPublicKey({
+      publicKey: oracle.publicKey.toBytes(),
+      message: hashedMessage,
+      signature: nacl.sign.detached(hashedMessage, oracle.secretKey),
+    });
+  }
+
+  async function mintDevBadge(dev: Keypair, reputationLevel: number) {
+    const issuedAt = Math.floor(Date.now() / 1000);
+    const nonce = 1;
+    const metrics = [18, 107, 10, 50, 5, 3, 20, 365];
+    const [
+      repoCount,
+      totalCommits,
+      ownedRepoCount,
+      totalStars,
+      prsMerged,
+      issuesClosed,
+      followers,
+      accountAgeDays,
+    ] = metrics;
+
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("dev_badge"),
+        hashedUsername,
+        dev.publicKey.toBuffer(),
+        ...metrics.map(u32BE),
+        Buffer.from([reputationLevel]),
+        i64BE(issuedAt),
+        u64BE(nonce),
+      ])
+    );
+
+    const mintIx = await program.methods
+      .mintDevBadge(
+        Array.from(hashedUsername),
+        repoCount,
+        ownedRepoCount,
+        totalStars,
+        totalCommits,
+        prsMerged,
+        issuesClosed,
+        followers,
+        accountAgeDays,
+        reputationLevel,
+        new anchor.BN(issuedAt),
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        dev: dev.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(oracleIx(hashedMessage)).add(mintIx);
+    await sendAndConfirmTransaction(connection, tx, [dev]);
+  }
+
+  async function requestMigration(issuedAt: number) {
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("identity_migration"),
+        hashedUsername,
+        oldWallet.publicKey.toBuffer(),
+        newWallet.publicKey.toBuffer(),
+        i64BE(issuedAt),
+      ])
+    );
+
+    const requestIx = await program.methods
+      .requestMigration(Array.from(hashedUsername), new anchor.BN(issuedAt))
+      .accounts({
+        newWallet: newWallet.publicKey,
+        oldWallet: oldWallet.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(oracleIx(hashedMessage)).add(requestIx);
+    await sendAndConfirmTransaction(connection, tx, [newWallet]);
+  }
+
+  function migrateDevIdentity() {
+    return program.methods
+      .migrateDevIdentity()
+      .accounts({
+        newWallet: newWallet.publicKey,
+        oldWallet: oldWallet.publicKey,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .signers([newWallet])
+      .rpc();
+  }
+
+  before(async () => {
+    const config = await program.account.ghostConfig.fetchNullable(
+      pda([Buffer.from("ghost_config")])
+    );
+
+    if (config === null) {
+      await program.methods
+        .initConfig(
+          Array.from(oracle.publicKey.toBytes()),
+          new anchor.BN(300),
+          2,
+          [0, 3, 5, 10, 20],
+          "http://localhost:3000"
+        )
+        .accounts({
+          admin: payer.publicKey,
+          programData: PublicKey.findProgramAddressSync(
+            [program.programId.toBuffer()],
+            BPF_LOADER
+          )[0],
+        })
+        .rpc();
+    } else {
+      await program.methods
+        .setOracles([Array.from(oracle.publicKey.toBytes())], 1)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    const scoringParams = await program.account.scoringParams.fetchNullable(
+      pda([Buffer.from("scoring_params")])
+    );
+    if (scoringParams === null) {
+      await program.methods
+        .initScoringParams(SCORING_TIERS)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    for (const wallet of [oldWallet, newWallet]) {
+      const sig = await connection.requestAirdrop(wallet.publicKey, 2e9);
+      await connection.confirmTransaction(sig);
+    }
+
+    await mintDevBadge(oldWallet, 2);
+  });
+
+  it("Should fail — migration still timelocked", async () => {
+    await requestMigration(Math.floor(Date.now() / 1000));
+
+    try {
+      await migrateDevIdentity();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("MigrationLocked");
+    }
+  });
+
+  it("Old wallet cancels and the migration is requested again", async () => {
+    await program.methods
+      .cancelMigration()
+      .accounts({
+        oldWallet: oldWallet.publicKey,
+        newWallet: newWallet.publicKey,
+      })
+      .signers([oldWallet])
+      .rpc();
+
+    const migrationPda = pda([
+      Buffer.from("migration"),
+      oldWallet.publicKey.toBuffer(),
+    ]);
+    expect(await program.account.migrationRequest.fetchNullable(migrationPda))
+      .to.be.null;
+
+    // Another issued_at, the first request transaction may share the blockhash
+    await requestMigration(Math.floor(Date.now() / 1000) - 1);
+  });
+
+  it("Migrates the dev identity once the old wallet approves", async () => {
+    await program.methods
+      .approveMigration()
+      .accounts({ oldWallet: oldWallet.publicKey })
+      .signers([oldWallet])
+      .rpc();
+
+    await migrateDevIdentity();
+
+    const identityClaim = pda([Buffer.from("identity_claim"), hashedUsername]);
+    await program.methods
+      .finishMigration()
+      .accounts({
+        newWallet: newWallet.publicKey,
+        oldWallet: oldWallet.publicKey,
+        identityClaim,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .signers([newWallet])
+      .rpc();
+
+    const claim = await program.account.identityClaim.fetch(identityClaim);
+    expect(claim.wallet.toBase58()).to.equal(newWallet.publicKey.toBase58());
+
+    const newDevState = await program.account.devState.fetch(
+      pda([Buffer.from("dev_state"), newWallet.publicKey.toBuffer()])
+    );
+    expect(newDevState.reputationLevel).to.equal(2);
+    expect(Buffer.from(newDevState.hashedUsername)).to.deep.equal(
+      hashedUsername
+    );
+
+    const oldDevState = await program.account.devState.fetchNullable(
+      pda([Buffer.from("dev_state"), oldWallet.publicKey.toBuffer()])
+    );
+    expect(oldDevState).to.be.null;
+
+    const migration = await program.account.migrationRequest.fetch(
+      pda([Buffer.from("migration"), oldWallet.publicKey.toBuffer()])
+    );
+    expect(migration.completed).to.be.true;
+  });
+
+  it("Should fail — migrate_account on an up-to-date layout", async () => {
+    const devStatePda = pda([
+      Buffer.from("dev_state"),
+      newWallet.publicKey.toBuffer(),
+    ]);
+    expect((await program.account.devState.fetch(devStatePda)).version).to.equal(
+      1
+    );
+
+    try {
+      await program.methods
+        .migrateAccount()
+        .accounts({ payer: payer.publicKey, account: devStatePda })
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("AccountUpToDate");
+    }
+  });
+});
@@ -0,0 +1,278 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { GhostCheck } from "../target/types/ghost_check";
+import {
+  PublicKey,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+import crypto from "crypto";
+import nacl from "tweetnacl";
+import { expect } from "chai";
+
+// ── Helpers ──
+
+function sha256(data: Buffer | Uint8Array): Buffer {
+  return crypto.createHash("sha256").update(data).digest();
+}
+
+function u32BE(n: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32BE(n);
+  return buf;
+}
+
+function i64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64BE(BigInt(n));
+  return buf;
+}
+
+function u64BE(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64BE(BigInt(n));
+  return buf;
+}
+
+// Default scoring table: levels 2 (Coder) to 5 (Legend)
+const SCORING_TIERS = [
+  { minRepos: 5, minCommits: 30, minAccountAgeDays: 0 },
+  { minRepos: 20, minCommits: 200, minAccountAgeDays: 120 },
+  { minRepos: 50, minCommits: 500, minAccountAgeDays: 365 },
+  { minRepos: 100, minCommits: 1500, minAccountAgeDays: 730 },
+];
+
+// ── Tests ──
+
+describe("org_badge", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.GhostCheck as Program<GhostCheck>;
+  const payer = provider.wallet.payer;
+  const connection = provider.connection;
+
+  // Oracle registered by these tests, its signature alone meets a threshold of 1
+  const oracle = Keypair.generate();
+  const orgAdmin = Keypair.generate();
+  const member = Keypair.generate();
+  const outsider = Keypair.generate();
+
+  const BPF_LOADER = new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  );
+  const hashedOrg = sha256(Buffer.from("ghostcheck-org"));
+
+  const pda = (seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const orgStatePda = pda([Buffer.from("org_state"), hashedOrg]);
+  const orgBadgePda = pda([Buffer.from("org_badge"), hashedOrg]);
+
+  function oracleIx(hashedMessage: Buffer) {
+    return Ed25519Program.createInstructionWithPublicKey({
+      publicKey: oracle.publicKey.toBytes(),
+      message: hashedMessage,
+      signature: nacl.sign.detached(hashedMessage, oracle.secretKey),
+    });
+  }
+
+  async function mintDevBadge(dev: Keypair) {
+    const issuedAt = Math.floor(Date.now() / 1000);
+    const nonce = 1;
+    const reputationLevel = 2;
+    const hashedUsername = sha256(Buffer.from(dev.publicKey.toBase58()));
+    const metrics = [18, 107, 10, 50, 5, 3, 20, 365];
+    const [
+      repoCount,
+      totalCommits,
+      ownedRepoCount,
+      totalStars,
+      prsMerged,
+      issuesClosed,
+      followers,
+      accountAgeDays,
+    ] = metrics;
+
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("dev_badge"),
+        hashedUsername,
+        dev.publicKey.toBuffer(),
+        ...metrics.map(u32BE),
+        Buffer.from([reputationLevel]),
+        i64BE(issuedAt),
+        u64BE(nonce),
+      ])
+    );
+
+    const mintIx = await program.methods
+      .mintDevBadge(
+        Array.from(hashedUsername),
+        repoCount,
+        ownedRepoCount,
+        totalStars,
+        totalCommits,
+        prsMerged,
+        issuesClosed,
+        followers,
+        accountAgeDays,
+        reputationLevel,
+        new anchor.BN(issuedAt),
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        dev: dev.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(oracleIx(hashedMessage)).add(mintIx);
+    await sendAndConfirmTransaction(connection, tx, [dev]);
+  }
+
+  function linkOrgMember(dev: Keypair, authority: Keypair) {
+    return program.methods
+      .linkOrgMember()
+      .accounts({
+        dev: dev.publicKey,
+        authority: authority.publicKey,
+        orgState: orgStatePda,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .signers([dev, authority])
+      .rpc();
+  }
+
+  before(async () => {
+    const config = await program.account.ghostConfig.fetchNullable(
+      pda([Buffer.from("ghost_config")])
+    );
+
+    if (config === null) {
+      await program.methods
+        .initConfig(
+          Array.from(oracle.publicKey.toBytes()),
+          new anchor.BN(300),
+          2,
+          [0, 3, 5, 10, 20],
+          "http://localhost:3000"
+        )
+        .accounts({
+          admin: payer.publicKey,
+          programData: PublicKey.findProgramAddressSync(
+            [program.programId.toBuffer()],
+            BPF_LOADER
+          )[0],
+        })
+        .rpc();
+    } else {
+      await program.methods
+        .setOracles([Array.from(oracle.publicKey.toBytes())], 1)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    const scoringParams = await program.account.scoringParams.fetchNullable(
+      pda([Buffer.from("scoring_params")])
+    );
+    if (scoringParams === null) {
+      await program.methods
+        .initScoringParams(SCORING_TIERS)
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+    }
+
+    for (const wallet of [orgAdmin, member, outsider]) {
+      const sig = await connection.requestAirdrop(wallet.publicKey, 2e9);
+      await connection.confirmTransaction(sig);
+    }
+
+    await mintDevBadge(member);
+  });
+
+  it("Org admin mints the org badge", async () => {
+    const issuedAt = Math.floor(Date.now() / 1000);
+    const nonce = 1;
+    const [publicRepos, totalStars, memberCount] = [12, 340, 8];
+
+    const hashedMessage = sha256(
+      Buffer.concat([
+        Buffer.from("org_badge"),
+        hashedOrg,
+        orgAdmin.publicKey.toBuffer(),
+        u32BE(publicRepos),
+        u32BE(totalStars),
+        u32BE(memberCount),
+        i64BE(issuedAt),
+        u64BE(nonce),
+      ])
+    );
+
+    const mintIx = await program.methods
+      .mintOrgBadge(
+        Array.from(hashedOrg),
+        publicRepos,
+        totalStars,
+        memberCount,
+        new anchor.BN(issuedAt),
+        new anchor.BN(nonce)
+      )
+      .accounts({
+        authority: orgAdmin.publicKey,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .instruction();
+
+    const tx = new Transaction().add(oracleIx(hashedMessage)).add(mintIx);
+    await sendAndConfirmTransaction(connection, tx, [orgAdmin]);
+
+    const orgState = await program.account.orgState.fetch(orgStatePda);
+    expect(orgState.authority.toBase58()).to.equal(
+      orgAdmin.publicKey.toBase58()
+    );
+    expect(orgState.orgBadge.toBase58()).to.equal(orgBadgePda.toBase58());
+    expect(orgState.totalStars).to.equal(totalStars);
+    expect(orgState.linkedMembers).to.equal(0);
+  });
+
+  it("Should fail — link co-signed by someone other than the org admin", async () => {
+    try {
+      await linkOrgMember(member, outsider);
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("OrgAuthorityMismatch");
+    }
+  });
+
+  it("Links a dev badge holder to the org", async () => {
+    await linkOrgMember(member, orgAdmin);
+
+    const membership = await program.account.orgMembership.fetch(
+      pda([
+        Buffer.from("org_member"),
+        orgBadgePda.toBuffer(),
+        member.publicKey.toBuffer(),
+      ])
+    );
+    expect(membership.dev.toBase58()).to.equal(member.publicKey.toBase58());
+
+    const orgState = await program.account.orgState.fetch(orgStatePda);
+    expect(orgState.linkedMembers).to.equal(1);
+  });
+
+  it("Should fail — the same dev links twice", async () => {
+    try {
+      await linkOrgMember(member, orgAdmin);
+      throw new Error("Should have failed");
+    } catch (e) {
+      expect(e.message).to.include("already in use");
+    }
+  });
+});